@@ -0,0 +1,76 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Benchmarks of proving circuits made of many lookups, whose cost is
+//! dominated by the sorted multiset and the plookup accumulator.
+//!
+//! Every circuit appends the table of
+//! [`PlookupTable4Arity::create_xor_table`], and queries it once per pair
+//! of random inputs. Run with `--features parallel` to compare against the
+//! single threaded construction.
+
+use criterion::{
+    criterion_group, criterion_main, BenchmarkId, Criterion, Throughput,
+};
+use dusk_plonk::plookup::PlookupTable4Arity;
+use dusk_plonk::prelude::*;
+use rand_core::OsRng;
+use rand_core::RngCore;
+
+/// Number of bits of the inputs of the xor table, which has `4^BITS` rows
+const BITS: u32 = 5;
+
+/// Numbers of lookups of the circuits
+const LOOKUPS: [usize; 3] = [256, 1024, 4096];
+
+fn lookups(
+    composer: &mut StandardComposer,
+    table: &PlookupTable4Arity,
+    n: usize,
+) {
+    composer.append_lookup_table(table);
+    (0..n).for_each(|_| {
+        let a = BlsScalar::from(OsRng.next_u64() % (1 << BITS));
+        let b = BlsScalar::from(OsRng.next_u64() % (1 << BITS));
+        let a = composer.add_input(a);
+        let b = composer.add_input(b);
+        composer.plookup_query(a, b, table);
+    });
+}
+
+fn plookup_benches(c: &mut Criterion) {
+    let table = PlookupTable4Arity::create_xor_table(BITS).unwrap();
+
+    let mut group = c.benchmark_group("plookup_prove");
+    group.sample_size(10);
+    LOOKUPS.iter().for_each(|n| {
+        let mut prover = Prover::new(b"plookup-bench");
+        lookups(prover.mut_cs(), &table, *n);
+        let size = prover.mut_cs().total_size().next_power_of_two();
+        let pub_params = PublicParameters::setup(2 * size, &mut OsRng)
+            .expect("failed to setup the public parameters");
+        let (ck, _) = pub_params
+            .trim(2 * size)
+            .expect("failed to trim the public parameters");
+        prover
+            .preprocess(&ck)
+            .expect("failed to preprocess the circuit");
+        let prover_key = prover.prover_key.clone().unwrap();
+
+        group.throughput(Throughput::Elements(*n as u64));
+        group.bench_function(BenchmarkId::new("lookups", n), |b| {
+            b.iter(|| {
+                prover
+                    .prove_with_preprocessed(&ck, &prover_key)
+                    .expect("failed to prove the circuit")
+            })
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, plookup_benches);
+criterion_main!(benches);
@@ -0,0 +1,130 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Benchmarks of the Zelbet gadgets, measuring both the time spent building
+//! the gates and the time spent proving the resulting circuits.
+//!
+//! Every circuit appends the table of
+//! [`PlookupTable4Arity::create_hash_table`]. The number of gates of each
+//! circuit is part of the parameter of its benchmark ids, and the
+//! throughputs are given in gates.
+
+use criterion::{
+    criterion_group, criterion_main, BenchmarkId, Criterion, Throughput,
+};
+use dusk_plonk::plookup::table::hash_tables::SboxTable;
+use dusk_plonk::plookup::PlookupTable4Arity;
+use dusk_plonk::prelude::*;
+use rand_core::OsRng;
+
+/// Numbers of inputs of the sponge hashes
+const SPONGE_INPUTS: [usize; 4] = [1, 2, 4, 8];
+
+/// Number of gadgets appended to each circuit of the decomposition and
+/// s-box benchmarks
+const GADGETS: usize = 8;
+
+fn decomposition(composer: &mut StandardComposer, inputs: &[BlsScalar]) {
+    composer.append_lookup_table(&PlookupTable4Arity::create_hash_table());
+    let s_i_decomposition = composer.s_i_decomposition_vars();
+    inputs.iter().for_each(|input| {
+        let x = composer.add_input(*input);
+        composer.decomposition_gadget(x, s_i_decomposition);
+    });
+}
+
+fn s_box(composer: &mut StandardComposer, nibbles: &[BlsScalar]) {
+    composer.append_lookup_table(&PlookupTable4Arity::create_hash_table());
+    nibbles.iter().for_each(|nibble| {
+        let x = composer.add_input(*nibble);
+        composer.s_box_and_constraints(x, 27, false, &SboxTable::default());
+    });
+}
+
+fn sponge_hash(composer: &mut StandardComposer, inputs: &[BlsScalar]) {
+    composer.append_lookup_table(&PlookupTable4Arity::create_hash_table());
+    let inputs: Vec<Variable> = inputs
+        .iter()
+        .map(|input| composer.add_input(*input))
+        .collect();
+    composer.zelbet_sponge_hash(&inputs);
+}
+
+fn random_scalars(n: usize) -> Vec<BlsScalar> {
+    (0..n).map(|_| BlsScalar::random(&mut OsRng)).collect()
+}
+
+/// Benchmarks building the circuit of `gadget` over `inputs`, then proving
+/// it once it's preprocessed. The benchmarks are parametrized by `id` and
+/// the number of gates of the circuit.
+fn bench_gadget(
+    c: &mut Criterion,
+    name: &str,
+    gadget: fn(&mut StandardComposer, &[BlsScalar]),
+    inputs: &[BlsScalar],
+    id: usize,
+) {
+    let mut composer = StandardComposer::new();
+    gadget(&mut composer, inputs);
+    let gates = composer.circuit_size();
+    let parameter = format!("{}/{}_gates", id, gates);
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(gates as u64));
+    group.bench_function(BenchmarkId::new("gates", &parameter), |b| {
+        b.iter(|| {
+            let mut composer = StandardComposer::new();
+            gadget(&mut composer, inputs);
+            composer
+        })
+    });
+
+    let mut prover = Prover::new(b"zelbet-bench");
+    gadget(prover.mut_cs(), inputs);
+    let size = prover.mut_cs().total_size().next_power_of_two();
+    let pub_params = PublicParameters::setup(2 * size, &mut OsRng)
+        .expect("failed to setup the public parameters");
+    let (ck, _) = pub_params
+        .trim(2 * size)
+        .expect("failed to trim the public parameters");
+    prover
+        .preprocess(&ck)
+        .expect("failed to preprocess the circuit");
+    let prover_key = prover.prover_key.clone().unwrap();
+
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("prove", &parameter), |b| {
+        b.iter(|| {
+            prover
+                .prove_with_preprocessed(&ck, &prover_key)
+                .expect("failed to prove the circuit")
+        })
+    });
+    group.finish();
+}
+
+fn zelbet_benches(c: &mut Criterion) {
+    bench_gadget(
+        c,
+        "decomposition_gadget",
+        decomposition,
+        &random_scalars(GADGETS),
+        GADGETS,
+    );
+
+    let nibbles: Vec<BlsScalar> = (0..GADGETS as u64)
+        .map(|k| BlsScalar::from(k * 97 % 660))
+        .collect();
+    bench_gadget(c, "s_box_and_constraints", s_box, &nibbles, GADGETS);
+
+    SPONGE_INPUTS.iter().for_each(|n| {
+        let inputs = random_scalars(*n);
+        bench_gadget(c, "zelbet_sponge_hash", sponge_hash, &inputs, *n);
+    });
+}
+
+criterion_group!(benches, zelbet_benches);
+criterion_main!(benches);
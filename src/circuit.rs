@@ -13,6 +13,7 @@ use crate::proof_system::{Proof, Prover, ProverKey, Verifier, VerifierKey};
 use alloc::vec::Vec;
 #[cfg(feature = "canon")]
 use canonical_derive::Canon;
+use core::convert::TryFrom;
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::{DeserializableSlice, Serializable, Write};
 use dusk_jubjub::{JubJubAffine, JubJubScalar};
@@ -41,6 +42,122 @@ impl From<JubJubAffine> for PublicInputValue {
     }
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+/// Public inputs of a circuit paired with the gate positions they are bound
+/// to.
+///
+/// The entries are always kept sorted by position, so two sets of public
+/// inputs holding the same `(position, value)` pairs have the same byte
+/// representation regardless of the order in which they were inserted.
+pub struct PublicInputs(Vec<(usize, BlsScalar)>);
+
+impl PublicInputs {
+    /// Creates an empty set of public inputs.
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    /// Builds the public inputs of a circuit from its [`PublicInputValue`]s
+    /// and the positions returned by [`StandardComposer::pi_positions`].
+    pub fn from_values(
+        pub_input_values: &[PublicInputValue],
+        pub_input_pos: &[usize],
+    ) -> Self {
+        let mut pi = Self::new();
+        pub_input_values
+            .iter()
+            .map(|pub_input| pub_input.0.iter().copied())
+            .flatten()
+            .zip(pub_input_pos.iter().copied())
+            .for_each(|(value, pos)| pi.insert(pos, value));
+        pi
+    }
+
+    /// Binds `value` to the gate at `position`, replacing any value that was
+    /// previously bound to it.
+    pub fn insert(&mut self, position: usize, value: BlsScalar) {
+        match self.0.binary_search_by_key(&position, |(pos, _)| *pos) {
+            Ok(i) => self.0[i].1 = value,
+            Err(i) => self.0.insert(i, (position, value)),
+        }
+    }
+
+    /// Returns the `(position, value)` pairs sorted by position.
+    pub fn entries(&self) -> &[(usize, BlsScalar)] {
+        &self.0
+    }
+
+    /// Serializes the public inputs into a vector of bytes: the number of
+    /// entries followed by each position and value, sorted by position.
+    /// These are the bytes consumed by [`verify_proof_with_pi_bytes`].
+    ///
+    /// # Panics
+    /// If the number of entries or a position doesn't fit in a `u32`.
+    pub fn to_var_bytes(&self) -> Vec<u8> {
+        let mut buff =
+            vec![0u8; u32::SIZE + self.0.len() * (u32::SIZE + BlsScalar::SIZE)];
+        let mut writer = &mut buff[..];
+
+        writer
+            .write(&to_u32(self.0.len()).to_bytes())
+            .expect("the buffer is sized for the entries");
+        self.0.iter().for_each(|(pos, value)| {
+            writer
+                .write(&to_u32(*pos).to_bytes())
+                .expect("the buffer is sized for the entries");
+            writer
+                .write(&value.to_bytes())
+                .expect("the buffer is sized for the entries");
+        });
+
+        buff
+    }
+
+    /// Deserializes `PublicInputs` from a slice of bytes.
+    ///
+    /// Only the canonical encoding produced by
+    /// [`PublicInputs::to_var_bytes`] is accepted: positions that are not
+    /// strictly increasing yield [`Error::InvalidPublicInputBytes`].
+    pub fn from_slice(mut buf: &[u8]) -> Result<Self, Error> {
+        let entries_num = u32::from_reader(&mut buf)? as usize;
+
+        let mut entries: Vec<(usize, BlsScalar)> = vec![];
+        for _ in 0..entries_num {
+            let pos = u32::from_reader(&mut buf)? as usize;
+            let value = BlsScalar::from_reader(&mut buf)?;
+            if let Some((last_pos, _)) = entries.last() {
+                if *last_pos >= pos {
+                    return Err(Error::InvalidPublicInputBytes);
+                }
+            }
+            entries.push((pos, value));
+        }
+
+        Ok(Self(entries))
+    }
+
+    /// Builds the dense public input vector of a circuit padded to
+    /// `trim_size`, as consumed by [`Verifier::verify`].
+    ///
+    /// Returns [`Error::PublicInputOutOfRange`] if a position, which may come
+    /// from untrusted bytes, isn't below `trim_size`.
+    pub fn to_dense_pi_vec(
+        &self,
+        trim_size: usize,
+    ) -> Result<Vec<BlsScalar>, Error> {
+        let mut pi = vec![BlsScalar::zero(); trim_size];
+        for (pos, value) in self.0.iter() {
+            let entry =
+                pi.get_mut(*pos).ok_or(Error::PublicInputOutOfRange {
+                    position: *pos,
+                    size: trim_size,
+                })?;
+            *entry = -value;
+        }
+        Ok(pi)
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Collection of structs/objects that the Verifier will use in order to
 /// de/serialize data needed for Circuit proof verification.
@@ -69,7 +186,9 @@ impl VerifierData {
     }
 
     /// Deserializes the `VerifierData` into a vector of bytes.
-    #[allow(unused_must_use)]
+    ///
+    /// # Panics
+    /// If the number of public inputs or a position doesn't fit in a `u32`.
     pub fn to_var_bytes(&self) -> Vec<u8> {
         let mut buff =
             vec![
@@ -78,12 +197,16 @@ impl VerifierData {
             ];
         let mut writer = &mut buff[..];
 
-        writer.write(&self.key.to_bytes());
-        writer.write(&(self.pi_pos.len() as u32).to_bytes());
+        writer
+            .write(&self.key.to_bytes())
+            .expect("the buffer is sized for the key and the positions");
+        writer
+            .write(&to_u32(self.pi_pos.len()).to_bytes())
+            .expect("the buffer is sized for the key and the positions");
         self.pi_pos.iter().copied().for_each(|pos| {
-            // Omit the result since disk_bytes write can't fail here
-            // due to the fact that we're writing into a vector basically.
-            let _ = writer.write(&(pos as u32).to_bytes());
+            writer
+                .write(&to_u32(pos).to_bytes())
+                .expect("the buffer is sized for the key and the positions");
         });
 
         buff
@@ -294,37 +417,60 @@ pub fn verify_proof(
     pub_inputs_values: &[PublicInputValue],
     pub_inputs_positions: &[usize],
     transcript_init: &'static [u8],
+) -> Result<(), Error> {
+    verify_proof_with_pi(
+        pub_params,
+        verifier_key,
+        proof,
+        &PublicInputs::from_values(pub_inputs_values, pub_inputs_positions),
+        transcript_init,
+    )
+}
+
+/// Same as [`verify_proof`], but reads the public inputs from the canonical
+/// bytes of [`PublicInputs::to_var_bytes`], so that the prover and the
+/// verifier can't disagree on their order.
+///
+/// Returns [`Error::InvalidPublicInputBytes`] if the positions of the bytes
+/// aren't strictly increasing, see [`PublicInputs::from_slice`].
+pub fn verify_proof_with_pi_bytes(
+    pub_params: &PublicParameters,
+    verifier_key: &VerifierKey,
+    proof: &Proof,
+    pub_inputs_bytes: &[u8],
+    transcript_init: &'static [u8],
+) -> Result<(), Error> {
+    verify_proof_with_pi(
+        pub_params,
+        verifier_key,
+        proof,
+        &PublicInputs::from_slice(pub_inputs_bytes)?,
+        transcript_init,
+    )
+}
+
+/// Verifies a proof against the dense PI vector of `pub_inputs`.
+fn verify_proof_with_pi(
+    pub_params: &PublicParameters,
+    verifier_key: &VerifierKey,
+    proof: &Proof,
+    pub_inputs: &PublicInputs,
+    transcript_init: &'static [u8],
 ) -> Result<(), Error> {
     let mut verifier = Verifier::new(transcript_init);
     verifier.verifier_key = Some(verifier_key.clone());
     verifier.verify(
         proof,
         pub_params.opening_key(),
-        build_pi(
-            pub_inputs_values,
-            pub_inputs_positions,
-            verifier_key.padded_circuit_size(),
-        )
-        .as_slice(),
+        pub_inputs
+            .to_dense_pi_vec(verifier_key.padded_circuit_size())?
+            .as_slice(),
     )
 }
 
-/// Build PI vector for Proof verifications.
-fn build_pi(
-    pub_input_values: &[PublicInputValue],
-    pub_input_pos: &[usize],
-    trim_size: usize,
-) -> Vec<BlsScalar> {
-    let mut pi = vec![BlsScalar::zero(); trim_size];
-    pub_input_values
-        .iter()
-        .map(|pub_input| pub_input.0.clone())
-        .flatten()
-        .zip(pub_input_pos.iter().copied())
-        .for_each(|(value, pos)| {
-            pi[pos] = -value;
-        });
-    pi
+/// Converts a length or a position to the `u32` of its serialization.
+fn to_u32(n: usize) -> u32 {
+    u32::try_from(n).expect("lengths and positions fit in a u32")
 }
 
 #[cfg(feature = "std")]
@@ -485,6 +631,172 @@ mod tests {
             &public_inputs,
             &verif_data.pi_pos(),
             b"Test",
-        )
+        )?;
+
+        // The verifier also reads the canonical bytes of the public inputs
+        let pi_bytes =
+            PublicInputs::from_values(&public_inputs, &verif_data.pi_pos())
+                .to_var_bytes();
+        verify_proof_with_pi_bytes(
+            &pp,
+            &verif_data.key(),
+            &proof,
+            &pi_bytes,
+            b"Test",
+        )?;
+
+        // But rejects them out of order
+        let mut bytes = pi_bytes;
+        let entry_size = u32::SIZE + BlsScalar::SIZE;
+        let (first, second) = bytes[u32::SIZE..].split_at_mut(entry_size);
+        first.swap_with_slice(&mut second[..entry_size]);
+        assert!(matches!(
+            verify_proof_with_pi_bytes(
+                &pp,
+                &verif_data.key(),
+                &proof,
+                &bytes,
+                b"Test",
+            ),
+            Err(Error::InvalidPublicInputBytes)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checked_key_loading() -> Result<(), Error> {
+        use rand_core::OsRng;
+
+        let pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let mut circuit = TestCircuit::default();
+        let (pk, vd) = circuit.compile(&pp)?;
+        let (ck, _) = pp.trim(circuit.padded_circuit_size())?;
+
+        let pk_bytes = pk.to_var_bytes();
+        let vk_bytes = vd.key().to_bytes();
+        let vk = VerifierKey::from_slice_for_circuit(
+            &vk_bytes,
+            circuit.padded_circuit_size(),
+        )?;
+        let loaded_pk = ProverKey::from_slice_for_circuit(&pk_bytes, &ck, &vk)?;
+        assert_eq!(loaded_pk, pk);
+
+        // The loaded keys prove and verify without any preprocessing
+        let f = JubJubAffine::from(
+            dusk_jubjub::GENERATOR_EXTENDED * JubJubScalar::from(2u64),
+        );
+        let proof = TestCircuit {
+            a: BlsScalar::from(20u64),
+            b: BlsScalar::from(5u64),
+            c: BlsScalar::from(25u64),
+            d: BlsScalar::from(100u64),
+            e: JubJubScalar::from(2u64),
+            f,
+        }
+        .gen_proof(&pp, &loaded_pk, b"Test")?;
+        let public_inputs: Vec<PublicInputValue> = vec![
+            BlsScalar::from(25u64).into(),
+            BlsScalar::from(100u64).into(),
+            f.into(),
+        ];
+        verify_proof(&pp, &vk, &proof, &public_inputs, vd.pi_pos(), b"Test")?;
+
+        // Keys of another circuit size are rejected
+        assert!(matches!(
+            VerifierKey::from_slice_for_circuit(&vk_bytes, 1 << 12),
+            Err(Error::CircuitSizeMismatch { expected, .. })
+                if expected == 1 << 12
+        ));
+
+        // So are commit keys too small for the circuit
+        let (small_ck, _) = pp.trim(circuit.padded_circuit_size() / 2)?;
+        assert!(matches!(
+            ProverKey::from_slice_for_circuit(&pk_bytes, &small_ck, &vk),
+            Err(Error::SrsTooSmall { .. })
+        ));
+
+        // And commit keys of another setup
+        let other_pp = PublicParameters::setup(1 << 12, &mut OsRng)?;
+        let (other_ck, _) = other_pp.trim(circuit.padded_circuit_size())?;
+        assert!(matches!(
+            ProverKey::from_slice_for_circuit(&pk_bytes, &other_ck, &vk),
+            Err(Error::KeyMismatch)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_inputs_serde() -> Result<(), Error> {
+        let values: Vec<PublicInputValue> = vec![
+            BlsScalar::from(25u64).into(),
+            JubJubAffine::from(
+                dusk_jubjub::GENERATOR_EXTENDED * JubJubScalar::from(2u64),
+            )
+            .into(),
+        ];
+        let pi = PublicInputs::from_values(&values, &[7, 2, 40]);
+
+        let positions: Vec<usize> =
+            pi.entries().iter().map(|(pos, _)| *pos).collect();
+        assert_eq!(positions, vec![2, 7, 40]);
+
+        let bytes = pi.to_var_bytes();
+        assert_eq!(PublicInputs::from_slice(&bytes)?, pi);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_inputs_canonical_order() {
+        let entries = [
+            (3, BlsScalar::from(1u64)),
+            (9, BlsScalar::from(2u64)),
+            (0, BlsScalar::from(3u64)),
+            (4, BlsScalar::from(4u64)),
+        ];
+
+        let mut pi = PublicInputs::new();
+        entries
+            .iter()
+            .for_each(|(pos, value)| pi.insert(*pos, *value));
+
+        let mut shuffled = PublicInputs::new();
+        entries
+            .iter()
+            .rev()
+            .for_each(|(pos, value)| shuffled.insert(*pos, *value));
+
+        assert_eq!(pi.to_var_bytes(), shuffled.to_var_bytes());
+        assert_eq!(
+            pi.to_dense_pi_vec(16).unwrap(),
+            shuffled.to_dense_pi_vec(16).unwrap()
+        );
+
+        // Swap the first two encoded entries, which is not canonical
+        let mut bytes = pi.to_var_bytes();
+        let entry_size = u32::SIZE + BlsScalar::SIZE;
+        let (first, second) = bytes[u32::SIZE..].split_at_mut(entry_size);
+        first.swap_with_slice(&mut second[..entry_size]);
+        assert!(PublicInputs::from_slice(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_public_inputs_out_of_range() {
+        let mut pi = PublicInputs::new();
+        pi.insert(3, BlsScalar::from(8));
+        pi.insert(15, BlsScalar::one());
+        assert!(pi.to_dense_pi_vec(16).is_ok());
+
+        // A position from untrusted bytes past the circuit is rejected
+        let pi = PublicInputs::from_slice(&pi.to_var_bytes()).unwrap();
+        assert!(matches!(
+            pi.to_dense_pi_vec(8),
+            Err(Error::PublicInputOutOfRange {
+                position: 15,
+                size: 8
+            })
+        ));
     }
 }
@@ -164,7 +164,7 @@ impl CommitKey {
         // Check whether we can safely commit to this polynomial
         self.check_commit_degree_is_within_bounds(polynomial.degree())?;
 
-        // Compute commitment
+        // Compute commitment, with the bucket method of Pippenger
         Ok(Commitment::from(msm_variable_base(
             &self.powers_of_g,
             &polynomial.coeffs,
@@ -261,6 +261,23 @@ impl OpeningKey {
         proofs: &[Proof],
         transcript: &mut Transcript,
     ) -> Result<(), Error> {
+        let (total_c, total_w) = self.accumulate(points, proofs, transcript);
+        self.check_accumulated(total_c, total_w)
+    }
+
+    /// Folds a batch of opening proofs into the pair of points `(c, w)` that
+    /// satisfies `e(-w, beta_h) * e(c, h) == 1` if and only if, with
+    /// overwhelming probability, every proof of the batch is valid.
+    ///
+    /// Accumulators of different batches can be further combined with
+    /// random weights and checked with a single pairing by
+    /// [`OpeningKey::check_accumulated`].
+    pub(crate) fn accumulate(
+        &self,
+        points: &[BlsScalar],
+        proofs: &[Proof],
+        transcript: &mut Transcript,
+    ) -> (G1Projective, G1Projective) {
         let mut total_c = G1Projective::identity();
         let mut total_w = G1Projective::identity();
 
@@ -283,6 +300,16 @@ impl OpeningKey {
         }
         total_c -= self.g * g_multiplier;
 
+        (total_c, total_w)
+    }
+
+    /// Performs the pairing check of an accumulator computed by
+    /// [`OpeningKey::accumulate`].
+    pub(crate) fn check_accumulated(
+        &self,
+        total_c: G1Projective,
+        total_w: G1Projective,
+    ) -> Result<(), Error> {
         let affine_total_w = G1Affine::from(-total_w);
         let affine_total_c = G1Affine::from(total_c);
 
@@ -413,6 +440,26 @@ mod test {
         assert!(ok);
         Ok(())
     }
+
+    #[test]
+    fn test_commit_matches_naive_msm() -> Result<(), Error> {
+        // Sizes on both sides of the window changes of the bucket method
+        for degree in [1, 7, 31, 32, 100, 1000].iter() {
+            let (ck, _) = setup_test(*degree)?;
+            let poly = Polynomial::rand(*degree, &mut OsRng);
+
+            let naive: G1Projective = ck
+                .powers_of_g
+                .iter()
+                .zip(poly.coeffs.iter())
+                .fold(G1Projective::identity(), |acc, (point, coeff)| {
+                    acc + point * coeff
+                });
+            assert_eq!(ck.commit(&poly)?, Commitment::from(naive));
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_batch_verification() -> Result<(), Error> {
         let degree = 25;
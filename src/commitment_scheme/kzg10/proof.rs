@@ -28,7 +28,7 @@ pub(crate) mod alloc {
     use ::alloc::vec::Vec;
     use dusk_bls12_381::G1Projective;
     use merlin::Transcript;
-    #[cfg(feature = "std")]
+    #[cfg(feature = "parallel")]
     use rayon::prelude::*;
 
     /// Proof that multiple polynomials were correctly evaluated at a point `z`,
@@ -72,19 +72,19 @@ pub(crate) mod alloc {
                 self.commitments_to_polynomials.len() - 1,
             );
 
-            #[cfg(not(feature = "std"))]
+            #[cfg(not(feature = "parallel"))]
             let flattened_poly_commitments_iter =
                 self.commitments_to_polynomials.iter().zip(powers.iter());
-            #[cfg(not(feature = "std"))]
+            #[cfg(not(feature = "parallel"))]
             let flattened_poly_evaluations_iter =
                 self.evaluated_points.iter().zip(powers.iter());
 
-            #[cfg(feature = "std")]
+            #[cfg(feature = "parallel")]
             let flattened_poly_commitments_iter = self
                 .commitments_to_polynomials
                 .par_iter()
                 .zip(powers.par_iter());
-            #[cfg(feature = "std")]
+            #[cfg(feature = "parallel")]
             let flattened_poly_evaluations_iter =
                 self.evaluated_points.par_iter().zip(powers.par_iter());
 
@@ -159,6 +159,51 @@ impl PublicParameters {
         Ok(pp)
     }
 
+    /// Loads [`PublicParameters`] exported with
+    /// [`PublicParameters::to_var_bytes`], such as the powers of a trusted
+    /// setup ceremony, to prove and verify circuits of `padded_circuit_size`.
+    ///
+    /// The degree is checked against `padded_circuit_size` before any point
+    /// is decoded, returning [`Error::SrsTooSmall`] if the circuits don't
+    /// fit, or if the degree is zero, as the second power is needed to check
+    /// the setup. Every point is then decoded as in
+    /// [`PublicParameters::from_slice`], checking it's in the prime order
+    /// subgroup, and the first power of the [`CommitKey`] is checked against
+    /// the [`OpeningKey`] so that both halves come from the same setup.
+    pub fn from_slice_for_circuit(
+        bytes: &[u8],
+        padded_circuit_size: usize,
+    ) -> Result<PublicParameters, Error> {
+        let powers =
+            bytes.len().saturating_sub(OpeningKey::SIZE) / G1Affine::SIZE;
+        // The pairing check below reads the second power
+        let required = padded_circuit_size.max(1);
+        if powers <= required {
+            return Err(Error::SrsTooSmall {
+                degree: powers.saturating_sub(1),
+                required,
+            });
+        }
+
+        let pp = PublicParameters::from_slice(bytes)?;
+        let powers_of_g = &pp.commit_key.powers_of_g;
+        let opening_key = &pp.opening_key;
+        if powers_of_g[0] != opening_key.g {
+            return Err(Error::PairingCheckFailure);
+        }
+        // e(beta * g, h) = e(g, beta * h)
+        let pairing = dusk_bls12_381::multi_miller_loop(&[
+            (&-powers_of_g[1], &opening_key.prepared_h),
+            (&opening_key.g, &opening_key.prepared_beta_h),
+        ])
+        .final_exponentiation();
+        if pairing != dusk_bls12_381::Gt::identity() {
+            return Err(Error::PairingCheckFailure);
+        }
+
+        Ok(pp)
+    }
+
     /// Trim truncates the [`PublicParameters`] to allow the prover to commit to
     /// polynomials up to the and including the truncated degree.
     /// Returns the [`CommitKey`] and [`OpeningKey`] used to generate and verify
@@ -187,6 +232,8 @@ impl PublicParameters {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::constraint_system::StandardComposer;
+    use crate::proof_system::{Prover, Verifier};
     use dusk_bls12_381::BlsScalar;
     use rand_core::OsRng;
 
@@ -231,4 +278,70 @@ mod test {
         assert_eq!(pp.opening_key.h, pp_p.opening_key.h);
         assert_eq!(pp.opening_key.beta_h, pp_p.opening_key.beta_h);
     }
+
+    #[test]
+    fn test_load_public_parameters_for_circuit() {
+        let gadget = |composer: &mut StandardComposer| {
+            let a = composer.add_input(BlsScalar::from(3));
+            let b = composer.add_input(BlsScalar::from(4));
+            let c =
+                composer.mul(BlsScalar::one(), a, b, BlsScalar::zero(), None);
+            composer.constrain_to_constant(c, BlsScalar::from(12), None);
+        };
+        let mut prover = Prover::new(b"srs");
+        gadget(prover.mut_cs());
+        let size = prover.mut_cs().next_power_of_two_size();
+
+        let bytes = PublicParameters::setup(2 * size, &mut OsRng)
+            .unwrap()
+            .to_var_bytes();
+        let pp =
+            PublicParameters::from_slice_for_circuit(&bytes, 2 * size).unwrap();
+
+        // The reimported parameters prove and verify the circuit
+        let (ck, _) = pp.trim(2 * size).unwrap();
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.mut_cs().construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"srs");
+        gadget(verifier.mut_cs());
+        let (ck, ok) = pp.trim(size).unwrap();
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &ok, &public_inputs).is_ok());
+
+        // Circuits larger than the setup are rejected
+        assert!(matches!(
+            PublicParameters::from_slice_for_circuit(&bytes, 4 * size),
+            Err(Error::SrsTooSmall { required, .. }) if required == 4 * size
+        ));
+
+        // Setups with a single power can't be checked
+        let single_power = &bytes[..OpeningKey::SIZE + G1Affine::SIZE];
+        assert!(matches!(
+            PublicParameters::from_slice_for_circuit(single_power, 0),
+            Err(Error::SrsTooSmall {
+                degree: 0,
+                required: 1
+            })
+        ));
+
+        // So are points out of the curve
+        let mut malformed = bytes.clone();
+        malformed[OpeningKey::SIZE + G1Affine::SIZE + 1] ^= 0xff;
+        assert!(
+            PublicParameters::from_slice_for_circuit(&malformed, size).is_err()
+        );
+
+        // And powers which don't match the opening key
+        let other = PublicParameters::setup(2 * size, &mut OsRng)
+            .unwrap()
+            .to_var_bytes();
+        let mut mixed = bytes[..OpeningKey::SIZE].to_vec();
+        mixed.extend_from_slice(&other[OpeningKey::SIZE..]);
+        assert!(matches!(
+            PublicParameters::from_slice_for_circuit(&mixed, size),
+            Err(Error::PairingCheckFailure)
+        ));
+    }
 }
@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::zelbet::{pack_bytes, ACCUMULATOR_DOMAIN};
+use crate::constraint_system::StandardComposer;
+use crate::constraint_system::Variable;
+
+impl StandardComposer {
+    /// Adds `element` to the accumulator `acc`, returning the new
+    /// accumulator `H(tag, acc, element)`, where `H` is
+    /// [`StandardComposer::zelbet_sponge_hash`] and `tag` is the packing of
+    /// [`ACCUMULATOR_DOMAIN`] by [`pack_bytes`].
+    ///
+    /// The tag separates the updates from the other hashes of three
+    /// elements, and the padding of the sponge from the hashes of any other
+    /// number of elements, such as the nodes of
+    /// [`StandardComposer::merkle_verify`].
+    ///
+    /// The accumulator is order-dependent: adding the same elements in
+    /// another order gives another accumulator, so it commits to the
+    /// sequence of additions rather than to the set of elements, as suits
+    /// append-only sets. The first accumulator is up to the caller.
+    ///
+    /// The lookups of the hash need the table of
+    /// [`PlookupTable4Arity::create_hash_table`] to be appended to the
+    /// composer.
+    ///
+    /// [`PlookupTable4Arity::create_hash_table`]:
+    /// crate::plookup::PlookupTable4Arity::create_hash_table
+    pub fn accumulator_add(
+        &mut self,
+        acc: Variable,
+        element: Variable,
+    ) -> Variable {
+        let tag = self.add_constant_var(pack_bytes(ACCUMULATOR_DOMAIN)[0]);
+        self.zelbet_sponge_hash(&[tag, acc, element])
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+    use crate::plookup::PlookupTable4Arity;
+    use dusk_bls12_381::BlsScalar;
+
+    /// Out of circuit accumulator of `elements`, added in order to zero
+    fn accumulate(elements: &[u64]) -> BlsScalar {
+        let mut composer = StandardComposer::new();
+        let acc = elements.iter().fold(composer.zero_var(), |acc, element| {
+            let element = composer.add_input(BlsScalar::from(*element));
+            composer.accumulator_add(acc, element)
+        });
+        composer.variables[&acc]
+    }
+
+    #[test]
+    fn test_accumulator_add() {
+        // Should pass as the accumulator is the one of the same additions
+        let res = gadget_plookup_tester(
+            |composer| {
+                let acc = [3u64, 5, 7].iter().fold(
+                    composer.zero_var(),
+                    |acc, element| {
+                        let element =
+                            composer.add_input(BlsScalar::from(*element));
+                        composer.accumulator_add(acc, element)
+                    },
+                );
+                composer.constrain_to_constant(
+                    acc,
+                    accumulate(&[3, 5, 7]),
+                    None,
+                );
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+
+        // Should fail as the elements were added in another order
+        let res = gadget_plookup_tester(
+            |composer| {
+                let acc = [5u64, 3, 7].iter().fold(
+                    composer.zero_var(),
+                    |acc, element| {
+                        let element =
+                            composer.add_input(BlsScalar::from(*element));
+                        composer.accumulator_add(acc, element)
+                    },
+                );
+                composer.constrain_to_constant(
+                    acc,
+                    accumulate(&[3, 5, 7]),
+                    None,
+                );
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_accumulator_order_and_domain() {
+        // The accumulator depends on the order of the additions
+        assert_ne!(accumulate(&[3, 5]), accumulate(&[5, 3]));
+        assert_ne!(accumulate(&[3, 5, 7]), accumulate(&[3, 7, 5]));
+        assert_ne!(accumulate(&[3]), accumulate(&[3, 3]));
+
+        // An update isn't the plain hash of the accumulator and the element
+        let mut composer = StandardComposer::new();
+        let acc = composer.zero_var();
+        let element = composer.add_input(BlsScalar::from(3));
+        let plain = composer.zelbet_sponge_hash(&[acc, element]);
+        assert_ne!(composer.variables[&plain], accumulate(&[3]));
+    }
+}
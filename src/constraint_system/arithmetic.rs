@@ -6,6 +6,7 @@
 
 use crate::constraint_system::StandardComposer;
 use crate::constraint_system::Variable;
+use crate::error::Error;
 use dusk_bls12_381::BlsScalar;
 
 impl StandardComposer {
@@ -265,6 +266,20 @@ impl StandardComposer {
         self.big_mul(q_m, a, b, None, q_c, pi)
     }
 
+    /// Multiplies `a` by the constant `c`, computing & returning the product
+    /// [`Variable`].
+    ///
+    /// `c` is the left selector of a single addition gate, so unlike
+    /// [`StandardComposer::mul`] the constant doesn't need a [`Variable`]
+    /// of its own, nor the gate constraining it, and the right wire is left
+    /// to the zero [`Variable`].
+    ///
+    /// Forces `c * w_l = w_o(computed by the gate)`.
+    pub fn mul_const(&mut self, a: Variable, c: BlsScalar) -> Variable {
+        let zero = self.zero_var;
+        self.add((c, a), (BlsScalar::zero(), zero), BlsScalar::zero(), None)
+    }
+
     /// Adds a width-4 [`StandardComposer::big_mul_gate`] with the left, right
     /// and fourth inputs and it's scaling factors, computing & returning
     /// the output (result) [`Variable`] and adding the corresponding mul
@@ -308,207 +323,572 @@ impl StandardComposer {
 
         self.big_mul_gate(a, b, c, Some(d), q_m, q_o, q_c, q_4, pi)
     }
+
+    /// Computes the inner product `sum(a_i * b_i)` of `a` and `b`, returning
+    /// the accumulator [`Variable`].
+    ///
+    /// Each term is added to the accumulator with a single multiply-add gate
+    /// `acc' = a_i * b_i + acc`, the accumulator being on the fourth wire.
+    /// The inner product of empty slices is zero.
+    ///
+    /// Returns an error if `a` and `b` have different lengths.
+    pub fn inner_product(
+        &mut self,
+        a: &[Variable],
+        b: &[Variable],
+    ) -> Result<Variable, Error> {
+        if a.len() != b.len() {
+            return Err(Error::InputLengthMismatch {
+                left: a.len(),
+                right: b.len(),
+            });
+        }
+
+        Ok(a.iter()
+            .zip(b.iter())
+            .fold(self.zero_var, |acc, (a_i, b_i)| {
+                self.big_mul(
+                    BlsScalar::one(),
+                    *a_i,
+                    *b_i,
+                    Some((BlsScalar::one(), acc)),
+                    BlsScalar::zero(),
+                    None,
+                )
+            }))
+    }
+
+    /// Computes the sum of `vars`, returning the accumulator [`Variable`].
+    ///
+    /// The first gate adds up to three elements, and each following one
+    /// adds the next two to the accumulator with a
+    /// [`StandardComposer::big_add`], so `n` elements take `(n - 1) / 2`
+    /// rounded up gates. The sum of an empty slice is the zero [`Variable`],
+    /// and the sum of a single element is the element itself, neither
+    /// adding any gate.
+    pub fn sum(&mut self, vars: &[Variable]) -> Variable {
+        let (first, rest) = match vars.split_first() {
+            Some(split) => split,
+            None => return self.zero_var,
+        };
+
+        rest.chunks(2).fold(*first, |acc, terms| {
+            self.big_add(
+                (BlsScalar::one(), acc),
+                (BlsScalar::one(), terms[0]),
+                terms.get(1).map(|d| (BlsScalar::one(), *d)),
+                BlsScalar::zero(),
+                None,
+            )
+        })
+    }
+
+    /// Adds a single gate to the circuit description constraining the point
+    /// `(x, y)` to lie on the line `y = a * x + b`.
+    ///
+    /// Forces `a * x + b - y = 0`.
+    pub fn assert_affine(
+        &mut self,
+        x: Variable,
+        y: Variable,
+        a: BlsScalar,
+        b: BlsScalar,
+    ) {
+        self.add_gate(
+            x,
+            self.zero_var,
+            y,
+            a,
+            BlsScalar::zero(),
+            -BlsScalar::one(),
+            b,
+            None,
+        );
+    }
+
+    /// Returns the quotient `q = a / b`, constraining `b` to be nonzero with
+    /// [`StandardComposer::assert_nonzero`] and adding a gate forcing
+    /// `q * b - a = 0`.
+    pub fn div(&mut self, a: Variable, b: Variable) -> Variable {
+        self.assert_nonzero(b);
+
+        let b_value = self.variables[&b];
+        let q = match b_value == BlsScalar::zero() {
+            true => BlsScalar::zero(),
+            false => self.variables[&a] * b_value.invert().unwrap(),
+        };
+        let q = self.add_input(q);
+
+        self.mul_gate(
+            q,
+            b,
+            a,
+            BlsScalar::one(),
+            BlsScalar::one(),
+            BlsScalar::zero(),
+            None,
+        );
+
+        q
+    }
+
+    /// Adds a single gate to the circuit description constraining `x` to be
+    /// nonzero, by witnessing its inverse `inv`.
+    ///
+    /// Forces `x * inv - 1 = 0`, which no `inv` satisfies for a zero `x`.
+    pub fn assert_nonzero(&mut self, x: Variable) {
+        let value = self.variables[&x];
+        let inv = match value == BlsScalar::zero() {
+            true => BlsScalar::zero(),
+            false => value.invert().unwrap(),
+        };
+        let inv = self.add_input(inv);
+
+        self.mul_gate(
+            x,
+            inv,
+            self.zero_var,
+            BlsScalar::one(),
+            BlsScalar::zero(),
+            -BlsScalar::one(),
+            None,
+        );
+    }
 }
 
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
     use crate::constraint_system::helper::*;
+    use crate::constraint_system::StandardComposer;
+    use crate::plookup::table::hash_tables::SboxTable;
+    use crate::plookup::PlookupTable4Arity;
+    use alloc::vec::Vec;
     use dusk_bls12_381::BlsScalar;
 
     #[test]
     fn test_public_inputs() {
-        let res = gadget_tester(
-            |composer| {
-                let var_one = composer.add_input(BlsScalar::one());
-
-                let should_be_three = composer.big_add(
-                    (BlsScalar::one(), var_one),
-                    (BlsScalar::one(), var_one),
-                    None,
-                    BlsScalar::zero(),
-                    Some(BlsScalar::one()),
-                );
-                composer.constrain_to_constant(
-                    should_be_three,
-                    BlsScalar::from(3),
-                    None,
-                );
-                let should_be_four = composer.big_add(
-                    (BlsScalar::one(), var_one),
-                    (BlsScalar::one(), var_one),
-                    None,
-                    BlsScalar::zero(),
-                    Some(BlsScalar::from(2)),
-                );
-                composer.constrain_to_constant(
-                    should_be_four,
-                    BlsScalar::from(4),
-                    None,
-                );
-            },
-            200,
-        );
+        let res = gadget_tester(|composer| {
+            let var_one = composer.add_input(BlsScalar::one());
+
+            let should_be_three = composer.big_add(
+                (BlsScalar::one(), var_one),
+                (BlsScalar::one(), var_one),
+                None,
+                BlsScalar::zero(),
+                Some(BlsScalar::one()),
+            );
+            composer.constrain_to_constant(
+                should_be_three,
+                BlsScalar::from(3),
+                None,
+            );
+            let should_be_four = composer.big_add(
+                (BlsScalar::one(), var_one),
+                (BlsScalar::one(), var_one),
+                None,
+                BlsScalar::zero(),
+                Some(BlsScalar::from(2)),
+            );
+            composer.constrain_to_constant(
+                should_be_four,
+                BlsScalar::from(4),
+                None,
+            );
+        });
         assert!(res.is_ok());
     }
 
     #[test]
     fn test_correct_add_mul_gate() {
-        let res = gadget_tester(
-            |composer| {
-                // Verify that (4+5+5) * (6+7+7) = 280
-                let four = composer.add_input(BlsScalar::from(4));
-                let five = composer.add_input(BlsScalar::from(5));
-                let six = composer.add_input(BlsScalar::from(6));
-                let seven = composer.add_input(BlsScalar::from(7));
-
-                let fourteen = composer.big_add(
-                    (BlsScalar::one(), four),
-                    (BlsScalar::one(), five),
-                    Some((BlsScalar::one(), five)),
-                    BlsScalar::zero(),
-                    None,
-                );
+        let res = gadget_tester(|composer| {
+            // Verify that (4+5+5) * (6+7+7) = 280
+            let four = composer.add_input(BlsScalar::from(4));
+            let five = composer.add_input(BlsScalar::from(5));
+            let six = composer.add_input(BlsScalar::from(6));
+            let seven = composer.add_input(BlsScalar::from(7));
+
+            let fourteen = composer.big_add(
+                (BlsScalar::one(), four),
+                (BlsScalar::one(), five),
+                Some((BlsScalar::one(), five)),
+                BlsScalar::zero(),
+                None,
+            );
 
-                let twenty = composer.big_add(
-                    (BlsScalar::one(), six),
-                    (BlsScalar::one(), seven),
-                    Some((BlsScalar::one(), seven)),
-                    BlsScalar::zero(),
-                    None,
-                );
-
-                // There are quite a few ways to check the equation is correct,
-                // depending on your circumstance If we already
-                // have the output wire, we can constrain the output of the
-                // mul_gate to be equal to it If we do not, we
-                // can compute it using the `mul` If the output
-                // is public, we can also constrain the output wire of the mul
-                // gate to it. This is what this test does
-                let output = composer.mul(
-                    BlsScalar::one(),
-                    fourteen,
-                    twenty,
-                    BlsScalar::zero(),
-                    None,
-                );
-                composer.constrain_to_constant(
-                    output,
-                    BlsScalar::from(280),
-                    None,
-                );
-            },
-            200,
-        );
+            let twenty = composer.big_add(
+                (BlsScalar::one(), six),
+                (BlsScalar::one(), seven),
+                Some((BlsScalar::one(), seven)),
+                BlsScalar::zero(),
+                None,
+            );
+
+            // There are quite a few ways to check the equation is correct,
+            // depending on your circumstance If we already
+            // have the output wire, we can constrain the output of the
+            // mul_gate to be equal to it If we do not, we
+            // can compute it using the `mul` If the output
+            // is public, we can also constrain the output wire of the mul
+            // gate to it. This is what this test does
+            let output = composer.mul(
+                BlsScalar::one(),
+                fourteen,
+                twenty,
+                BlsScalar::zero(),
+                None,
+            );
+            composer.constrain_to_constant(output, BlsScalar::from(280), None);
+        });
         assert!(res.is_ok());
     }
 
     #[test]
     fn test_correct_add_gate() {
-        let res = gadget_tester(
-            |composer| {
-                let zero = composer.zero_var();
-                let one = composer.add_input(BlsScalar::one());
-
-                let c = composer.add(
-                    (BlsScalar::one(), one),
-                    (BlsScalar::zero(), zero),
-                    BlsScalar::from(2u64),
-                    None,
-                );
-                composer.constrain_to_constant(c, BlsScalar::from(3), None);
-            },
-            32,
-        );
+        let res = gadget_tester(|composer| {
+            let zero = composer.zero_var();
+            let one = composer.add_input(BlsScalar::one());
+
+            let c = composer.add(
+                (BlsScalar::one(), one),
+                (BlsScalar::zero(), zero),
+                BlsScalar::from(2u64),
+                None,
+            );
+            composer.constrain_to_constant(c, BlsScalar::from(3), None);
+        });
         assert!(res.is_ok())
     }
 
+    #[test]
+    fn test_affine() {
+        // Should pass as (3, 23) lies on y = 7x + 2
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(3u64));
+            let y = composer.add_input(BlsScalar::from(23u64));
+            composer.assert_affine(
+                x,
+                y,
+                BlsScalar::from(7u64),
+                BlsScalar::from(2u64),
+            );
+        });
+        assert!(res.is_ok());
+
+        // Should pass as (5, 0) lies on y = -x + 5
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(5u64));
+            let y = composer.zero_var();
+            composer.assert_affine(
+                x,
+                y,
+                -BlsScalar::one(),
+                BlsScalar::from(5u64),
+            );
+        });
+        assert!(res.is_ok());
+
+        // Should fail as (3, 24) does not lie on y = 7x + 2
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(3u64));
+            let y = composer.add_input(BlsScalar::from(24u64));
+            composer.assert_affine(
+                x,
+                y,
+                BlsScalar::from(7u64),
+                BlsScalar::from(2u64),
+            );
+        });
+        assert!(res.is_err());
+    }
+
     #[test]
     fn test_correct_big_add_mul_gate() {
-        let res = gadget_tester(
-            |composer| {
-                // Verify that (4+5+5) * (6+7+7) + (8*9) = 352
-                let four = composer.add_input(BlsScalar::from(4));
-                let five = composer.add_input(BlsScalar::from(5));
-                let six = composer.add_input(BlsScalar::from(6));
-                let seven = composer.add_input(BlsScalar::from(7));
-                let nine = composer.add_input(BlsScalar::from(9));
-
-                let fourteen = composer.big_add(
-                    (BlsScalar::one(), four),
-                    (BlsScalar::one(), five),
-                    Some((BlsScalar::one(), five)),
-                    BlsScalar::zero(),
-                    None,
-                );
+        let res = gadget_tester(|composer| {
+            // Verify that (4+5+5) * (6+7+7) + (8*9) = 352
+            let four = composer.add_input(BlsScalar::from(4));
+            let five = composer.add_input(BlsScalar::from(5));
+            let six = composer.add_input(BlsScalar::from(6));
+            let seven = composer.add_input(BlsScalar::from(7));
+            let nine = composer.add_input(BlsScalar::from(9));
+
+            let fourteen = composer.big_add(
+                (BlsScalar::one(), four),
+                (BlsScalar::one(), five),
+                Some((BlsScalar::one(), five)),
+                BlsScalar::zero(),
+                None,
+            );
 
-                let twenty = composer.big_add(
-                    (BlsScalar::one(), six),
-                    (BlsScalar::one(), seven),
-                    Some((BlsScalar::one(), seven)),
-                    BlsScalar::zero(),
-                    None,
-                );
+            let twenty = composer.big_add(
+                (BlsScalar::one(), six),
+                (BlsScalar::one(), seven),
+                Some((BlsScalar::one(), seven)),
+                BlsScalar::zero(),
+                None,
+            );
 
-                let output = composer.big_mul(
-                    BlsScalar::one(),
-                    fourteen,
-                    twenty,
-                    Some((BlsScalar::from(8), nine)),
-                    BlsScalar::zero(),
-                    None,
-                );
-                composer.constrain_to_constant(
-                    output,
-                    BlsScalar::from(352),
-                    None,
-                );
-            },
-            200,
-        );
+            let output = composer.big_mul(
+                BlsScalar::one(),
+                fourteen,
+                twenty,
+                Some((BlsScalar::from(8), nine)),
+                BlsScalar::zero(),
+                None,
+            );
+            composer.constrain_to_constant(output, BlsScalar::from(352), None);
+        });
         assert!(res.is_ok());
     }
 
     #[test]
     fn test_incorrect_add_mul_gate() {
-        let res = gadget_tester(
-            |composer| {
-                // Verify that (5+5) * (6+7) != 117
-                let five = composer.add_input(BlsScalar::from(5));
-                let six = composer.add_input(BlsScalar::from(6));
-                let seven = composer.add_input(BlsScalar::from(7));
-
-                let five_plus_five = composer.big_add(
-                    (BlsScalar::one(), five),
-                    (BlsScalar::one(), five),
-                    None,
-                    BlsScalar::zero(),
-                    None,
-                );
+        let res = gadget_tester(|composer| {
+            // Verify that (5+5) * (6+7) != 117
+            let five = composer.add_input(BlsScalar::from(5));
+            let six = composer.add_input(BlsScalar::from(6));
+            let seven = composer.add_input(BlsScalar::from(7));
+
+            let five_plus_five = composer.big_add(
+                (BlsScalar::one(), five),
+                (BlsScalar::one(), five),
+                None,
+                BlsScalar::zero(),
+                None,
+            );
+
+            let six_plus_seven = composer.big_add(
+                (BlsScalar::one(), six),
+                (BlsScalar::one(), seven),
+                None,
+                BlsScalar::zero(),
+                None,
+            );
 
-                let six_plus_seven = composer.big_add(
-                    (BlsScalar::one(), six),
-                    (BlsScalar::one(), seven),
+            let output = composer.mul(
+                BlsScalar::one(),
+                five_plus_five,
+                six_plus_seven,
+                BlsScalar::zero(),
+                None,
+            );
+            composer.constrain_to_constant(output, BlsScalar::from(117), None);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_mul_const() {
+        let res = gadget_tester(|composer| {
+            let c = BlsScalar::from(700);
+            let a = composer.add_input(BlsScalar::from(13));
+
+            let gates = composer.circuit_size();
+            let by_constant = composer.mul_const(a, c);
+            let const_gates = composer.circuit_size() - gates;
+
+            let gates = composer.circuit_size();
+            let c_var = composer.add_constant_var(c);
+            let by_variable = composer.big_mul(
+                BlsScalar::one(),
+                a,
+                c_var,
+                None,
+                BlsScalar::zero(),
+                None,
+            );
+            let variable_gates = composer.circuit_size() - gates;
+
+            assert_eq!(
+                composer.variables[&by_constant],
+                composer.variables[&by_variable]
+            );
+            assert_eq!(const_gates, 1);
+            assert!(const_gates < variable_gates);
+            composer.assert_equal(by_constant, by_variable);
+            composer.constrain_to_constant(
+                by_constant,
+                BlsScalar::from(9100),
+                None,
+            );
+        });
+        assert!(res.is_ok());
+
+        // Should fail as 13 * 700 != 9101
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(13));
+            let product = composer.mul_const(a, BlsScalar::from(700));
+            composer.constrain_to_constant(
+                product,
+                BlsScalar::from(9101),
+                None,
+            );
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_inner_product() {
+        let res = gadget_tester(|composer| {
+            let a: Vec<_> = [1u64, 2, 3, 4, 5]
+                .iter()
+                .map(|a| composer.add_input(BlsScalar::from(*a)))
+                .collect();
+            let b: Vec<_> = [6u64, 7, 8, 9, 10]
+                .iter()
+                .map(|b| composer.add_input(BlsScalar::from(*b)))
+                .collect();
+
+            let product = composer.inner_product(&a, &b).unwrap();
+
+            // Same computation with separate multiplications and
+            // additions
+            let expected = a.iter().zip(b.iter()).fold(
+                composer.zero_var(),
+                |acc, (a, b)| {
+                    let term = composer.big_mul(
+                        BlsScalar::one(),
+                        *a,
+                        *b,
+                        None,
+                        BlsScalar::zero(),
+                        None,
+                    );
+                    composer.big_add(
+                        (BlsScalar::one(), acc),
+                        (BlsScalar::one(), term),
+                        None,
+                        BlsScalar::zero(),
+                        None,
+                    )
+                },
+            );
+            composer.assert_equal(product, expected);
+            composer.constrain_to_constant(product, BlsScalar::from(130), None);
+        });
+        assert!(res.is_ok());
+
+        let mut composer = StandardComposer::new();
+        let one = composer.add_input(BlsScalar::one());
+        assert!(composer.inner_product(&[one, one], &[one]).is_err());
+        assert_eq!(
+            composer.inner_product(&[], &[]).unwrap(),
+            composer.zero_var()
+        );
+    }
+
+    #[test]
+    fn test_sum() {
+        let res = gadget_tester(|composer| {
+            let vars: Vec<_> = (1..=10u64)
+                .map(|x| composer.add_input(BlsScalar::from(x * x)))
+                .collect();
+
+            let gates = composer.circuit_size();
+            let sum = composer.sum(&vars);
+            assert_eq!(composer.circuit_size() - gates, 5);
+
+            // Same computation with one addition per element
+            let expected = vars.iter().fold(composer.zero_var(), |acc, x| {
+                composer.big_add(
+                    (BlsScalar::one(), acc),
+                    (BlsScalar::one(), *x),
                     None,
                     BlsScalar::zero(),
                     None,
-                );
+                )
+            });
+            composer.assert_equal(sum, expected);
+            composer.constrain_to_constant(sum, BlsScalar::from(385), None);
+        });
+        assert!(res.is_ok());
 
-                let output = composer.mul(
-                    BlsScalar::one(),
-                    five_plus_five,
-                    six_plus_seven,
-                    BlsScalar::zero(),
-                    None,
-                );
-                composer.constrain_to_constant(
-                    output,
-                    BlsScalar::from(117),
-                    None,
-                );
+        // Neither the empty slice nor a single element add a gate
+        let mut composer = StandardComposer::new();
+        let x = composer.add_input(BlsScalar::from(7));
+        let gates = composer.circuit_size();
+        assert_eq!(composer.sum(&[]), composer.zero_var());
+        assert_eq!(composer.sum(&[x]), x);
+        assert_eq!(composer.circuit_size(), gates);
+
+        // Should fail as the sum isn't the expected one
+        let res = gadget_tester(|composer| {
+            let vars: Vec<_> = (1..=10u64)
+                .map(|x| composer.add_input(BlsScalar::from(x)))
+                .collect();
+            let sum = composer.sum(&vars);
+            composer.constrain_to_constant(sum, BlsScalar::from(54), None);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_assert_nonzero() {
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(7));
+            composer.assert_nonzero(x);
+            let minus_one = composer.add_input(-BlsScalar::one());
+            composer.assert_nonzero(minus_one);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as zero has no inverse
+        let res = gadget_tester(|composer| {
+            let zero = composer.add_input(BlsScalar::zero());
+            composer.assert_nonzero(zero);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_div() {
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(42));
+            let b = composer.add_input(BlsScalar::from(6));
+            let q = composer.div(a, b);
+            composer.constrain_to_constant(q, BlsScalar::from(7), None);
+
+            // Non exact divisions give the field quotient
+            let c = composer.add_input(BlsScalar::from(5));
+            let q = composer.div(a, c);
+            assert_eq!(
+                composer.variables[&q] * BlsScalar::from(5),
+                BlsScalar::from(42)
+            );
+        });
+        assert!(res.is_ok());
+
+        // Dividing by the outputs of the Zelbet s-box
+        let res = gadget_plookup_tester(
+            |composer| {
+                let a = composer.add_input(-BlsScalar::from(3));
+                (0..6u64).for_each(|nibble| {
+                    let x = composer.add_input(BlsScalar::from(nibble));
+                    let y = composer
+                        .s_box_and_constraints(
+                            x,
+                            27,
+                            false,
+                            &SboxTable::default(),
+                        )
+                        .y_i;
+                    let q = composer.div(a, y);
+                    assert_eq!(
+                        composer.variables[&q] * composer.variables[&y],
+                        -BlsScalar::from(3)
+                    );
+                });
             },
-            200,
+            PlookupTable4Arity::create_hash_table(),
         );
+        assert!(res.is_ok());
+
+        // Should fail as the divisor is zero
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(42));
+            let zero = composer.add_input(BlsScalar::zero());
+            composer.div(a, zero);
+        });
         assert!(res.is_err());
     }
 }
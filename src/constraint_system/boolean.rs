@@ -6,7 +6,9 @@
 
 use crate::constraint_system::StandardComposer;
 use crate::constraint_system::Variable;
+use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
 
 impl StandardComposer {
     /// Adds a boolean constraint (also known as binary constraint) where
@@ -43,40 +45,279 @@ impl StandardComposer {
 
         a
     }
+
+    /// Returns whether the value of `a` is either `0` or `1`, which allows to
+    /// detect a witness that would fail a [`StandardComposer::boolean_gate`]
+    /// before proving.
+    pub fn is_boolean_witness(&self, a: Variable) -> bool {
+        let value = self.variables[&a];
+        value == BlsScalar::zero() || value == BlsScalar::one()
+    }
+
+    /// Adds the constraints enforcing that `x` is one of the values of `set`,
+    /// generalizing [`StandardComposer::boolean_gate`] to any small set of
+    /// constants with `prod (x - s_i) = 0`.
+    ///
+    /// The product is accumulated with a chain of
+    /// [`StandardComposer::big_mul`], each gate multiplying the running
+    /// product by the next `x - s_i`, and is then constrained to zero, which
+    /// adds as many gates as there are values in `set`. A single value is
+    /// constrained with [`StandardComposer::constrain_to_constant`].
+    ///
+    /// # Panics
+    /// If `set` is empty.
+    pub fn assert_in_set(&mut self, x: Variable, set: &[BlsScalar]) {
+        assert!(!set.is_empty(), "the set of values can't be empty");
+        if set.len() == 1 {
+            self.constrain_to_constant(x, set[0], None);
+            return;
+        }
+
+        // (x - s_0) * (x - s_1) = x^2 - (s_0 + s_1) * x + s_0 * s_1
+        let first = self.big_mul(
+            BlsScalar::one(),
+            x,
+            x,
+            Some((-(set[0] + set[1]), x)),
+            set[0] * set[1],
+            None,
+        );
+        let product = set[2..].iter().fold(first, |acc, s| {
+            // acc * (x - s) = acc * x - s * acc
+            self.big_mul(
+                BlsScalar::one(),
+                acc,
+                x,
+                Some((-*s, acc)),
+                BlsScalar::zero(),
+                None,
+            )
+        });
+        self.constrain_to_constant(product, BlsScalar::zero(), None);
+    }
+
+    /// Decomposes `x` into `num_bits` bits, from the most significant one to
+    /// the least significant one, constraining each of them to be boolean,
+    /// see [`StandardComposer::boolean_gate`], and their weighted sum to be
+    /// equal to `x`.
+    ///
+    /// If `x` doesn't fit in `num_bits` bits, only its `num_bits` least
+    /// significant bits are witnessed and the circuit is unsatisfied. For
+    /// `num_bits` larger than 254, the sum can wrap around the field
+    /// modulus, so the decomposition isn't unique.
+    pub fn to_bits(&mut self, x: Variable, num_bits: usize) -> Vec<Variable> {
+        let bytes = self.variables[&x].to_bytes();
+        let bits: Vec<Variable> = (0..num_bits)
+            .rev()
+            .map(|i| {
+                let bit =
+                    bytes.get(i / 8).map_or(0, |byte| (byte >> (i % 8)) & 1);
+                let bit = self.add_input(BlsScalar::from(bit as u64));
+                self.boolean_gate(bit)
+            })
+            .collect();
+
+        let sum = bits.iter().fold(self.zero_var, |acc, bit| {
+            self.add(
+                (BlsScalar::from(2), acc),
+                (BlsScalar::one(), *bit),
+                BlsScalar::zero(),
+                None,
+            )
+        });
+        self.assert_equal(sum, x);
+
+        bits
+    }
+
+    /// Adds the constraints that check that at least `k` of the
+    /// `conditions` hold.
+    ///
+    /// Every condition is constrained to be boolean by this method, see
+    /// [`StandardComposer::boolean_gate`], so the number of conditions which
+    /// hold is their sum. The gadget then checks that `sum - k` is in the
+    /// range \[0, 2^num_bits\), where `2^num_bits` is larger than the
+    /// number of conditions.
+    pub fn assert_threshold(&mut self, conditions: &[Variable], k: u64) {
+        conditions.iter().for_each(|condition| {
+            self.boolean_gate(*condition);
+        });
+        let sum = self.sum(conditions);
+
+        let diff = self.add(
+            (BlsScalar::one(), sum),
+            (BlsScalar::zero(), self.zero_var),
+            -BlsScalar::from(k),
+            None,
+        );
+
+        // Smallest non-zero even number of bits that can hold the number of
+        // conditions
+        let num_bits = 64 - (conditions.len() as u64).leading_zeros() as usize;
+        self.range_gate(diff, core::cmp::max(2, num_bits + num_bits % 2));
+    }
 }
 
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
     use super::super::helper::*;
+    use crate::constraint_system::StandardComposer;
+    use alloc::vec::Vec;
     use dusk_bls12_381::BlsScalar;
     #[test]
     fn test_correct_bool_gate() {
-        let res = gadget_tester(
-            |composer| {
-                let zero = composer.zero_var();
-                let one = composer.add_input(BlsScalar::one());
-
-                composer.boolean_gate(zero);
-                composer.boolean_gate(one);
-            },
-            32,
-        );
+        let res = gadget_tester(|composer| {
+            let zero = composer.zero_var();
+            let one = composer.add_input(BlsScalar::one());
+
+            composer.boolean_gate(zero);
+            composer.boolean_gate(one);
+        });
         assert!(res.is_ok())
     }
 
     #[test]
     fn test_incorrect_bool_gate() {
-        let res = gadget_tester(
-            |composer| {
-                let zero = composer.add_input(BlsScalar::from(5));
-                let one = composer.add_input(BlsScalar::one());
-
-                composer.boolean_gate(zero);
-                composer.boolean_gate(one);
-            },
-            32,
-        );
+        let res = gadget_tester(|composer| {
+            let zero = composer.add_input(BlsScalar::from(5));
+            let one = composer.add_input(BlsScalar::one());
+
+            composer.boolean_gate(zero);
+            composer.boolean_gate(one);
+        });
         assert!(res.is_err())
     }
+
+    #[test]
+    fn test_is_boolean_witness() {
+        let mut composer = StandardComposer::new();
+        let zero = composer.zero_var();
+        let one = composer.add_input(BlsScalar::one());
+        let two = composer.add_input(BlsScalar::from(2));
+        let minus_one = composer.add_input(-BlsScalar::one());
+
+        assert!(composer.is_boolean_witness(zero));
+        assert!(composer.is_boolean_witness(one));
+        assert!(!composer.is_boolean_witness(two));
+        assert!(!composer.is_boolean_witness(minus_one));
+
+        // Should fail as neither 2 nor -1 are boolean
+        let res = gadget_tester(|composer| {
+            let two = composer.add_input(BlsScalar::from(2));
+            composer.boolean_gate(two);
+        });
+        assert!(res.is_err());
+
+        let res = gadget_tester(|composer| {
+            let minus_one = composer.add_input(-BlsScalar::one());
+            composer.boolean_gate(minus_one);
+        });
+        assert!(res.is_err());
+    }
+
+    /// Decomposes `value` with [`StandardComposer::to_bits`] and returns
+    /// the witnessed bits.
+    fn witness_bits(
+        composer: &mut StandardComposer,
+        value: u64,
+        num_bits: usize,
+    ) -> Vec<u64> {
+        let x = composer.add_input(BlsScalar::from(value));
+        composer
+            .to_bits(x, num_bits)
+            .iter()
+            .map(|bit| (composer.variables[bit] == BlsScalar::one()) as u64)
+            .collect()
+    }
+
+    #[test]
+    fn test_to_bits() {
+        let res = gadget_tester(|composer| {
+            assert_eq!(witness_bits(composer, 5, 3), [1, 0, 1]);
+            assert_eq!(witness_bits(composer, 6, 4), [0, 1, 1, 0]);
+            assert_eq!(witness_bits(composer, 0, 4), [0; 4]);
+            assert_eq!(witness_bits(composer, 1, 1), [1]);
+            assert!(witness_bits(composer, 0, 0).is_empty());
+        });
+        assert!(res.is_ok());
+
+        // 2^8 - 1 is the largest value of 8 bits
+        let res = gadget_tester(|composer| {
+            assert_eq!(witness_bits(composer, 255, 8), [1; 8]);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as 2^8 doesn't fit in 8 bits
+        let res = gadget_tester(|composer| {
+            witness_bits(composer, 256, 8);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_threshold() {
+        // Should pass as exactly 3 out of 5 conditions hold
+        let res = gadget_tester(|composer| {
+            let conditions: Vec<_> = [1u64, 0, 1, 0, 1]
+                .iter()
+                .map(|c| composer.add_input(BlsScalar::from(*c)))
+                .collect();
+            composer.assert_threshold(&conditions, 3);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as only 2 out of 5 conditions hold
+        let res = gadget_tester(|composer| {
+            let conditions: Vec<_> = [1u64, 0, 0, 0, 1]
+                .iter()
+                .map(|c| composer.add_input(BlsScalar::from(*c)))
+                .collect();
+            composer.assert_threshold(&conditions, 3);
+        });
+        assert!(res.is_err());
+
+        // Should pass as all conditions hold
+        let res = gadget_tester(|composer| {
+            let conditions: Vec<_> = [1u64; 5]
+                .iter()
+                .map(|c| composer.add_input(BlsScalar::from(*c)))
+                .collect();
+            composer.assert_threshold(&conditions, 3);
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_assert_in_set() {
+        // Should pass as every value is a member of the set
+        let res = gadget_tester(|composer| {
+            let set = [BlsScalar::zero(), BlsScalar::one(), BlsScalar::from(2)];
+            set.iter().for_each(|value| {
+                let x = composer.add_input(*value);
+                composer.assert_in_set(x, &set);
+            });
+        });
+        assert!(res.is_ok());
+
+        // Should fail as 3 isn't a member of the set
+        let res = gadget_tester(|composer| {
+            let set = [BlsScalar::zero(), BlsScalar::one(), BlsScalar::from(2)];
+            let x = composer.add_input(BlsScalar::from(3));
+            composer.assert_in_set(x, &set);
+        });
+        assert!(res.is_err());
+
+        // Should behave as an equality with a single value
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(5));
+            composer.assert_in_set(x, &[BlsScalar::from(5)]);
+        });
+        assert!(res.is_ok());
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(4));
+            composer.assert_in_set(x, &[BlsScalar::from(5)]);
+        });
+        assert!(res.is_err());
+    }
 }
@@ -20,12 +20,22 @@
 
 use crate::constraint_system::Variable;
 use crate::error::Error;
+use crate::fft::EvaluationDomain;
 use crate::permutation::Permutation;
-use crate::plookup::PlookupTable4Arity;
-use alloc::collections::BTreeMap;
+use crate::plookup::{LookupRow, PlookupTable4Arity};
+use alloc::borrow::Cow;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
-use hashbrown::HashMap;
+use dusk_bytes::Serializable;
+use hashbrown::{HashMap, HashSet};
+
+#[cfg(not(feature = "std"))]
+use core::cell::RefCell;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
 
 /// The StandardComposer is the circuit-builder tool that the `dusk-plonk`
 /// repository provides so that circuit descriptions can be written, stored and
@@ -52,7 +62,7 @@ use hashbrown::HashMap;
 /// Each gate or group of gates adds an specific functionallity or operation to
 /// de circuit description, and so, that's why we can understand
 /// the StandardComposer as a builder.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StandardComposer {
     /// Number of arithmetic gates in the circuit
     pub(crate) n: usize,
@@ -86,6 +96,9 @@ pub struct StandardComposer {
     /// Sparse representation of the Public Inputs linking the positions of the
     /// non-zero ones to it's actual values.
     pub(crate) public_inputs_sparse_store: BTreeMap<usize, BlsScalar>,
+    /// Positions of the Public Inputs declared with
+    /// [`StandardComposer::add_public_input_named`], by name.
+    pub(crate) public_input_names: BTreeMap<String, usize>,
 
     // Witness vectors
     /// Left wire witness vector.
@@ -100,17 +113,138 @@ pub struct StandardComposer {
     /// Public lookup table
     pub lookup_table: PlookupTable4Arity,
 
+    /// Lookup tables appended with
+    /// [`StandardComposer::append_shared_lookup_table`], whose rows follow
+    /// the ones of `lookup_table`.
+    pub(crate) shared_lookup_tables: Vec<Arc<PlookupTable4Arity>>,
+
+    /// Whether preprocessing fails when the plookup gates outnumber the
+    /// rows of the lookup table, see
+    /// [`StandardComposer::set_strict_plookup`].
+    pub(crate) strict_plookup: bool,
+
     /// A zero variable that is a part of the circuit description.
     /// We reserve a variable to be zero in the system
     /// This is so that when a gate only uses three wires, we set the fourth
     /// wire to be the variable that references zero
     pub(crate) zero_var: Variable,
 
+    /// Variables fixed to a constant by
+    /// [`StandardComposer::add_constant_var`], by the bytes of the
+    /// constant.
+    pub(crate) constants: HashMap<[u8; 32], Variable>,
+
     /// These are the actual variable values.
     pub(crate) variables: HashMap<Variable, BlsScalar>,
 
+    /// Canonical forms of the values memoized by
+    /// [`StandardComposer::reduced_witness`].
+    pub(crate) reduced: ReducedWitnesses,
+
     /// Permutation argument.
     pub(crate) perm: Permutation,
+
+    /// Names given to variables with [`StandardComposer::add_input_named`].
+    #[cfg(feature = "trace")]
+    pub(crate) names: HashMap<Variable, String>,
+}
+
+/// Limbs of the canonical forms of the values of some variables, filled by
+/// [`StandardComposer::reduced_witness`].
+///
+/// It's emptied when cloned, and filled again on demand.
+#[derive(Debug, Default)]
+pub(crate) struct ReducedWitnesses(
+    #[cfg(feature = "std")] Mutex<HashMap<Variable, [u64; 4]>>,
+    #[cfg(not(feature = "std"))] RefCell<HashMap<Variable, [u64; 4]>>,
+);
+
+impl ReducedWitnesses {
+    fn with<R>(
+        &self,
+        f: impl FnOnce(&mut HashMap<Variable, [u64; 4]>) -> R,
+    ) -> R {
+        #[cfg(feature = "std")]
+        let mut reduced = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        #[cfg(not(feature = "std"))]
+        let mut reduced = self.0.borrow_mut();
+
+        f(&mut reduced)
+    }
+}
+
+impl Clone for ReducedWitnesses {
+    fn clone(&self) -> Self {
+        ReducedWitnesses::default()
+    }
+}
+
+/// State of a [`StandardComposer`] captured by
+/// [`StandardComposer::snapshot`], which can be brought back with
+/// [`StandardComposer::restore`].
+#[derive(Debug, Clone)]
+pub struct ComposerSnapshot(StandardComposer);
+
+/// Summary of the size of a circuit, returned by
+/// [`StandardComposer::circuit_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitReport {
+    /// Total number of gates in the circuit
+    pub gates: usize,
+    /// Number of gates with the arithmetic selector turned on
+    pub arithmetic_gates: usize,
+    /// Number of plookup gates
+    pub plookup_gates: usize,
+    /// Number of rows of the lookup table
+    pub lookup_table_rows: usize,
+    /// Number of variables added to the circuit
+    pub variables: usize,
+}
+
+/// Selector vectors of a circuit, indexed by gate, returned by
+/// [`StandardComposer::selector_polynomials`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorPolys {
+    /// Multiplier selector
+    pub q_m: Vec<BlsScalar>,
+    /// Left wire selector
+    pub q_l: Vec<BlsScalar>,
+    /// Right wire selector
+    pub q_r: Vec<BlsScalar>,
+    /// Output wire selector
+    pub q_o: Vec<BlsScalar>,
+    /// Fourth wire selector
+    pub q_4: Vec<BlsScalar>,
+    /// Constant wire selector
+    pub q_c: Vec<BlsScalar>,
+    /// Arithmetic wire selector
+    pub q_arith: Vec<BlsScalar>,
+    /// Range selector
+    pub q_range: Vec<BlsScalar>,
+    /// Logic selector
+    pub q_logic: Vec<BlsScalar>,
+    /// Fixed base group addition selector
+    pub q_fixed_group_add: Vec<BlsScalar>,
+    /// Variable base group addition selector
+    pub q_variable_group_add: Vec<BlsScalar>,
+    /// Plookup gate selector
+    pub q_lookup: Vec<BlsScalar>,
+}
+
+/// Values of the wires and public inputs of a circuit, indexed by gate,
+/// returned by [`StandardComposer::export_witness`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessAssignment {
+    /// Values of the left wires
+    pub w_l: Vec<BlsScalar>,
+    /// Values of the right wires
+    pub w_r: Vec<BlsScalar>,
+    /// Values of the output wires
+    pub w_o: Vec<BlsScalar>,
+    /// Values of the fourth wires
+    pub w_4: Vec<BlsScalar>,
+    /// Dense public inputs, see [`StandardComposer::construct_dense_pi_vec`]
+    pub public_inputs: Vec<BlsScalar>,
 }
 
 impl StandardComposer {
@@ -119,9 +253,136 @@ impl StandardComposer {
         self.n
     }
 
+    /// Returns a copy of the selector vectors of the circuit built so far,
+    /// holding one value per gate, before any padding to the domain size.
+    /// This exposes the arithmetization of the circuit to other backends.
+    pub fn selector_polynomials(&self) -> SelectorPolys {
+        SelectorPolys {
+            q_m: self.q_m.clone(),
+            q_l: self.q_l.clone(),
+            q_r: self.q_r.clone(),
+            q_o: self.q_o.clone(),
+            q_4: self.q_4.clone(),
+            q_c: self.q_c.clone(),
+            q_arith: self.q_arith.clone(),
+            q_range: self.q_range.clone(),
+            q_logic: self.q_logic.clone(),
+            q_fixed_group_add: self.q_fixed_group_add.clone(),
+            q_variable_group_add: self.q_variable_group_add.clone(),
+            q_lookup: self.q_lookup.clone(),
+        }
+    }
+
+    /// Returns the values assigned to the wires of every gate of the circuit
+    /// built so far, in gate order, along with the public inputs. Together
+    /// with [`StandardComposer::selector_polynomials`], they allow to check
+    /// the gate equations outside of the prover.
+    pub fn export_witness(&self) -> WitnessAssignment {
+        let values = |wires: &[Variable]| {
+            wires.iter().map(|var| self.variables[var]).collect()
+        };
+
+        WitnessAssignment {
+            w_l: values(&self.w_l),
+            w_r: values(&self.w_r),
+            w_o: values(&self.w_o),
+            w_4: values(&self.w_4),
+            public_inputs: self.construct_dense_pi_vec(),
+        }
+    }
+
+    /// Returns a [`CircuitReport`] with the number of gates of each kind,
+    /// lookup table rows and variables of the circuit, which helps choosing
+    /// the size used to preprocess it.
+    pub fn circuit_report(&self) -> CircuitReport {
+        let count = |selector: &[BlsScalar]| {
+            selector.iter().filter(|q| **q != BlsScalar::zero()).count()
+        };
+
+        CircuitReport {
+            gates: self.n,
+            arithmetic_gates: count(&self.q_arith),
+            plookup_gates: count(&self.q_lookup),
+            lookup_table_rows: self.lookup_table_size(),
+            variables: self.variables.len(),
+        }
+    }
+
     /// Returns the total size of the circuit including lookup table rows
     pub fn total_size(&self) -> usize {
-        core::cmp::max(self.n, self.lookup_table.0.len())
+        core::cmp::max(self.n, self.lookup_table_size())
+    }
+
+    /// Returns [`StandardComposer::total_size`] rounded up to the next power
+    /// of two, which is the size of the evaluation domain the circuit is
+    /// preprocessed over. The public parameters must be trimmed to at least
+    /// this size, or twice it on the prover side.
+    pub fn next_power_of_two_size(&self) -> usize {
+        self.total_size().next_power_of_two()
+    }
+
+    /// Returns the evaluation domain the circuit is preprocessed and proven
+    /// over, of size [`StandardComposer::next_power_of_two_size`], to build
+    /// custom polynomial arguments on top of the circuit. It should only be
+    /// called once every gate and table row is added.
+    ///
+    /// Returns [`Error::InvalidEvalDomainSize`] if the circuit is larger than
+    /// the biggest domain of the field.
+    pub fn domain(&self) -> Result<EvaluationDomain, Error> {
+        EvaluationDomain::new(self.next_power_of_two_size())
+    }
+
+    /// Returns the positions of the declared Public Inputs along with the
+    /// [`Variable`] on the left wire of the gate holding each of them, which
+    /// is the constrained [`Variable`] for Public Inputs declared with
+    /// [`StandardComposer::constrain_to_constant`].
+    pub fn public_input_variables(&self) -> Vec<(usize, Variable)> {
+        self.public_inputs_sparse_store
+            .keys()
+            .map(|pos| (*pos, self.w_l[*pos]))
+            .collect()
+    }
+
+    /// Checks that no [`Variable`] is bound to Public Inputs of different
+    /// values, returning [`Error::ConflictingPublicInputs`] with the gates of
+    /// the first conflict otherwise, as no witness could satisfy both.
+    ///
+    /// The bound value of a Public Input is only known for the gates of
+    /// [`StandardComposer::constrain_to_constant`], which
+    /// `q_l * a + q_c + PI = 0` binds to `a = -(q_c + PI) / q_l`, so the
+    /// other Public Inputs are left unchecked. This is called by
+    /// [`Prover::prove`](crate::proof_system::Prover::prove).
+    pub fn check_public_inputs(&self) -> Result<(), Error> {
+        let zero = BlsScalar::zero();
+        let mut bound: HashMap<Variable, (usize, BlsScalar)> = HashMap::new();
+
+        for (pos, pi) in self.public_inputs_sparse_store.iter() {
+            let pos = *pos;
+            let binds_left_wire = self.q_arith[pos] == BlsScalar::one()
+                && self.q_l[pos] != zero
+                && [self.q_m[pos], self.q_r[pos], self.q_o[pos], self.q_4[pos]]
+                    .iter()
+                    .all(|q| *q == zero);
+            if !binds_left_wire {
+                continue;
+            }
+
+            let value = -(self.q_c[pos] + pi) * self.q_l[pos].invert().unwrap();
+            match bound.get(&self.w_l[pos]) {
+                Some((first, bound_value)) if *bound_value != value => {
+                    return Err(Error::ConflictingPublicInputs {
+                        first: *first,
+                        second: pos,
+                    })
+                }
+                Some(_) => (),
+                None => {
+                    bound.insert(self.w_l[pos], (pos, value));
+                }
+            }
+        }
+
+        Ok(())
     }
 
     /// Constructs a dense vector of the Public Inputs from the positions and
@@ -136,6 +397,42 @@ impl StandardComposer {
         pi
     }
 
+    /// Constructs a dense vector of the Public Inputs declared with
+    /// [`StandardComposer::add_public_input_named`] from their values, given
+    /// by name in any order.
+    ///
+    /// Returns [`Error::MissingPublicInput`] if a named Public Input of the
+    /// circuit has no value, [`Error::UnknownPublicInput`] if a value is
+    /// given for a name that isn't declared, and
+    /// [`Error::DuplicatePublicInput`] if a name is given more than one
+    /// value. Public Inputs declared in any other way are left to zero.
+    pub fn construct_dense_pi_vec_named<'a, I>(
+        &self,
+        values: I,
+    ) -> Result<Vec<BlsScalar>, Error>
+    where
+        I: IntoIterator<Item = (&'a str, BlsScalar)>,
+    {
+        let mut pi = vec![BlsScalar::zero(); self.n];
+        let mut assigned = BTreeSet::new();
+        for (name, value) in values {
+            let pos = self
+                .public_input_names
+                .get(name)
+                .ok_or(Error::UnknownPublicInput)?;
+            if !assigned.insert(name) {
+                return Err(Error::DuplicatePublicInput);
+            }
+            pi[*pos] = -value;
+        }
+
+        if assigned.len() != self.public_input_names.len() {
+            return Err(Error::MissingPublicInput);
+        }
+
+        Ok(pi)
+    }
+
     /// Returns the positions that the Public Inputs occupy in this Composer
     /// instance.
     // TODO: Find a more performant solution which can return a ref to a Vec or
@@ -178,6 +475,25 @@ impl StandardComposer {
         var
     }
 
+    /// Returns a [`Variable`] fixed to `constant` in the circuit description,
+    /// as [`StandardComposer::add_witness_to_circuit_description`] does, but
+    /// shared by every call with the same constant.
+    ///
+    /// Only the first call for a constant adds a gate, so circuits reusing
+    /// many constants stay smaller. Zero is cached as
+    /// [`StandardComposer::zero_var`].
+    pub fn add_constant_var(&mut self, constant: BlsScalar) -> Variable {
+        let key = constant.to_bytes();
+        match self.constants.get(&key) {
+            Some(var) => *var,
+            None => {
+                let var = self.add_witness_to_circuit_description(constant);
+                self.constants.insert(key, var);
+                var
+            }
+        }
+    }
+
     /// Creates a new circuit with an expected circuit size.
     /// This will allow for less reallocations when building the circuit
     /// since the `Vec`s will already have an appropriate allocation at the
@@ -199,6 +515,7 @@ impl StandardComposer {
             q_variable_group_add: Vec::with_capacity(expected_size),
             q_lookup: Vec::with_capacity(expected_size),
             public_inputs_sparse_store: BTreeMap::new(),
+            public_input_names: BTreeMap::new(),
 
             w_l: Vec::with_capacity(expected_size),
             w_r: Vec::with_capacity(expected_size),
@@ -206,17 +523,28 @@ impl StandardComposer {
             w_4: Vec::with_capacity(expected_size),
 
             lookup_table: PlookupTable4Arity::new(),
+            shared_lookup_tables: Vec::new(),
+            strict_plookup: false,
 
             zero_var: Variable(0),
 
+            constants: HashMap::new(),
+
             variables: HashMap::with_capacity(expected_size),
+            reduced: ReducedWitnesses::default(),
 
             perm: Permutation::new(),
+
+            #[cfg(feature = "trace")]
+            names: HashMap::new(),
         };
 
         // Reserve the first variable to be zero
         composer.zero_var =
             composer.add_witness_to_circuit_description(BlsScalar::zero());
+        composer
+            .constants
+            .insert(BlsScalar::zero().to_bytes(), composer.zero_var);
 
         // Add dummy constraints
         composer.add_dummy_constraints();
@@ -224,6 +552,81 @@ impl StandardComposer {
         composer
     }
 
+    /// Resets the composer to the state of a freshly created one, dropping
+    /// all of its gates, variables, public inputs and lookup table rows while
+    /// retaining the capacity of the backing `Vec`s, so they can be reused to
+    /// build the next circuit without re-allocating.
+    ///
+    /// # Note
+    ///
+    /// Every [`Variable`] previously returned by this composer is invalidated
+    /// and must not be used with it anymore.
+    pub fn clear(&mut self) {
+        self.n = 0;
+
+        self.q_m.clear();
+        self.q_l.clear();
+        self.q_r.clear();
+        self.q_o.clear();
+        self.q_c.clear();
+        self.q_4.clear();
+        self.q_arith.clear();
+        self.q_range.clear();
+        self.q_logic.clear();
+        self.q_fixed_group_add.clear();
+        self.q_variable_group_add.clear();
+        self.q_lookup.clear();
+        self.public_inputs_sparse_store.clear();
+        self.public_input_names.clear();
+
+        self.w_l.clear();
+        self.w_r.clear();
+        self.w_o.clear();
+        self.w_4.clear();
+
+        self.lookup_table.0.clear();
+        self.shared_lookup_tables.clear();
+
+        self.variables.clear();
+        self.reduced.with(|reduced| reduced.clear());
+        self.perm.variable_map.clear();
+        self.constants.clear();
+        #[cfg(feature = "trace")]
+        self.names.clear();
+
+        // Reserve the first variable to be zero
+        self.zero_var =
+            self.add_witness_to_circuit_description(BlsScalar::zero());
+        self.constants
+            .insert(BlsScalar::zero().to_bytes(), self.zero_var);
+
+        // Add dummy constraints
+        self.add_dummy_constraints();
+    }
+
+    /// Captures the gates, variables, public inputs and lookup tables of the
+    /// composer, so that alternative gadgets can be tried from this point
+    /// and undone with [`StandardComposer::restore`].
+    ///
+    /// The state is copied, except for the tables appended with
+    /// [`StandardComposer::append_shared_lookup_table`] which are shared
+    /// with the snapshot.
+    pub fn snapshot(&self) -> ComposerSnapshot {
+        ComposerSnapshot(self.clone())
+    }
+
+    /// Brings the composer back to the state captured by `snapshot`, which
+    /// is moved into the composer. Clone the snapshot beforehand to restore
+    /// it more than once.
+    ///
+    /// # Note
+    ///
+    /// Every [`Variable`] created after the snapshot was taken is
+    /// invalidated and must not be used with the composer anymore.
+    pub fn restore(&mut self, snapshot: ComposerSnapshot) {
+        *self = snapshot.0;
+    }
+
     /// Witness representation of zero of the first variable of any circuit
     pub const fn zero_var(&self) -> Variable {
         self.zero_var
@@ -244,6 +647,98 @@ impl StandardComposer {
         var
     }
 
+    /// Returns the value of `var` in canonical form, that is a [`BlsScalar`]
+    /// whose limbs hold the integer representation of the value instead of
+    /// its Montgomery form.
+    ///
+    /// This is the form expected by the out of circuit computations on the
+    /// integer value of a witness, such as the nibble comparisons of the
+    /// Zelbet gadgets, while every [`Variable`] holds, and every gadget takes
+    /// and returns, values in the usual Montgomery form. The result must not
+    /// be used in field arithmetic: [`BlsScalar::from_raw`] converts it back.
+    pub fn witness_canonical(&self, var: Variable) -> BlsScalar {
+        BlsScalar(self.reduced_witness(var))
+    }
+
+    /// Returns the limbs of the value of `var` in canonical form, as
+    /// [`StandardComposer::witness_canonical`] does.
+    ///
+    /// The reduction of every variable is memoized, so gadgets reading the
+    /// integer value of the same witness many times, such as the
+    /// decomposition and the s-box of Zelbet, only reduce it once. The values
+    /// of the variables never change, so the cache is only dropped by
+    /// [`StandardComposer::clear`].
+    pub fn reduced_witness(&self, var: Variable) -> [u64; 4] {
+        self.reduced.with(|reduced| {
+            *reduced
+                .entry(var)
+                .or_insert_with(|| self.variables[&var].reduce().0)
+        })
+    }
+
+    /// Same as [`StandardComposer::add_input`], also labelling the returned
+    /// [`Variable`] with `name` for debugging.
+    ///
+    /// The names are only kept with the `trace` feature, which reports them
+    /// when [`StandardComposer::check_circuit_satisfied`] finds an
+    /// unsatisfied gate. Otherwise they are dropped, so that proving isn't
+    /// affected.
+    pub fn add_input_named(&mut self, s: BlsScalar, name: &str) -> Variable {
+        let var = self.add_input(s);
+        #[cfg(feature = "trace")]
+        self.names.insert(var, name.to_string());
+        #[cfg(not(feature = "trace"))]
+        let _ = name;
+
+        var
+    }
+
+    /// Adds a Public Input with the given `name`, returning the [`Variable`]
+    /// constrained to be equal to it.
+    ///
+    /// The verifier doesn't need to know the position of the Public Input in
+    /// the circuit: it passes the values by name to
+    /// [`Verifier::verify_named`], which places them internally.
+    ///
+    /// # Panics
+    ///
+    /// If a Public Input with the same `name` was already added.
+    ///
+    /// [`Verifier::verify_named`]:
+    /// crate::proof_system::Verifier::verify_named
+    pub fn add_public_input_named(
+        &mut self,
+        value: BlsScalar,
+        name: &str,
+    ) -> Variable {
+        let var = self.add_input_named(value, name);
+        assert!(
+            self.public_input_names
+                .insert(name.to_string(), self.n)
+                .is_none(),
+            "a public input named {} was already added",
+            name
+        );
+        self.constrain_to_constant(var, BlsScalar::zero(), Some(-value));
+
+        var
+    }
+
+    /// Returns the name given to `var` with
+    /// [`StandardComposer::add_input_named`].
+    #[cfg(feature = "trace")]
+    pub fn name_of(&self, var: Variable) -> Option<&str> {
+        self.names.get(&var).map(|name| name.as_str())
+    }
+
+    /// Returns the name given to `var` with
+    /// [`StandardComposer::add_input_named`], which is always `None` without
+    /// the `trace` feature.
+    #[cfg(not(feature = "trace"))]
+    pub fn name_of(&self, _var: Variable) -> Option<&str> {
+        None
+    }
+
     /// This pushes the result of a lookup read to a gate
     pub fn lookup_gate(
         &mut self,
@@ -352,6 +847,26 @@ impl StandardComposer {
         );
     }
 
+    /// Same as [`StandardComposer::constrain_to_constant`] without Public
+    /// Input, but also checks out of circuit that the witness of `a` equals
+    /// `constant`, returning [`Error::ConstantMismatch`] otherwise instead of
+    /// waiting for the proof to fail.
+    ///
+    /// The gate is appended in both cases, so the circuit description
+    /// doesn't depend on the witness.
+    pub fn try_constrain_to_constant(
+        &mut self,
+        a: Variable,
+        constant: BlsScalar,
+    ) -> Result<(), Error> {
+        self.constrain_to_constant(a, constant, None);
+
+        match self.variables[&a] == constant {
+            true => Ok(()),
+            false => Err(Error::ConstantMismatch),
+        }
+    }
+
     /// Add a constraint into the circuit description that states that two
     /// [`Variable`]s are equal.
     pub fn assert_equal(&mut self, a: Variable, b: Variable) {
@@ -374,41 +889,31 @@ impl StandardComposer {
     /// bit == 1 => choice_a,
     /// bit == 0 => choice_b,
     ///
-    /// # Note
-    /// The `bit` used as input which is a [`Variable`] should had previously
-    /// been constrained to be either 1 or 0 using a bool constrain. See:
-    /// [`StandardComposer::boolean_gate`].
+    /// The `bit` is constrained to be either 1 or 0 by this method, see
+    /// [`StandardComposer::boolean_gate`], and the selection
+    /// `choice_b + bit * (choice_a - choice_b)` takes two more gates.
     pub fn conditional_select(
         &mut self,
         bit: Variable,
         choice_a: Variable,
         choice_b: Variable,
     ) -> Variable {
-        // bit * choice_a
-        let bit_times_a =
-            self.mul(BlsScalar::one(), bit, choice_a, BlsScalar::zero(), None);
-
-        // 1 - bit
-        let one_min_bit = self.add(
-            (-BlsScalar::one(), bit),
-            (BlsScalar::zero(), self.zero_var),
-            BlsScalar::one(),
-            None,
-        );
+        self.boolean_gate(bit);
 
-        // (1 - bit) * b
-        let one_min_bit_choice_b = self.mul(
-            BlsScalar::one(),
-            one_min_bit,
-            choice_b,
+        // choice_a - choice_b
+        let diff = self.add(
+            (BlsScalar::one(), choice_a),
+            (-BlsScalar::one(), choice_b),
             BlsScalar::zero(),
             None,
         );
 
-        // [ (1 - bit) * b ] + [ bit * a ]
-        self.add(
-            (BlsScalar::one(), one_min_bit_choice_b),
-            (BlsScalar::one(), bit_times_a),
+        // bit * (choice_a - choice_b) + choice_b
+        self.big_mul(
+            BlsScalar::one(),
+            bit,
+            diff,
+            Some((BlsScalar::one(), choice_b)),
             BlsScalar::zero(),
             None,
         )
@@ -468,6 +973,14 @@ impl StandardComposer {
         f_x
     }
 
+    /// Number of rows added to the lookup table by
+    /// [`StandardComposer::add_dummy_constraints`]
+    pub(crate) const DUMMY_LOOKUP_ROWS: usize = 3;
+
+    /// Number of gates with the lookup selector turned on added by
+    /// [`StandardComposer::add_dummy_constraints`]
+    pub(crate) const DUMMY_LOOKUP_GATES: usize = 2;
+
     /// This function is used to add a blinding factor to the witness
     /// polynomials. It essentially adds two dummy gates to the circuit
     /// description which are guaranteed to always satisfy the gate equation.
@@ -575,6 +1088,27 @@ impl StandardComposer {
     /// the cause is an unsatisfied gate equation, the function will panic.
     #[cfg(feature = "trace")]
     pub fn check_circuit_satisfied(&self) {
+        if let Err(Error::Unsatisfiable { gate, .. }) =
+            self.try_check_circuit_satisfied()
+        {
+            let name = |var: &Variable| self.name_of(*var).unwrap_or("-");
+            panic!(
+                "Check failed at gate {} (w_l: {}, w_r: {}, w_o: {}, w_4: {})",
+                gate,
+                name(&self.w_l[gate]),
+                name(&self.w_r[gate]),
+                name(&self.w_o[gate]),
+                name(&self.w_4[gate]),
+            );
+        }
+    }
+
+    /// Same as [`StandardComposer::check_circuit_satisfied`], but returns
+    /// [`Error::Unsatisfiable`] with the index and the wire values of the
+    /// first gate whose equation doesn't hold instead of panicking. The
+    /// lookups are checked separately by
+    /// [`StandardComposer::precheck_lookups`].
+    pub fn try_check_circuit_satisfied(&self) -> Result<(), Error> {
         let w_l: Vec<&BlsScalar> = self
             .w_l
             .iter()
@@ -694,8 +1228,15 @@ impl StandardComposer {
                         + delta(a - four * b)
                         + delta(d_next - four * a));
 
-            assert_eq!(k, BlsScalar::zero(), "Check failed at gate {}", i,);
+            if k != BlsScalar::zero() {
+                return Err(Error::Unsatisfiable {
+                    gate: i,
+                    wires: [*a, *b, *c, *d],
+                });
+            }
         }
+
+        Ok(())
     }
 
     /// Adds a plookup gate to the circuit with its corresponding
@@ -705,16 +1246,33 @@ impl StandardComposer {
     /// the largest amount of performance and the minimum circuit-size
     /// possible. Since it allows the end-user to set every selector coefficient
     /// as scaling value on the gate eq.
-    pub fn plookup_gate(
+    ///
+    /// The columns of the query are given as a [`LookupRow`]; the positional
+    /// form `(a, b, c, d)` converts into one, with `d` being the tag.
+    ///
+    /// # Panics
+    /// If no lookup table was added to the composer, see
+    /// [`StandardComposer::has_lookup_table`].
+    pub fn plookup_gate<R: Into<LookupRow>>(
         &mut self,
-        a: Variable,
-        b: Variable,
-        c: Variable,
-        d: Option<Variable>,
+        row: R,
         pi: BlsScalar,
     ) -> Variable {
+        assert!(
+            self.has_lookup_table(),
+            "plookup gate added without a lookup table, append one with \
+             `StandardComposer::append_lookup_table` first"
+        );
+
+        let LookupRow {
+            query: a,
+            aux: b,
+            output: c,
+            tag,
+        } = row.into();
+
         // Check if advice wire has a value
-        let d = match d {
+        let d = match tag {
             Some(var) => var,
             None => self.zero_var,
         };
@@ -748,22 +1306,317 @@ impl StandardComposer {
         c
     }
 
+    /// Same as [`StandardComposer::plookup_gate`], but returns
+    /// [`Error::TableMissing`] instead of panicking if no lookup table was
+    /// added to the composer, in which case no gate is appended.
+    pub fn try_plookup_gate<R: Into<LookupRow>>(
+        &mut self,
+        row: R,
+        pi: BlsScalar,
+    ) -> Result<Variable, Error> {
+        match self.has_lookup_table() {
+            true => Ok(self.plookup_gate(row, pi)),
+            false => Err(Error::TableMissing),
+        }
+    }
+
+    /// Adds a plookup gate, see [`StandardComposer::plookup_gate`], querying
+    /// the table `table_id` of a table built with
+    /// [`PlookupTable4Arity::merge_tagged`].
+    ///
+    /// The tag of `row`, or zero if it has none, is offset by
+    /// [`PlookupTable4Arity::table_tag`] before the lookup.
+    pub fn tagged_plookup_gate<R: Into<LookupRow>>(
+        &mut self,
+        row: R,
+        table_id: u64,
+        pi: BlsScalar,
+    ) -> Variable {
+        let row = row.into();
+        let tag = row.tag.unwrap_or(self.zero_var);
+        let tagged = self.add(
+            (BlsScalar::one(), tag),
+            (BlsScalar::zero(), self.zero_var),
+            PlookupTable4Arity::table_tag(table_id),
+            None,
+        );
+
+        self.plookup_gate(
+            LookupRow {
+                tag: Some(tagged),
+                ..row
+            },
+            pi,
+        )
+    }
+
+    /// Adds a plookup gate, see [`StandardComposer::plookup_gate`], whose
+    /// output is resolved from `table` rather than given, returning it.
+    ///
+    /// The first row of `table` whose first two columns hold the values of
+    /// `a` and `b` supplies the output and the tag, which are both witnessed.
+    /// `table` should be the one appended to the composer, such as
+    /// [`PlookupTable4Arity::create_hash_table`] mapping a nibble and its
+    /// position to its s-box output. If no row matches, the output and the
+    /// tag are witnessed as zero and the lookup fails at proving.
+    pub fn plookup_query(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        table: &PlookupTable4Arity,
+    ) -> Variable {
+        let a_value = self.variables[&a];
+        let b_value = self.variables[&b];
+        let (c, d) = table
+            .0
+            .iter()
+            .find(|row| row[0] == a_value && row[1] == b_value)
+            .map_or((BlsScalar::zero(), BlsScalar::zero()), |row| {
+                (row[2], row[3])
+            });
+
+        let c = self.add_input(c);
+        let d = self.add_input(d);
+        self.plookup_gate(
+            LookupRow {
+                query: a,
+                aux: b,
+                output: c,
+                tag: Some(d),
+            },
+            BlsScalar::zero(),
+        )
+    }
+
+    /// Adds a plookup gate looking `a` up in the table `tables[selector]`,
+    /// returning the output of the matching row.
+    ///
+    /// The tables must map a single input to its output, with rows
+    /// `(a, 0, output, 0)` such as the ones of
+    /// [`PlookupTable4Arity::from_fn`], and the table
+    /// [`PlookupTable4Arity::merge_tagged`] builds out of `tables` must be
+    /// appended to the composer. The lookup carries the
+    /// [`PlookupTable4Arity::table_tag`] of `selector`, so only the rows of
+    /// the selected table can match it, and `selector` is also constrained
+    /// to be the index of one of the `tables`.
+    ///
+    /// # Panics
+    ///
+    /// If `tables` is empty.
+    pub fn conditional_lookup(
+        &mut self,
+        selector: Variable,
+        a: Variable,
+        tables: &[&PlookupTable4Arity],
+    ) -> Variable {
+        assert!(!tables.is_empty(), "no table to select from");
+
+        // selector * (selector - 1) * ... * (selector - tables.len() + 1) = 0
+        let roots = (1..tables.len() as u64).fold(selector, |product, i| {
+            self.big_mul(
+                BlsScalar::one(),
+                product,
+                selector,
+                Some((-BlsScalar::from(i), product)),
+                BlsScalar::zero(),
+                None,
+            )
+        });
+        self.constrain_to_constant(roots, BlsScalar::zero(), None);
+
+        let index = self.witness_canonical(selector).0[0];
+        let a_value = self.variables[&a];
+        let output = tables
+            .get(index as usize)
+            .and_then(|table| {
+                table.0.iter().find(|row| {
+                    row[0] == a_value
+                        && row[1] == BlsScalar::zero()
+                        && row[3] == BlsScalar::zero()
+                })
+            })
+            .map_or(BlsScalar::zero(), |row| row[2]);
+
+        let output = self.add_input(output);
+        let tag = self.add(
+            (PlookupTable4Arity::table_tag(1), selector),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::zero(),
+            None,
+        );
+        self.plookup_gate(
+            LookupRow {
+                query: a,
+                aux: self.zero_var,
+                output,
+                tag: Some(tag),
+            },
+            BlsScalar::zero(),
+        )
+    }
+
+    /// Returns `true` if the lookup table of the composer holds any row
+    /// besides the ones added by
+    /// [`StandardComposer::add_dummy_constraints`].
+    pub fn has_lookup_table(&self) -> bool {
+        self.lookup_table_size() > Self::DUMMY_LOOKUP_ROWS
+    }
+
     /// When StandardComposer is initialised, it spawns a dummy table
     /// with 3 entries that should not be removed. This function appends
     /// its input table to the composer's dummy table
     pub fn append_lookup_table(&mut self, table: &PlookupTable4Arity) {
         table.0.iter().for_each(|k| self.lookup_table.0.push(*k))
     }
+
+    /// Appends `table` to the lookup table of the composer without copying
+    /// its rows, so that a large table, such as
+    /// [`PlookupTable4Arity::create_hash_table`], can be shared by the
+    /// composers of many circuits, including across threads.
+    ///
+    /// The shared tables are only read, and their rows come after the ones
+    /// of [`StandardComposer::lookup_table`], in the order the tables were
+    /// appended, see [`StandardComposer::lookup_rows`]. The prover and the
+    /// verifier of a circuit must append them in the same way.
+    pub fn append_shared_lookup_table(
+        &mut self,
+        table: Arc<PlookupTable4Arity>,
+    ) {
+        self.shared_lookup_tables.push(table)
+    }
+
+    /// Returns every row of the lookup table of the composer, the ones of
+    /// [`StandardComposer::lookup_table`] followed by the ones of the tables
+    /// appended with [`StandardComposer::append_shared_lookup_table`].
+    pub fn lookup_rows(&self) -> impl Iterator<Item = &[BlsScalar; 4]> {
+        self.lookup_table.0.iter().chain(
+            self.shared_lookup_tables
+                .iter()
+                .flat_map(|table| table.0.iter()),
+        )
+    }
+
+    /// Makes the preprocessing of the circuit fail with
+    /// [`Error::LookupTableTooSmall`] when its plookup gates outnumber the
+    /// rows of its lookup table, instead of only logging a warning. Neither
+    /// count includes the dummy gates and rows every composer starts with.
+    ///
+    /// The setting is kept by [`StandardComposer::clear`].
+    pub fn set_strict_plookup(&mut self, strict: bool) {
+        self.strict_plookup = strict;
+    }
+
+    /// Checks that the plookup gates don't outnumber the rows of the lookup
+    /// table, see [`StandardComposer::set_strict_plookup`].
+    pub(crate) fn check_lookup_table_size(&self) -> Result<(), Error> {
+        let report = self.circuit_report();
+        let gates = report
+            .plookup_gates
+            .saturating_sub(Self::DUMMY_LOOKUP_GATES);
+        let rows = report
+            .lookup_table_rows
+            .saturating_sub(Self::DUMMY_LOOKUP_ROWS);
+        if gates <= rows {
+            return Ok(());
+        }
+
+        match self.strict_plookup {
+            true => Err(Error::LookupTableTooSmall { gates, rows }),
+            false => {
+                log_warn!(
+                    "{} plookup gates outnumber the {} lookup table rows",
+                    gates,
+                    rows
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the number of rows of [`StandardComposer::lookup_rows`].
+    pub fn lookup_table_size(&self) -> usize {
+        self.shared_lookup_tables
+            .iter()
+            .fold(self.lookup_table.0.len(), |size, table| {
+                size + table.0.len()
+            })
+    }
+
+    /// Returns the lookup table committed to by the plookup argument, which
+    /// is only copied if some tables are shared.
+    pub(crate) fn full_lookup_table(&self) -> Cow<'_, PlookupTable4Arity> {
+        match self.shared_lookup_tables.is_empty() {
+            true => Cow::Borrowed(&self.lookup_table),
+            false => Cow::Owned(PlookupTable4Arity::from(
+                self.lookup_rows().copied().collect::<Vec<_>>(),
+            )),
+        }
+    }
+
+    /// Appends every table of `tables`, in order, with
+    /// [`StandardComposer::append_lookup_table`]. Lookups then resolve
+    /// against the union of their rows, which is committed to as a single
+    /// table by the plookup argument.
+    ///
+    /// Tables sharing inputs with different outputs make the lookups
+    /// ambiguous, see [`PlookupTable4Arity::merge_tagged`] to keep them
+    /// apart.
+    pub fn append_lookup_tables(&mut self, tables: &[&PlookupTable4Arity]) {
+        tables
+            .iter()
+            .for_each(|table| self.append_lookup_table(table))
+    }
+
+    /// Checks that the values queried by every lookup gate are rows of the
+    /// lookup table, so that an unsatisfiable lookup can be found before
+    /// proving.
+    ///
+    /// On failure, returns the index of every failing gate together with the
+    /// queried values, ordered as `(w_l, w_r, w_o, w_4)`.
+    pub fn precheck_lookups(&self) -> Result<(), Vec<(usize, [BlsScalar; 4])>> {
+        let to_bytes = |row: &[BlsScalar; 4]| {
+            [
+                row[0].to_bytes(),
+                row[1].to_bytes(),
+                row[2].to_bytes(),
+                row[3].to_bytes(),
+            ]
+        };
+        let table: HashSet<_> = self.lookup_rows().map(to_bytes).collect();
+
+        let failures: Vec<_> = self
+            .q_lookup
+            .iter()
+            .enumerate()
+            .filter(|(_, q_lookup)| **q_lookup == BlsScalar::one())
+            .map(|(gate, _)| {
+                let row = [
+                    self.variables[&self.w_l[gate]],
+                    self.variables[&self.w_r[gate]],
+                    self.variables[&self.w_o[gate]],
+                    self.variables[&self.w_4[gate]],
+                ];
+                (gate, row)
+            })
+            .filter(|(_, row)| !table.contains(&to_bytes(row)))
+            .collect();
+
+        match failures.is_empty() {
+            true => Ok(()),
+            false => Err(failures),
+        }
+    }
 }
 
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::commitment_scheme::kzg10::{Commitment, PublicParameters};
     use crate::constraint_system::helper::*;
+    use crate::plookup::table::hash_tables::SboxTable;
     use crate::plookup::PlookupTable4Arity;
-    use crate::proof_system::{Prover, Verifier};
+    use crate::proof_system::{Proof, Prover, Verifier};
     use rand_core::OsRng;
 
     #[test]
@@ -785,12 +1638,9 @@ mod tests {
     #[ignore]
     /// Tests that an empty circuit proof passes
     fn test_prove_verify() {
-        let res = gadget_tester(
-            |composer| {
-                // do nothing except add the dummy constraints
-            },
-            200,
-        );
+        let res = gadget_tester(|composer| {
+            // do nothing except add the dummy constraints
+        });
         assert!(res.is_ok());
     }
 
@@ -831,18 +1681,50 @@ mod tests {
                     composer.conditional_select(bit_0, choice_a, choice_b);
                 composer.assert_equal(choice, choice_b);
             },
-            65,
             t,
         );
         assert!(res.is_ok());
     }
 
     #[test]
-    #[should_panic]
-    fn test_gadget_fail() {
-        let mut t = PlookupTable4Arity::new();
-        t.insert_special_row(
-            BlsScalar::from(12),
+    fn test_assert_equal() {
+        // Should pass as both witnesses hold the same value
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(7u64));
+            let b = composer.add_input(BlsScalar::from(7u64));
+            composer.assert_equal(a, b);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as the witnesses differ
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(7u64));
+            let b = composer.add_input(BlsScalar::from(8u64));
+            composer.assert_equal(a, b);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_conditional_select_non_boolean() {
+        // Should fail as the bit is neither 0 nor 1
+        let res = gadget_tester(|composer| {
+            let bit = composer.add_input(BlsScalar::from(2u64));
+            let choice_a = composer.add_input(BlsScalar::from(10u64));
+            let choice_b = composer.add_input(BlsScalar::from(20u64));
+
+            let choice = composer.conditional_select(bit, choice_a, choice_b);
+            composer.constrain_to_constant(choice, BlsScalar::zero(), None);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gadget_fail() {
+        let mut t = PlookupTable4Arity::new();
+        t.insert_special_row(
+            BlsScalar::from(12),
             BlsScalar::from(12),
             BlsScalar::from(12),
             BlsScalar::from(12),
@@ -854,134 +1736,1620 @@ mod tests {
                 let three = composer
                     .add_witness_to_circuit_description(BlsScalar::from(3));
                 composer.plookup_gate(
-                    twelve,
-                    twelve,
-                    twelve,
-                    Some(three),
+                    (twelve, twelve, twelve, Some(three)),
                     BlsScalar::zero(),
                 );
-            },
-            65,
-            t,
-        );
-        assert!(res.is_err());
+            },
+            t,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    // XXX: Move this to integration tests
+    fn test_multiple_proofs() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+
+        // Create a prover struct
+        let mut prover = Prover::new(b"demo");
+
+        // Add gadgets
+        dummy_gadget(10, prover.mut_cs());
+
+        // Commit Key
+        let (ck, _) = public_parameters.trim(2 * 20).unwrap();
+
+        // Preprocess circuit
+        prover.preprocess(&ck).unwrap();
+
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+
+        let mut proofs = Vec::new();
+
+        // Compute multiple proofs
+        for _ in 0..3 {
+            proofs.push(prover.prove(&ck).unwrap());
+
+            // Add another witness instance
+            dummy_gadget(10, prover.mut_cs());
+        }
+
+        // Verifier
+        //
+        let mut verifier = Verifier::new(b"demo");
+
+        // Add gadgets
+        dummy_gadget(10, verifier.mut_cs());
+
+        // Commit and Verifier Key
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        // Preprocess
+        verifier.preprocess(&ck).unwrap();
+
+        for proof in proofs {
+            assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_clear() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(25, prover.mut_cs());
+        let capacity = prover.cs.q_m.capacity();
+
+        // Reuse the composer for a smaller circuit
+        prover.mut_cs().clear();
+        assert_eq!(prover.cs.circuit_size(), 3);
+        assert_eq!(prover.cs.q_m.capacity(), capacity);
+
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let res = gadget_tester(|composer| {
+            dummy_gadget(5, composer);
+            let a = composer.add_input(BlsScalar::from(3));
+            let snapshot = composer.snapshot();
+            let gates = composer.circuit_size();
+            let variables = composer.variables.len();
+
+            // An unsatisfied branch, undone before proving
+            let b = composer.add_input(BlsScalar::from(4));
+            composer.assert_equal(a, b);
+            composer.add_public_input_named(BlsScalar::one(), "x");
+            composer.constrain_to_constant(a, BlsScalar::from(5), None);
+            assert_eq!(composer.circuit_size(), gates + 3);
+
+            composer.restore(snapshot.clone());
+            assert_eq!(composer.circuit_size(), gates);
+            assert_eq!(composer.variables.len(), variables);
+            assert!(composer.public_input_names.is_empty());
+
+            // Another branch from the same snapshot
+            composer.constrain_to_constant(a, BlsScalar::from(5), None);
+            composer.restore(snapshot);
+            assert_eq!(composer.circuit_size(), gates);
+
+            composer.constrain_to_constant(a, BlsScalar::from(3), None);
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_deterministic_prover() {
+        // Proofs don't depend on the prover instance, so equal circuits give
+        // equal proofs
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * 20).unwrap();
+
+        let prove = || {
+            let mut prover = Prover::new(b"demo");
+            prover.mut_cs().lookup_table.insert_multi_mul(0, 3);
+            let two = prover.mut_cs().add_input(BlsScalar::from(2));
+            let three = prover.mut_cs().add_input(BlsScalar::from(3));
+            let six = prover.mut_cs().add_input(BlsScalar::from(6));
+            let one = prover.mut_cs().add_input(BlsScalar::one());
+            prover
+                .mut_cs()
+                .plookup_gate((two, three, six, Some(one)), BlsScalar::zero());
+            dummy_gadget(10, prover.mut_cs());
+            prover.preprocess(&ck).unwrap();
+            prover.prove(&ck).unwrap()
+        };
+
+        assert_eq!(prove().to_bytes()[..], prove().to_bytes()[..]);
+
+        // Nor on how rayon splits the work with the `parallel` feature: a
+        // single thread runs every split in order, as the serial path does
+        #[cfg(feature = "parallel")]
+        {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap();
+            assert_eq!(
+                pool.install(&prove).to_bytes()[..],
+                prove().to_bytes()[..]
+            );
+        }
+    }
+
+    #[test]
+    fn test_versioned_proof_bytes() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, ok) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        prover.preprocess(&ck).unwrap();
+        let bytes = prover.prove(&ck).unwrap().to_versioned_bytes();
+
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        let proof = Proof::from_versioned_bytes(&bytes).unwrap();
+        assert!(verifier.verify(&proof, &ok, &public_inputs).is_ok());
+
+        // Flipping a byte of the version, a commitment, an evaluation or the
+        // circuit digest is caught either when decoding or when verifying
+        let evaluations = 1 + 15 * Commitment::SIZE;
+        for &i in &[0, 1, 100, evaluations, bytes.len() - 1] {
+            let mut corrupted = bytes.clone();
+            corrupted[i] ^= 1;
+            let rejected = match Proof::from_versioned_bytes(&corrupted) {
+                Ok(proof) => {
+                    verifier.verify(&proof, &ok, &public_inputs).is_err()
+                }
+                Err(_) => true,
+            };
+            assert!(rejected, "corrupted byte {} was accepted", i);
+        }
+    }
+
+    #[test]
+    fn test_transcript_seed() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, ok) = public_parameters.trim(2 * 20).unwrap();
+
+        let prove = |mut prover: Prover| {
+            dummy_gadget(10, prover.mut_cs());
+            prover.preprocess(&ck).unwrap();
+            prover.prove(&ck).unwrap()
+        };
+
+        let mut prover = Prover::with_transcript_seed(b"audit", b"seed");
+        dummy_gadget(10, prover.mut_cs());
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        prover.preprocess(&ck).unwrap();
+        let proof = prover.prove(&ck).unwrap();
+        let again = prove(Prover::with_transcript_seed(b"audit", b"seed"));
+        assert_eq!(proof.to_bytes()[..], again.to_bytes()[..]);
+
+        let mut verifier = Verifier::with_transcript_seed(b"audit", b"seed");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &ok, &public_inputs).is_ok());
+
+        // Another label or seed changes the challenges, and thus the proof
+        let other_label = prove(Prover::with_transcript_label(b"other"));
+        let other_seed = prove(Prover::with_transcript_seed(b"audit", b"x"));
+        assert_ne!(proof.to_bytes()[..], other_label.to_bytes()[..]);
+        assert_ne!(proof.to_bytes()[..], other_seed.to_bytes()[..]);
+
+        let mut verifier = Verifier::new(b"audit");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &ok, &public_inputs).is_err());
+    }
+
+    #[test]
+    fn test_cached_prover() {
+        let public_parameters =
+            PublicParameters::setup(2 * 70, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * 64).unwrap();
+
+        // The cache is filled by the first proof and reused by the second
+        let mut cached = Prover::new(b"demo");
+        dummy_gadget(10, cached.mut_cs());
+        cached.preprocess(&ck).unwrap();
+        cached.prove(&ck).unwrap();
+        let domain = cached.cache.as_ref().unwrap().domain;
+        dummy_gadget(10, cached.mut_cs());
+        let cached_proof = cached.prove(&ck).unwrap();
+        assert_eq!(cached.cache.as_ref().unwrap().domain, domain);
+
+        let mut fresh = Prover::new(b"demo");
+        dummy_gadget(10, fresh.mut_cs());
+        fresh.preprocess(&ck).unwrap();
+        let fresh_proof = fresh.prove(&ck).unwrap();
+
+        assert_eq!(cached_proof.to_bytes()[..], fresh_proof.to_bytes()[..]);
+
+        // A cache of a different size is discarded
+        cached.clear();
+        dummy_gadget(40, cached.mut_cs());
+        cached.preprocess(&ck).unwrap();
+        cached.cache = fresh.cache.take();
+        cached.prove(&ck).unwrap();
+        assert_ne!(cached.cache.as_ref().unwrap().domain, domain);
+    }
+
+    #[test]
+    fn test_hashed_public_inputs() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, ok) = public_parameters.trim(2 * 20).unwrap();
+
+        fn gadget(composer: &mut StandardComposer, x: BlsScalar) {
+            let x = composer.add_input(x);
+            composer.constrain_to_constant(
+                x,
+                BlsScalar::zero(),
+                Some(-BlsScalar::from(7)),
+            );
+            dummy_gadget(10, composer);
+        }
+
+        let mut prover = Prover::new(b"demo");
+        gadget(prover.mut_cs(), BlsScalar::from(7));
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        gadget(prover.mut_cs(), BlsScalar::from(7));
+        let hashed_proof = prover.prove_with_hashed_pi(&ck).unwrap();
+
+        // The hash is only bound to the transcript of the hashed proof
+        gadget(prover.mut_cs(), BlsScalar::from(7));
+        assert_eq!(
+            prover.prove(&ck).unwrap().to_bytes()[..],
+            proof.to_bytes()[..]
+        );
+
+        let mut verifier = Verifier::new(b"demo");
+        gadget(verifier.mut_cs(), BlsScalar::zero());
+        verifier.preprocess(&ck).unwrap();
+
+        assert!(verifier.verify(&proof, &ok, &public_inputs).is_ok());
+        assert!(verifier
+            .verify_with_hashed_pi(&hashed_proof, &ok, &public_inputs)
+            .is_ok());
+
+        // Each proof is only accepted by its own configuration
+        assert!(verifier.verify(&hashed_proof, &ok, &public_inputs).is_err());
+        assert!(verifier
+            .verify_with_hashed_pi(&proof, &ok, &public_inputs)
+            .is_err());
+
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs[verifier.circuit_size() - 1] = BlsScalar::one();
+        assert!(verifier
+            .verify_with_hashed_pi(&hashed_proof, &ok, &wrong_inputs)
+            .is_err());
+    }
+
+    #[test]
+    fn test_committed_public_inputs() {
+        use crate::fft::EvaluationDomain;
+        use crate::proof_system::disclosure::hidden_quotient;
+        use crate::proof_system::{
+            CommittedPublicInputs, ProofPublicInputs, PublicInputSpec,
+        };
+
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, ok) = public_parameters.trim(2 * 20).unwrap();
+
+        fn gadget(composer: &mut StandardComposer, inputs: [u64; 4]) {
+            inputs.iter().for_each(|input| {
+                let x = composer.add_input(BlsScalar::from(*input));
+                composer.constrain_to_constant(
+                    x,
+                    BlsScalar::zero(),
+                    Some(-BlsScalar::from(*input)),
+                );
+            });
+            dummy_gadget(10, composer);
+        }
+
+        let mut prover = Prover::new(b"disclosure");
+        gadget(prover.mut_cs(), [1, 2, 3, 4]);
+        let positions: Vec<usize> = prover
+            .cs
+            .public_input_variables()
+            .iter()
+            .map(|(pos, _)| *pos)
+            .collect();
+        let hidden = [positions[1], positions[3]];
+
+        // Reveals the first and third inputs only
+        let dense = prover.cs.construct_dense_pi_vec();
+        let spec: Vec<PublicInputSpec> = dense
+            .iter()
+            .enumerate()
+            .map(|(pos, value)| match hidden.contains(&pos) {
+                true => PublicInputSpec::Committed,
+                false => PublicInputSpec::Revealed(*value),
+            })
+            .collect();
+        let (proof, committed) =
+            prover.prove_with_committed_pi(&ck, &hidden).unwrap();
+
+        let mut verifier = Verifier::new(b"disclosure");
+        gadget(verifier.mut_cs(), [0; 4]);
+        verifier.preprocess(&ck).unwrap();
+        let verify = |proof: &Proof,
+                      specs: &[PublicInputSpec],
+                      committed: &CommittedPublicInputs| {
+            verifier.verify(
+                proof,
+                &ok,
+                ProofPublicInputs::Disclosed { specs, committed },
+            )
+        };
+
+        assert!(verify(&proof, &spec, &committed).is_ok());
+
+        // The hidden inputs are not in the revealed ones
+        let revealed: Vec<BlsScalar> = spec
+            .iter()
+            .map(|input| match input {
+                PublicInputSpec::Revealed(value) => *value,
+                PublicInputSpec::Committed => BlsScalar::zero(),
+            })
+            .collect();
+        assert!(verifier.verify(&proof, &ok, &revealed).is_err());
+        assert!(verifier.verify(&proof, &ok, &dense).is_err());
+
+        let mut wrong_spec = spec.clone();
+        wrong_spec[positions[0]] = PublicInputSpec::Revealed(-BlsScalar::one());
+        assert!(verify(&proof, &wrong_spec, &committed).is_err());
+
+        // The opening of the commitment is checked
+        let mut tampered = committed;
+        tampered.evaluation += BlsScalar::one();
+        assert!(verify(&proof, &spec, &tampered).is_err());
+
+        // Other hidden values give another commitment, which isn't
+        // accepted along with the first proof
+        gadget(prover.mut_cs(), [1, 5, 3, 6]);
+        let (other_proof, other_committed) =
+            prover.prove_with_committed_pi(&ck, &hidden).unwrap();
+        assert_ne!(other_committed.commitment(), committed.commitment());
+        assert!(verify(&other_proof, &spec, &other_committed).is_ok());
+        assert!(verify(&proof, &spec, &other_committed).is_err());
+
+        // The hidden polynomial can't offset a revealed input: the first
+        // input is shifted by one, and the hidden polynomial is nonzero at
+        // its position to cancel the shift
+        let domain =
+            EvaluationDomain::new(prover.prover_key.as_ref().unwrap().n)
+                .unwrap();
+        let mut offsets = dense.clone();
+        offsets[positions[0]] = -BlsScalar::one();
+        let offset_hidden = [positions[0], positions[1], positions[3]];
+        gadget(prover.mut_cs(), [1, 2, 3, 4]);
+        let (shifted_proof, shifted_committed) = prover
+            .prove_with_committed_poly(
+                &ck,
+                &hidden_quotient(&domain, &offsets, &offset_hidden),
+            )
+            .unwrap();
+        let mut shifted_spec = spec.clone();
+        shifted_spec[positions[0]] =
+            PublicInputSpec::Revealed(dense[positions[0]] + BlsScalar::one());
+        assert!(
+            verify(&shifted_proof, &shifted_spec, &shifted_committed).is_err()
+        );
+
+        // Without the offset, the same positions can be hidden honestly
+        gadget(prover.mut_cs(), [1, 2, 3, 4]);
+        let (hidden_proof, hidden_committed) =
+            prover.prove_with_committed_pi(&ck, &offset_hidden).unwrap();
+        let mut hidden_spec = spec.clone();
+        hidden_spec[positions[0]] = PublicInputSpec::Committed;
+        assert!(verify(&hidden_proof, &hidden_spec, &hidden_committed).is_ok());
+
+        // The hidden inputs must still satisfy the circuit
+        let mut unsatisfied = Prover::new(b"disclosure");
+        gadget(unsatisfied.mut_cs(), [1, 2, 3, 4]);
+        unsatisfied
+            .cs
+            .public_inputs_sparse_store
+            .insert(positions[1], -BlsScalar::from(7));
+        let (bad_proof, bad_committed) =
+            unsatisfied.prove_with_committed_pi(&ck, &hidden).unwrap();
+        assert!(verify(&bad_proof, &spec, &bad_committed).is_err());
+
+        gadget(prover.mut_cs(), [1, 2, 3, 4]);
+        assert!(matches!(
+            prover.prove_with_committed_pi(&ck, &[positions[3] + 1]),
+            Err(Error::UnknownPublicInput)
+        ));
+    }
+
+    #[test]
+    fn test_named_public_inputs() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, ok) = public_parameters.trim(2 * 20).unwrap();
+
+        fn gadget(composer: &mut StandardComposer, x: BlsScalar, y: BlsScalar) {
+            let x = composer.add_public_input_named(x, "x");
+            let y = composer.add_public_input_named(y, "y");
+            composer.add(
+                (BlsScalar::one(), x),
+                (BlsScalar::one(), y),
+                BlsScalar::zero(),
+                None,
+            );
+            dummy_gadget(10, composer);
+        }
+
+        let x = BlsScalar::from(3);
+        let y = BlsScalar::from(4);
+
+        let mut prover = Prover::new(b"demo");
+        gadget(prover.mut_cs(), x, y);
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        assert_eq!(
+            prover
+                .cs
+                .construct_dense_pi_vec_named(vec![("x", x), ("y", y)])
+                .unwrap(),
+            public_inputs
+        );
+        prover.preprocess(&ck).unwrap();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"demo");
+        gadget(verifier.mut_cs(), BlsScalar::zero(), BlsScalar::zero());
+        verifier.preprocess(&ck).unwrap();
+
+        assert!(verifier.verify(&proof, &ok, &public_inputs).is_ok());
+        assert!(verifier
+            .verify_named(&proof, &ok, vec![("x", x), ("y", y)])
+            .is_ok());
+        assert!(verifier
+            .verify_named(&proof, &ok, vec![("y", y), ("x", x)])
+            .is_ok());
+        assert!(verifier
+            .verify_named(&proof, &ok, vec![("x", y), ("y", x)])
+            .is_err());
+
+        assert!(matches!(
+            verifier.verify_named(&proof, &ok, vec![("x", x)]),
+            Err(Error::MissingPublicInput)
+        ));
+        assert!(matches!(
+            verifier.verify_named(&proof, &ok, vec![("x", x), ("z", y)]),
+            Err(Error::UnknownPublicInput)
+        ));
+        assert!(matches!(
+            verifier.verify_named(
+                &proof,
+                &ok,
+                vec![("x", y), ("y", y), ("x", x)]
+            ),
+            Err(Error::DuplicatePublicInput)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "a public input named x was already added")]
+    fn test_duplicate_public_input_name() {
+        let mut composer = StandardComposer::new();
+        composer.add_public_input_named(BlsScalar::one(), "x");
+        composer.add_public_input_named(BlsScalar::one(), "x");
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, ok) = public_parameters.trim(2 * 20).unwrap();
+
+        // Proves the knowledge of `x` such that `x - pi = 0`
+        fn gadget(composer: &mut StandardComposer, x: u64) {
+            let x = BlsScalar::from(x);
+            let x_var = composer.add_input(x);
+            composer.add_gate(
+                x_var,
+                composer.zero_var,
+                composer.zero_var,
+                BlsScalar::one(),
+                BlsScalar::zero(),
+                BlsScalar::zero(),
+                BlsScalar::zero(),
+                Some(-x),
+            );
+            dummy_gadget(10, composer);
+        }
+
+        let mut prover = Prover::new(b"demo");
+        gadget(prover.mut_cs(), 0);
+        prover.preprocess(&ck).unwrap();
+        prover.clear_witness();
+
+        let mut proofs = Vec::new();
+        let mut public_inputs = Vec::new();
+        for x in 1..4 {
+            gadget(prover.mut_cs(), x);
+            public_inputs.push(prover.cs.construct_dense_pi_vec());
+            proofs.push(prover.prove(&ck).unwrap());
+        }
+
+        let mut verifier = Verifier::new(b"demo");
+        gadget(verifier.mut_cs(), 0);
+        verifier.preprocess(&ck).unwrap();
+
+        assert!(verifier.verify_batch(&proofs, &ok, &public_inputs).is_ok());
+        assert!(verifier.verify_batch(&[], &ok, &[]).is_ok());
+        assert!(matches!(
+            verifier.verify_batch(&proofs, &ok, &public_inputs[1..]),
+            Err(Error::InputLengthMismatch { left: 3, right: 2 })
+        ));
+
+        // Swapping the public inputs of two proofs makes the batch fail
+        let mut swapped = public_inputs.clone();
+        swapped.swap(0, 1);
+        assert!(verifier.verify_batch(&proofs, &ok, &swapped).is_err());
+
+        // A single tampered proof makes the batch fail
+        let mut tampered = proofs.clone();
+        tampered[2].evaluations.a_eval += BlsScalar::one();
+        assert!(verifier
+            .verify_batch(&tampered, &ok, &public_inputs)
+            .is_err());
+        assert!(verifier
+            .verify_batch(&tampered[..2], &ok, &public_inputs[..2])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_cached_verifier() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        prover.mut_cs().add_gate(
+            prover.cs.zero_var,
+            prover.cs.zero_var,
+            prover.cs.zero_var,
+            BlsScalar::zero(),
+            BlsScalar::zero(),
+            BlsScalar::zero(),
+            BlsScalar::zero(),
+            Some(BlsScalar::from(5u64)),
+        );
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"demo");
+        let mut cached_verifier = Verifier::with_cache(b"demo");
+        [&mut verifier, &mut cached_verifier]
+            .iter_mut()
+            .for_each(|verifier| {
+                verifier.mut_cs().add_gate(
+                    verifier.cs.zero_var,
+                    verifier.cs.zero_var,
+                    verifier.cs.zero_var,
+                    BlsScalar::zero(),
+                    BlsScalar::zero(),
+                    BlsScalar::zero(),
+                    BlsScalar::zero(),
+                    Some(BlsScalar::from(5u64)),
+                );
+                dummy_gadget(10, verifier.mut_cs());
+                verifier.preprocess(&ck).unwrap();
+            });
+
+        let pi_index = public_inputs
+            .iter()
+            .position(|pi| pi != &BlsScalar::zero())
+            .unwrap();
+        let mut wrong_inputs = public_inputs.clone();
+        wrong_inputs[pi_index] += BlsScalar::one();
+
+        for _ in 0..2 {
+            assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+            assert!(cached_verifier
+                .verify(&proof, &vk, &public_inputs)
+                .is_ok());
+
+            assert!(verifier.verify(&proof, &vk, &wrong_inputs).is_err());
+            assert!(cached_verifier
+                .verify(&proof, &vk, &wrong_inputs)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_wire_commitments() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+
+        let wire_commitments = prover.wire_commitments(&ck).unwrap();
+        let proof = prover.prove(&ck).unwrap();
+
+        assert_eq!(wire_commitments, proof.wire_commitments());
+        assert_eq!(wire_commitments.left, proof.a_comm.0);
+        assert_eq!(wire_commitments.fourth, proof.d_comm.0);
+    }
+
+    #[test]
+    fn test_circuit_mismatch() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        // Prove a circuit with 10 dummy gates
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        // Verify it against a circuit of the same size with 9 dummy gates
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(9, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        assert!(matches!(
+            verifier.verify(&proof, &vk, &public_inputs),
+            Err(Error::CircuitMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_plookup_full() {
+        let public_parameters =
+            PublicParameters::setup(2 * 70, &mut OsRng).unwrap();
+
+        // Create a prover struct
+        let mut prover = Prover::new(b"test");
+
+        prover.cs.lookup_table.insert_multi_mul(0, 3);
+
+        // add to trans
+        prover.key_transcript(b"key", b"additional seed information");
+
+        let output = prover.cs.lookup_table.lookup(
+            BlsScalar::from(2),
+            BlsScalar::from(3),
+            BlsScalar::one(),
+        );
+
+        let two = prover
+            .cs
+            .add_witness_to_circuit_description(BlsScalar::from(2));
+        let three = prover
+            .cs
+            .add_witness_to_circuit_description(BlsScalar::from(3));
+        let result = prover
+            .cs
+            .add_witness_to_circuit_description(output.unwrap());
+        let one = prover
+            .cs
+            .add_witness_to_circuit_description(BlsScalar::one());
+
+        prover
+            .cs
+            .plookup_gate((two, three, result, Some(one)), BlsScalar::one());
+        prover
+            .cs
+            .plookup_gate((two, three, result, Some(one)), BlsScalar::one());
+        prover
+            .cs
+            .plookup_gate((two, three, result, Some(one)), BlsScalar::one());
+        prover
+            .cs
+            .plookup_gate((two, three, result, Some(one)), BlsScalar::one());
+        prover
+            .cs
+            .plookup_gate((two, three, result, Some(one)), BlsScalar::one());
+
+        prover.cs.big_add(
+            (BlsScalar::one(), two),
+            (BlsScalar::one(), three),
+            None,
+            BlsScalar::zero(),
+            Some(BlsScalar::zero()),
+        );
+
+        // Commit Key
+        let (ck, _) = public_parameters.trim(2 * 70).unwrap();
+
+        // Preprocess circuit
+        prover.preprocess(&ck).unwrap();
+
+        // Once the prove method is called, the public inputs are cleared
+        // So pre-fetch these before calling Prove
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+
+        (prover.prove(&ck).unwrap(), public_inputs);
+    }
+
+    #[test]
+    fn test_public_input_variables() {
+        let mut composer = StandardComposer::new();
+        dummy_gadget(3, &mut composer);
+
+        let a = composer.add_input(BlsScalar::from(2));
+        let b = composer.add_input(BlsScalar::from(5));
+
+        let a_pos = composer.circuit_size();
+        composer.constrain_to_constant(
+            a,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(2)),
+        );
+        dummy_gadget(2, &mut composer);
+        let b_pos = composer.circuit_size();
+        composer.constrain_to_constant(
+            b,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(5)),
+        );
+
+        assert_eq!(
+            composer.public_input_variables(),
+            vec![(a_pos, a), (b_pos, b)]
+        );
+    }
+
+    #[test]
+    fn test_precheck_lookups() {
+        let mut composer = StandardComposer::new();
+        composer.lookup_table.insert_multi_mul(0, 3);
+        assert!(composer.precheck_lookups().is_ok());
+
+        let two = composer.add_input(BlsScalar::from(2));
+        let three = composer.add_input(BlsScalar::from(3));
+        let six = composer.add_input(BlsScalar::from(6));
+        let seven = composer.add_input(BlsScalar::from(7));
+        let one = composer.add_input(BlsScalar::one());
+
+        composer.plookup_gate((two, three, six, Some(one)), BlsScalar::zero());
+        assert!(composer.precheck_lookups().is_ok());
+
+        let bad_gate = composer.circuit_size();
+        composer
+            .plookup_gate((two, three, seven, Some(one)), BlsScalar::zero());
+        composer.plookup_gate((three, two, six, Some(one)), BlsScalar::zero());
+
+        assert_eq!(
+            composer.precheck_lookups(),
+            Err(vec![(
+                bad_gate,
+                [
+                    BlsScalar::from(2),
+                    BlsScalar::from(3),
+                    BlsScalar::from(7),
+                    BlsScalar::one()
+                ]
+            )])
+        );
+    }
+
+    #[test]
+    fn test_circuit_report() {
+        let mut composer = StandardComposer::new();
+        let initial = composer.circuit_report();
+        assert_eq!(initial.gates, composer.circuit_size());
+
+        composer.lookup_table.insert_multi_mul(0, 2);
+        let two = composer.add_input(BlsScalar::from(2));
+        let three = composer.add_input(BlsScalar::from(3));
+        let one = composer.add_input(BlsScalar::one());
+        (0..5).for_each(|_| {
+            composer
+                .plookup_gate((two, three, two, Some(one)), BlsScalar::zero());
+        });
+        composer.add(
+            (BlsScalar::one(), two),
+            (BlsScalar::one(), three),
+            BlsScalar::zero(),
+            None,
+        );
+
+        let report = composer.circuit_report();
+        assert_eq!(report.gates, initial.gates + 6);
+        assert_eq!(report.plookup_gates, initial.plookup_gates + 5);
+        assert_eq!(report.arithmetic_gates, initial.arithmetic_gates + 1);
+        assert_eq!(report.lookup_table_rows, initial.lookup_table_rows + 16);
+        assert_eq!(report.variables, initial.variables + 4);
+    }
+
+    /// Preprocesses the circuit of `prover` with public parameters sized
+    /// after it, returning the size of the domain the prover key is built
+    /// over
+    fn preprocessed_domain_size(prover: &mut Prover) -> usize {
+        let size = prover.cs.next_power_of_two_size();
+        let public_parameters =
+            PublicParameters::setup(2 * size, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * size).unwrap();
+        prover.preprocess(&ck).unwrap();
+        prover.prover_key.as_ref().unwrap().n
+    }
+
+    #[test]
+    fn test_next_power_of_two_size() {
+        [(13, 16), (32, 32), (61, 64), (100, 128)].iter().for_each(
+            |(gates, domain)| {
+                let mut prover = Prover::new(b"size");
+                dummy_gadget(gates - 3, prover.mut_cs());
+                assert_eq!(prover.cs.circuit_size(), *gates);
+                assert_eq!(prover.cs.next_power_of_two_size(), *domain);
+                assert_eq!(preprocessed_domain_size(&mut prover), *domain);
+            },
+        );
+
+        // The lookup table can be the largest part of the circuit
+        let mut prover = Prover::new(b"size");
+        let table = PlookupTable4Arity::create_hash_table();
+        prover.mut_cs().append_lookup_table(&table);
+        dummy_gadget_plookup(10, prover.mut_cs());
+        assert_eq!(prover.cs.next_power_of_two_size(), 2048);
+        assert_eq!(preprocessed_domain_size(&mut prover), 2048);
+    }
+
+    #[test]
+    fn test_domain() {
+        let mut prover = Prover::new(b"domain");
+        dummy_gadget(40, prover.mut_cs());
+        let domain = prover.cs.domain().unwrap();
+        assert_eq!(domain.size(), prover.cs.circuit_size().next_power_of_two());
+
+        // The roots of unity are the powers of the generator
+        let generator = domain.group_generator();
+        assert_ne!(generator, BlsScalar::one());
+        assert_eq!(
+            generator.pow(&[domain.size() as u64, 0, 0, 0]),
+            BlsScalar::one()
+        );
+        let roots: Vec<BlsScalar> = domain.elements().collect();
+        assert_eq!(roots.len(), domain.size());
+        assert_eq!(roots[1], generator);
+
+        // It's the domain the prover works over
+        assert_eq!(preprocessed_domain_size(&mut prover), domain.size());
+        assert_eq!(prover.cs.domain().unwrap(), domain);
+    }
+
+    #[test]
+    fn test_try_constrain_to_constant() {
+        let mut composer = StandardComposer::new();
+        let five = composer.add_input(BlsScalar::from(5));
+
+        let gates = composer.circuit_size();
+        assert!(composer
+            .try_constrain_to_constant(five, BlsScalar::from(5))
+            .is_ok());
+        assert!(matches!(
+            composer.try_constrain_to_constant(five, BlsScalar::from(6)),
+            Err(Error::ConstantMismatch)
+        ));
+        // The mismatching gate is still appended
+        assert_eq!(composer.circuit_size(), gates + 2);
+
+        // It is only caught by the proof without the check
+        let res = gadget_tester(|composer| {
+            let five = composer.add_input(BlsScalar::from(5));
+            composer.constrain_to_constant(five, BlsScalar::from(6), None);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_conflicting_public_inputs() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input(BlsScalar::from(5));
+        let b = composer.add_input(BlsScalar::from(6));
+
+        // Binding a variable twice to the same value is fine
+        let first = composer.circuit_size();
+        composer.constrain_to_constant(
+            a,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(5)),
+        );
+        composer.constrain_to_constant(
+            a,
+            BlsScalar::from(2),
+            Some(-BlsScalar::from(3)),
+        );
+        composer.constrain_to_constant(
+            b,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(6)),
+        );
+        assert!(composer.check_public_inputs().is_ok());
+
+        let second = composer.circuit_size();
+        composer.constrain_to_constant(
+            a,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(6)),
+        );
+        assert!(matches!(
+            composer.check_public_inputs(),
+            Err(Error::ConflictingPublicInputs { first: f, second: s })
+                if f == first && s == second
+        ));
+
+        // The prover reports it before computing the proof
+        let mut prover = Prover::new(b"conflict");
+        let a = prover.mut_cs().add_input(BlsScalar::from(5));
+        prover
+            .mut_cs()
+            .add_public_input_named(BlsScalar::from(5), "five");
+        prover.mut_cs().constrain_to_constant(
+            a,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(5)),
+        );
+        prover.mut_cs().constrain_to_constant(
+            a,
+            BlsScalar::zero(),
+            Some(-BlsScalar::from(7)),
+        );
+        let size = prover.cs.next_power_of_two_size();
+        let public_parameters =
+            PublicParameters::setup(2 * size, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * size).unwrap();
+        assert!(matches!(
+            prover.prove(&ck),
+            Err(Error::ConflictingPublicInputs { .. })
+        ));
+    }
+
+    #[test]
+    fn test_selector_polynomials() {
+        let mut composer = StandardComposer::new();
+        composer.lookup_table.insert_multi_mul(0, 2);
+        let two = composer.add_input(BlsScalar::from(2));
+        let three = composer.add_input(BlsScalar::from(3));
+        let one = composer.add_input(BlsScalar::one());
+        composer.add(
+            (BlsScalar::one(), two),
+            (BlsScalar::from(5), three),
+            BlsScalar::zero(),
+            None,
+        );
+        let lookup_gate = composer.circuit_size();
+        composer.plookup_gate((two, three, two, Some(one)), BlsScalar::zero());
+        composer.range_gate(three, 2);
+
+        let selectors = composer.selector_polynomials();
+        [
+            &selectors.q_m,
+            &selectors.q_l,
+            &selectors.q_r,
+            &selectors.q_o,
+            &selectors.q_4,
+            &selectors.q_c,
+            &selectors.q_arith,
+            &selectors.q_range,
+            &selectors.q_logic,
+            &selectors.q_fixed_group_add,
+            &selectors.q_variable_group_add,
+            &selectors.q_lookup,
+        ]
+        .iter()
+        .for_each(|selector| {
+            assert_eq!(selector.len(), composer.circuit_size())
+        });
+
+        assert_eq!(selectors.q_r[lookup_gate - 1], BlsScalar::from(5));
+        assert_eq!(selectors.q_lookup[lookup_gate], BlsScalar::one());
+        assert_eq!(selectors.q_arith[lookup_gate], BlsScalar::zero());
+    }
+
+    #[test]
+    fn test_export_witness() {
+        let mut composer = StandardComposer::new();
+        composer.lookup_table.insert_multi_mul(0, 2);
+
+        let s_i_decomposition = composer.s_i_decomposition_vars();
+        let x = composer.add_input(-BlsScalar::from(3));
+        composer.decomposition_gadget(x, s_i_decomposition);
+
+        let two = composer.add_input(BlsScalar::from(2));
+        let three = composer.add_input(BlsScalar::from(3));
+        let one = composer.add_input(BlsScalar::one());
+        composer.plookup_gate((two, three, two, Some(one)), BlsScalar::zero());
+        composer.add_public_input_named(BlsScalar::from(7), "seven");
+
+        let selectors = composer.selector_polynomials();
+        let witness = composer.export_witness();
+        assert_eq!(witness.w_l.len(), composer.circuit_size());
+        assert_eq!(witness.w_4.len(), composer.circuit_size());
+        assert_eq!(witness.public_inputs.len(), composer.circuit_size());
+
+        (0..composer.circuit_size()).for_each(|i| {
+            let (a, b, c, d) = (
+                witness.w_l[i],
+                witness.w_r[i],
+                witness.w_o[i],
+                witness.w_4[i],
+            );
+
+            if selectors.q_arith[i] == BlsScalar::one() {
+                let gate = selectors.q_m[i] * a * b
+                    + selectors.q_l[i] * a
+                    + selectors.q_r[i] * b
+                    + selectors.q_o[i] * c
+                    + selectors.q_4[i] * d
+                    + selectors.q_c[i]
+                    + witness.public_inputs[i];
+                assert_eq!(gate, BlsScalar::zero(), "gate {}", i);
+            }
+            if selectors.q_lookup[i] == BlsScalar::one() {
+                assert!(
+                    composer.lookup_table.contains([a, b, c, d]),
+                    "lookup gate {}",
+                    i
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn test_witness_canonical() {
+        let mut composer = StandardComposer::new();
+        [
+            BlsScalar::zero(),
+            BlsScalar::one(),
+            BlsScalar::from(659),
+            -BlsScalar::from(3),
+            BlsScalar::from_raw([1, 2, 3, 4]),
+        ]
+        .iter()
+        .for_each(|value| {
+            let var = composer.add_input(*value);
+            let canonical = composer.witness_canonical(var);
+            assert_eq!(BlsScalar::from_raw(canonical.0), *value);
+        });
+
+        let small = composer.add_input(BlsScalar::from(659));
+        assert_eq!(composer.witness_canonical(small).0, [659, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_reduced_witness() {
+        let mut composer = StandardComposer::new();
+        let vars: Vec<_> = (0..16)
+            .map(|_| composer.add_input(BlsScalar::random(&mut OsRng)))
+            .collect();
+
+        // The memoized reductions agree with the uncached ones
+        (0..2).for_each(|_| {
+            vars.iter().for_each(|var| {
+                assert_eq!(
+                    composer.reduced_witness(*var),
+                    composer.variables[var].reduce().0
+                );
+            });
+        });
+
+        // The variables reallocated after clearing aren't served stale values
+        let var = vars[0];
+        let stale = composer.reduced_witness(var);
+        composer.clear();
+        let mut reallocated = composer.add_input(BlsScalar::from(7));
+        while reallocated != var {
+            reallocated = composer.add_input(BlsScalar::from(7));
+        }
+        assert_ne!(composer.reduced_witness(var), stale);
+        assert_eq!(composer.reduced_witness(var), [7, 0, 0, 0]);
     }
 
     #[test]
-    // XXX: Move this to integration tests
-    fn test_multiple_proofs() {
-        let public_parameters =
-            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+    fn test_named_inputs() {
+        let mut composer = StandardComposer::new();
+        let named = composer.add_input_named(BlsScalar::one(), "one");
+        let unnamed = composer.add_input(BlsScalar::one());
+
+        #[cfg(feature = "trace")]
+        assert_eq!(composer.name_of(named), Some("one"));
+        #[cfg(not(feature = "trace"))]
+        assert_eq!(composer.name_of(named), None);
+        assert_eq!(composer.name_of(unnamed), None);
+
+        // The names don't stick to the variables reused after clearing
+        composer.clear();
+        let reused = composer.add_input(BlsScalar::one());
+        assert_eq!(reused, named);
+        assert_eq!(composer.name_of(reused), None);
+    }
 
-        // Create a prover struct
-        let mut prover = Prover::new(b"demo");
+    #[test]
+    #[cfg(feature = "trace")]
+    #[should_panic(expected = "w_o: sum")]
+    fn test_named_inputs_failure() {
+        let mut composer = StandardComposer::new();
+        let a = composer.add_input_named(BlsScalar::one(), "a");
+        let b = composer.add_input_named(BlsScalar::from(2), "b");
+        let sum = composer.add_input_named(BlsScalar::from(4), "sum");
+        composer.add_gate(
+            a,
+            b,
+            sum,
+            BlsScalar::one(),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            None,
+        );
 
-        // Add gadgets
-        dummy_gadget(10, prover.mut_cs());
+        composer.check_circuit_satisfied();
+    }
 
-        // Commit Key
-        let (ck, _) = public_parameters.trim(2 * 20).unwrap();
+    #[test]
+    #[should_panic(expected = "plookup gate added without a lookup table")]
+    fn test_plookup_gate_without_table() {
+        let mut composer = StandardComposer::new();
+        assert!(!composer.has_lookup_table());
+
+        let two = composer.add_input(BlsScalar::from(2));
+        let three = composer.add_input(BlsScalar::from(3));
+        let six = composer.add_input(BlsScalar::from(6));
+        let one = composer.add_input(BlsScalar::one());
+        composer.plookup_gate((two, three, six, Some(one)), BlsScalar::zero());
+    }
 
-        // Preprocess circuit
-        prover.preprocess(&ck).unwrap();
+    #[test]
+    fn test_composer_errors() {
+        let mut composer = StandardComposer::new();
+        let two = composer.add_input(BlsScalar::from(2));
+        let three = composer.add_input(BlsScalar::from(3));
+        let six = composer.add_input(BlsScalar::from(6));
+
+        let gates = composer.circuit_size();
+        assert!(matches!(
+            composer.try_plookup_gate((two, three, six), BlsScalar::zero()),
+            Err(Error::TableMissing)
+        ));
+        assert_eq!(composer.circuit_size(), gates);
+
+        assert!(composer.try_check_circuit_satisfied().is_ok());
+        composer.mul_gate(
+            two,
+            three,
+            six,
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            None,
+        );
+        assert!(composer.try_check_circuit_satisfied().is_ok());
 
-        let public_inputs = prover.cs.construct_dense_pi_vec();
+        // 2 * 3 != 2
+        composer.mul_gate(
+            two,
+            three,
+            two,
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            None,
+        );
+        assert!(matches!(
+            composer.try_check_circuit_satisfied(),
+            Err(Error::Unsatisfiable { gate, .. }) if gate == gates + 1
+        ));
+    }
 
-        let mut proofs = Vec::new();
+    #[test]
+    fn test_prover_debug_mode() {
+        let mut prover = Prover::new(b"debug");
+        dummy_gadget(5, prover.mut_cs());
+        let two = prover.mut_cs().add_input(BlsScalar::from(2));
+        let three = prover.mut_cs().add_input(BlsScalar::from(3));
+        let gate = prover.cs.circuit_size();
+
+        // 2 * 3 != 2
+        prover.mut_cs().mul_gate(
+            two,
+            three,
+            two,
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            None,
+        );
+        dummy_gadget(2, prover.mut_cs());
 
-        // Compute multiple proofs
-        for _ in 0..3 {
-            proofs.push(prover.prove(&ck).unwrap());
+        let size = prover.cs.next_power_of_two_size();
+        let public_parameters =
+            PublicParameters::setup(2 * size, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * size).unwrap();
 
-            // Add another witness instance
-            dummy_gadget(10, prover.mut_cs());
-        }
+        prover.set_debug(true);
+        let wires = [
+            BlsScalar::from(2),
+            BlsScalar::from(3),
+            BlsScalar::from(2),
+            BlsScalar::zero(),
+        ];
+        assert!(matches!(
+            prover.prove(&ck),
+            Err(Error::Unsatisfiable { gate: g, wires: w })
+                if g == gate && w == wires
+        ));
+    }
 
-        // Verifier
-        //
-        let mut verifier = Verifier::new(b"demo");
+    #[test]
+    fn test_lookup_row() {
+        let mut composer = StandardComposer::new();
+        composer.lookup_table.insert_special_row(
+            BlsScalar::from(5),
+            BlsScalar::from(1),
+            BlsScalar::from(9),
+            BlsScalar::zero(),
+        );
+        composer.lookup_table.insert_special_row(
+            BlsScalar::from(5),
+            BlsScalar::from(2),
+            BlsScalar::from(9),
+            BlsScalar::one(),
+        );
 
-        // Add gadgets
-        dummy_gadget(10, verifier.mut_cs());
+        let one = composer.add_input(BlsScalar::one());
+        let two = composer.add_input(BlsScalar::from(2));
+        let five = composer.add_input(BlsScalar::from(5));
+        let nine = composer.add_input(BlsScalar::from(9));
 
-        // Commit and Verifier Key
-        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+        let row = LookupRow {
+            query: five,
+            aux: two,
+            output: nine,
+            tag: Some(one),
+        };
+        assert_eq!(row, LookupRow::from((five, two, nine, Some(one))));
+        assert_eq!(composer.plookup_gate(row, BlsScalar::zero()), nine);
+
+        // A missing tag matches rows whose fourth column is zero
+        composer.plookup_gate(
+            LookupRow {
+                query: five,
+                aux: one,
+                output: nine,
+                tag: None,
+            },
+            BlsScalar::zero(),
+        );
+        assert!(composer.precheck_lookups().is_ok());
+
+        // The tag is part of the match, so swapping it is caught
+        let bad_gate = composer.circuit_size();
+        composer.plookup_gate((five, two, nine), BlsScalar::zero());
+        assert_eq!(
+            composer.precheck_lookups(),
+            Err(vec![(
+                bad_gate,
+                [
+                    BlsScalar::from(5),
+                    BlsScalar::from(2),
+                    BlsScalar::from(9),
+                    BlsScalar::zero()
+                ]
+            )])
+        );
+    }
 
-        // Preprocess
-        verifier.preprocess(&ck).unwrap();
+    #[test]
+    fn test_merge_tagged() {
+        let mut mul = PlookupTable4Arity::new();
+        mul.insert_multi_mul(0, 3);
+        let mut xor = PlookupTable4Arity::new();
+        xor.insert_multi_xor(0, 3);
+        let merged = PlookupTable4Arity::merge_tagged(&[&mul, &xor]);
+        assert_eq!(merged.0.len(), mul.0.len() + xor.0.len());
+
+        // Each row is found in its own table
+        let res = gadget_plookup_tester(
+            |composer| {
+                let two = composer.add_input(BlsScalar::from(2));
+                let three = composer.add_input(BlsScalar::from(3));
+                let six = composer.add_input(BlsScalar::from(6));
+                let one = composer.add_input(BlsScalar::one());
+                let minus_one = composer.add_input(-BlsScalar::one());
+
+                composer.tagged_plookup_gate(
+                    (two, three, six, Some(one)),
+                    0,
+                    BlsScalar::zero(),
+                );
+                composer.tagged_plookup_gate(
+                    (two, three, one, Some(minus_one)),
+                    1,
+                    BlsScalar::zero(),
+                );
+            },
+            merged.clone(),
+        );
+        assert!(res.is_ok());
 
-        for proof in proofs {
-            assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
-        }
+        // A row of the mul table isn't found in the xor one
+        let res = gadget_plookup_tester(
+            |composer| {
+                let two = composer.add_input(BlsScalar::from(2));
+                let three = composer.add_input(BlsScalar::from(3));
+                let six = composer.add_input(BlsScalar::from(6));
+                let one = composer.add_input(BlsScalar::one());
+
+                composer.tagged_plookup_gate(
+                    (two, three, six, Some(one)),
+                    1,
+                    BlsScalar::zero(),
+                );
+            },
+            merged,
+        );
+        assert!(res.is_err());
     }
 
     #[test]
-    fn test_plookup_full() {
-        let public_parameters =
-            PublicParameters::setup(2 * 70, &mut OsRng).unwrap();
+    fn test_conditional_lookup() {
+        fn tables() -> [PlookupTable4Arity; 2] {
+            let domain = || (0..8u64).map(BlsScalar::from);
+            [
+                PlookupTable4Arity::from_fn(domain(), |x| x.square()),
+                PlookupTable4Arity::from_fn(domain(), |x| x.double()),
+            ]
+        }
 
-        // Create a prover struct
-        let mut prover = Prover::new(b"test");
+        fn merged() -> PlookupTable4Arity {
+            let [squares, doubles] = tables();
+            PlookupTable4Arity::merge_tagged(&[&squares, &doubles])
+        }
 
-        prover.cs.lookup_table.insert_multi_mul(0, 3);
+        fn lookup(
+            composer: &mut StandardComposer,
+            selector: u64,
+            a: u64,
+        ) -> BlsScalar {
+            let [squares, doubles] = tables();
+            let selector = composer.add_input(BlsScalar::from(selector));
+            let a = composer.add_input(BlsScalar::from(a));
+            let output =
+                composer.conditional_lookup(selector, a, &[&squares, &doubles]);
+            composer.variables[&output]
+        }
 
-        // add to trans
-        prover.key_transcript(b"key", b"additional seed information");
+        // Should pass as both selector values pick their own table
+        let res = gadget_plookup_tester(
+            |composer| {
+                assert_eq!(lookup(composer, 0, 5), BlsScalar::from(25));
+                assert_eq!(lookup(composer, 1, 5), BlsScalar::from(10));
+            },
+            merged(),
+        );
+        assert!(res.is_ok());
 
-        let output = prover.cs.lookup_table.lookup(
-            BlsScalar::from(2),
-            BlsScalar::from(3),
-            BlsScalar::one(),
+        // Should fail as there's no third table
+        let res = gadget_plookup_tester(
+            |composer| {
+                lookup(composer, 2, 5);
+            },
+            merged(),
         );
+        assert!(res.is_err());
 
-        let two = prover
-            .cs
-            .add_witness_to_circuit_description(BlsScalar::from(2));
-        let three = prover
-            .cs
-            .add_witness_to_circuit_description(BlsScalar::from(3));
-        let result = prover
-            .cs
-            .add_witness_to_circuit_description(output.unwrap());
-        let one = prover
-            .cs
-            .add_witness_to_circuit_description(BlsScalar::one());
+        // Should fail as the square isn't in the table of the doubles
+        let res = gadget_plookup_tester(
+            |composer| {
+                let [squares, doubles] = tables();
+                let selector = composer.add_input(BlsScalar::one());
+                let a = composer.add_input(BlsScalar::from(3));
+                let square = composer.add_input(BlsScalar::from(9));
+                let tag = composer.add_input(PlookupTable4Arity::table_tag(1));
+                composer.conditional_lookup(selector, a, &[&squares, &doubles]);
+                composer.plookup_gate(
+                    (a, composer.zero_var, square, Some(tag)),
+                    BlsScalar::zero(),
+                );
+            },
+            merged(),
+        );
+        assert!(res.is_err());
+    }
 
-        prover
-            .cs
-            .plookup_gate(two, three, result, Some(one), BlsScalar::one());
-        prover
-            .cs
-            .plookup_gate(two, three, result, Some(one), BlsScalar::one());
-        prover
-            .cs
-            .plookup_gate(two, three, result, Some(one), BlsScalar::one());
-        prover
-            .cs
-            .plookup_gate(two, three, result, Some(one), BlsScalar::one());
-        prover
-            .cs
-            .plookup_gate(two, three, result, Some(one), BlsScalar::one());
+    #[test]
+    fn test_add_constant_var() {
+        let res = gadget_tester(|composer| {
+            let gates = composer.circuit_size();
+            let seven = composer.add_constant_var(BlsScalar::from(7));
+            assert_eq!(composer.circuit_size(), gates + 1);
+
+            // The same constant is shared without adding any gate
+            assert_eq!(composer.add_constant_var(BlsScalar::from(7)), seven);
+            assert_eq!(
+                composer.add_constant_var(BlsScalar::zero()),
+                composer.zero_var
+            );
+            assert_eq!(composer.circuit_size(), gates + 1);
+
+            let eight = composer.add_constant_var(BlsScalar::from(8));
+            assert_ne!(eight, seven);
+            assert_eq!(composer.variables[&eight], BlsScalar::from(8));
+
+            // The shared variable is still constrained to its constant
+            let sum = composer.add(
+                (BlsScalar::one(), seven),
+                (BlsScalar::one(), seven),
+                BlsScalar::zero(),
+                None,
+            );
+            composer.constrain_to_constant(sum, BlsScalar::from(14), None);
+        });
+        assert!(res.is_ok());
 
-        prover.cs.big_add(
-            (BlsScalar::one(), two),
-            (BlsScalar::one(), three),
-            None,
-            BlsScalar::zero(),
-            Some(BlsScalar::zero()),
+        // The cache is dropped along with the variables
+        let mut composer = StandardComposer::new();
+        composer.add_constant_var(BlsScalar::from(7));
+        composer.clear();
+        let gates = composer.circuit_size();
+        composer.add_constant_var(BlsScalar::from(7));
+        assert_eq!(composer.circuit_size(), gates + 1);
+    }
+
+    #[test]
+    fn test_plookup_query() {
+        // Should pass as the s-box output of a nibble is found at position
+        // zero, and the larger nibbles are mapped to themselves
+        let res = gadget_plookup_tester(
+            |composer| {
+                let table = PlookupTable4Arity::create_hash_table();
+                let five = composer.add_input(BlsScalar::from(5));
+                let output =
+                    composer.plookup_query(five, composer.zero_var, &table);
+                assert_eq!(
+                    composer.variables[&output],
+                    SboxTable::default().output(5).unwrap()
+                );
+
+                let nibble = composer.add_input(BlsScalar::from(660));
+                let one = composer.add_input(BlsScalar::one());
+                let output = composer.plookup_query(nibble, one, &table);
+                assert_eq!(composer.variables[&output], BlsScalar::from(660));
+            },
+            PlookupTable4Arity::create_hash_table(),
         );
+        assert!(res.is_ok());
 
-        // Commit Key
-        let (ck, _) = public_parameters.trim(2 * 70).unwrap();
+        // Should fail as no row starts with 5 and 3
+        let res = gadget_plookup_tester(
+            |composer| {
+                let table = PlookupTable4Arity::create_hash_table();
+                let five = composer.add_input(BlsScalar::from(5));
+                let three = composer.add_input(BlsScalar::from(3));
+                let output = composer.plookup_query(five, three, &table);
+                assert_eq!(composer.variables[&output], BlsScalar::zero());
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+    }
 
-        // Preprocess circuit
-        prover.preprocess(&ck).unwrap();
+    #[test]
+    fn test_append_lookup_tables() {
+        let res = gadget_tester(|composer| {
+            let mut mul = PlookupTable4Arity::new();
+            mul.insert_multi_mul(0, 3);
+            let range = PlookupTable4Arity::create_range_table(4).unwrap();
+
+            let rows = composer.lookup_table.0.len();
+            composer.append_lookup_tables(&[&mul, &range]);
+            assert_eq!(
+                composer.lookup_table.0.len(),
+                rows + mul.0.len() + range.0.len()
+            );
 
-        // Once the prove method is called, the public inputs are cleared
-        // So pre-fetch these before calling Prove
-        let public_inputs = prover.cs.construct_dense_pi_vec();
+            // One lookup in each table
+            let two = composer.add_input(BlsScalar::from(2));
+            let three = composer.add_input(BlsScalar::from(3));
+            let six = composer.add_input(BlsScalar::from(6));
+            let one = composer.add_input(BlsScalar::one());
+            composer
+                .plookup_gate((two, three, six, Some(one)), BlsScalar::zero());
+            let x = composer.add_input(BlsScalar::from(13));
+            composer.range_check_lookup(x, 4);
+        });
+        assert!(res.is_ok(), "{:?}", res);
+    }
 
-        (prover.prove(&ck).unwrap(), public_inputs);
+    #[test]
+    fn test_shared_lookup_table() {
+        fn nibble_lookup(
+            composer: &mut StandardComposer,
+            nibble: u64,
+            table: &PlookupTable4Arity,
+        ) {
+            let x = composer.add_input(BlsScalar::from(nibble));
+            composer.plookup_query(x, composer.zero_var, table);
+        }
+
+        let table = Arc::new(PlookupTable4Arity::create_hash_table());
+        let size = (table.0.len() + StandardComposer::DUMMY_LOOKUP_ROWS)
+            .next_power_of_two();
+        let pub_params =
+            Arc::new(PublicParameters::setup(2 * size, &mut OsRng).unwrap());
+
+        // Every thread proves its own circuit over the same table
+        let handles: Vec<_> = (1..4)
+            .map(|nibble| {
+                let table = Arc::clone(&table);
+                let pub_params = Arc::clone(&pub_params);
+                std::thread::spawn(move || {
+                    let mut prover = Prover::new(b"shared");
+                    prover
+                        .mut_cs()
+                        .append_shared_lookup_table(Arc::clone(&table));
+                    nibble_lookup(prover.mut_cs(), nibble, &table);
+                    assert_eq!(
+                        prover.cs.lookup_table_size(),
+                        table.0.len() + StandardComposer::DUMMY_LOOKUP_ROWS
+                    );
+                    assert!(prover.cs.precheck_lookups().is_ok());
+
+                    let (ck, _) = pub_params
+                        .trim(2 * prover.cs.next_power_of_two_size())
+                        .unwrap();
+                    prover.preprocess(&ck).unwrap();
+                    let public_inputs = prover.cs.construct_dense_pi_vec();
+                    (nibble, prover.prove(&ck).unwrap(), public_inputs)
+                })
+            })
+            .collect();
+
+        // The proofs verify against copies of the table
+        handles.into_iter().for_each(|handle| {
+            let (nibble, proof, public_inputs) = handle.join().unwrap();
+
+            let mut verifier = Verifier::new(b"shared");
+            verifier.mut_cs().append_lookup_table(&table);
+            nibble_lookup(verifier.mut_cs(), nibble, &table);
+            let (ck, vk) = pub_params
+                .trim(verifier.cs.next_power_of_two_size())
+                .unwrap();
+            verifier.preprocess(&ck).unwrap();
+            assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+        });
+
+        // The threads dropped their references along with their composers
+        assert_eq!(Arc::strong_count(&table), 1);
     }
 
     #[test]
@@ -1074,43 +3442,28 @@ mod benches {
 
         (0..usize::pow(2, 10)).for_each(|_| {
             prover.cs.plookup_gate(
-                two,
-                three,
-                result,
-                Some(one),
+                (two, three, result, Some(one)),
                 BlsScalar::one(),
             );
             prover.cs.plookup_gate(
-                two,
-                three,
-                result,
-                Some(one),
+                (two, three, result, Some(one)),
                 BlsScalar::one(),
             );
             prover.cs.plookup_gate(
-                two,
-                three,
-                result,
-                Some(one),
+                (two, three, result, Some(one)),
                 BlsScalar::one(),
             );
             prover.cs.plookup_gate(
-                two,
-                three,
-                result,
-                Some(one),
+                (two, three, result, Some(one)),
                 BlsScalar::one(),
             );
             prover.cs.plookup_gate(
-                two,
-                three,
-                result,
-                Some(one),
+                (two, three, result, Some(one)),
                 BlsScalar::one(),
             );
             prover
                 .cs
-                .plookup_gate(two, two, two, Some(two), BlsScalar::one());
+                .plookup_gate((two, two, two, Some(two)), BlsScalar::one());
         });
 
         // prover.cs.
@@ -1125,4 +3478,61 @@ mod benches {
 
         b.iter(|| prover.prove(&ck).unwrap());
     }
+
+    #[bench]
+    fn bench_prove(b: &mut Bencher) {
+        let public_parameters =
+            PublicParameters::setup(2 * 2048, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * 1024).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(1000, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+
+        b.iter(|| {
+            dummy_gadget(1000, prover.mut_cs());
+            prover.prove(&ck).unwrap()
+        });
+    }
+
+    #[bench]
+    fn bench_prove_uncached(b: &mut Bencher) {
+        let public_parameters =
+            PublicParameters::setup(2 * 2048, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * 1024).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(1000, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let prover_key = prover.prover_key.as_ref().unwrap();
+
+        b.iter(|| prover.prove_with_preprocessed(&ck, prover_key).unwrap());
+    }
+
+    fn bench_verify(b: &mut Bencher, mut verifier: Verifier) {
+        let public_parameters =
+            PublicParameters::setup(2 * 2048, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 1024).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(1000, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        dummy_gadget(1000, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        b.iter(|| verifier.verify(&proof, &vk, &public_inputs).unwrap());
+    }
+
+    #[bench]
+    fn bench_verify_uncached(b: &mut Bencher) {
+        bench_verify(b, Verifier::new(b"demo"));
+    }
+
+    #[bench]
+    fn bench_verify_cached(b: &mut Bencher) {
+        bench_verify(b, Verifier::with_cache(b"demo"));
+    }
 }
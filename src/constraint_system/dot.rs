@@ -0,0 +1,144 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::StandardComposer;
+use crate::constraint_system::Variable;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use dusk_bls12_381::BlsScalar;
+
+impl StandardComposer {
+    /// Returns the circuit as a graphviz graph in the DOT language, for
+    /// debugging.
+    ///
+    /// Every [`Variable`] is an ellipse labeled with its name, given with
+    /// [`StandardComposer::add_input_named`], or its index otherwise. Every
+    /// gate is a box labeled with its index and kind, with an edge from each
+    /// of its wired [`Variable`]s labeled `a`, `b`, `c` or `d`. Plookup gates
+    /// are drawn as filled diamonds instead. The wires holding the zero
+    /// [`Variable`] are left out to keep the graph readable.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph circuit {\n");
+
+        let mut variables: Vec<&Variable> = self.variables.keys().collect();
+        variables.sort_by_key(|var| var.0);
+        variables
+            .into_iter()
+            .filter(|var| **var != self.zero_var)
+            .for_each(|var| {
+                let label = match self.name_of(*var) {
+                    Some(name) => {
+                        name.replace('\\', "\\\\").replace('"', "\\\"")
+                    }
+                    None => format!("v{}", var.0),
+                };
+                // Writing to a `String` can't fail
+                let _ = writeln!(dot, "  v{} [label=\"{}\"];", var.0, label);
+            });
+
+        (0..self.n).for_each(|gate| {
+            let kind = self.gate_kind(gate);
+            let style = match kind {
+                "plookup" => "shape=diamond, style=filled",
+                _ => "shape=box",
+            };
+            let _ = writeln!(
+                dot,
+                "  g{} [label=\"{} #{}\", {}];",
+                gate, kind, gate, style
+            );
+
+            let wires = [
+                ("a", self.w_l[gate]),
+                ("b", self.w_r[gate]),
+                ("c", self.w_o[gate]),
+                ("d", self.w_4[gate]),
+            ];
+            wires
+                .iter()
+                .filter(|(_, var)| *var != self.zero_var)
+                .for_each(|(wire, var)| {
+                    let _ = writeln!(
+                        dot,
+                        "  v{} -> g{} [label=\"{}\"];",
+                        var.0, gate, wire
+                    );
+                });
+        });
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Returns the kind of the gate of index `gate`, from its selectors.
+    fn gate_kind(&self, gate: usize) -> &'static str {
+        let on = |selector: &[BlsScalar]| selector[gate] != BlsScalar::zero();
+
+        if on(&self.q_lookup) {
+            "plookup"
+        } else if on(&self.q_range) {
+            "range"
+        } else if on(&self.q_logic) {
+            "logic"
+        } else if on(&self.q_fixed_group_add) {
+            "fixed_group_add"
+        } else if on(&self.q_variable_group_add) {
+            "variable_group_add"
+        } else {
+            "arithmetic"
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+    use crate::plookup::PlookupTable4Arity;
+
+    #[test]
+    fn test_to_dot() {
+        let mut composer = StandardComposer::new();
+        let table =
+            PlookupTable4Arity::from_fn((0..4u64).map(BlsScalar::from), |x| {
+                x.square()
+            });
+        composer.append_lookup_table(&table);
+        let x = composer.add_input_named(BlsScalar::from(3), "x");
+        let zero = composer.zero_var;
+        composer.plookup_query(x, zero, &table);
+        dummy_gadget(3, &mut composer);
+
+        let dot = composer.to_dot();
+        let lines: Vec<&str> = dot.lines().collect();
+
+        // Every statement sits between the header and the closing brace
+        assert_eq!(lines.first(), Some(&"digraph circuit {"));
+        assert_eq!(lines.last(), Some(&"}"));
+        let statements = &lines[1..lines.len() - 1];
+        assert!(statements.iter().all(|line| line.starts_with("  ")
+            && line.ends_with("];")
+            && line.matches('"').count() % 2 == 0));
+
+        // One node per gate, with the plookup ones drawn apart
+        let gates = statements
+            .iter()
+            .filter(|line| line.starts_with("  g"))
+            .count();
+        assert_eq!(gates, composer.circuit_size());
+        let plookup_gates = statements
+            .iter()
+            .filter(|line| line.contains("shape=diamond"))
+            .count();
+        assert_eq!(plookup_gates, composer.circuit_report().plookup_gates);
+
+        if cfg!(feature = "trace") {
+            assert!(dot.contains("[label=\"x\"]"));
+        }
+    }
+}
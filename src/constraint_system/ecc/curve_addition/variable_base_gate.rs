@@ -217,28 +217,23 @@ mod test {
 
     #[test]
     fn test_curve_addition() {
-        let res = gadget_tester(
-            |composer| {
-                let expected_point: JubJubAffine =
-                    (JubJubExtended::from(GENERATOR)
-                        + JubJubExtended::from(GENERATOR))
-                    .into();
-                let x = composer.add_input(GENERATOR.get_x());
-                let y = composer.add_input(GENERATOR.get_y());
-                let point_a = Point { x, y };
-                let point_b = Point { x, y };
-
-                let point = composer.point_addition_gate(point_a, point_b);
-                let point2 =
-                    classical_point_addition(composer, point_a, point_b);
-
-                composer.assert_equal_point(point, point2);
-
-                composer
-                    .assert_equal_public_point(point.into(), expected_point);
-            },
-            2000,
-        );
+        let res = gadget_tester(|composer| {
+            let expected_point: JubJubAffine =
+                (JubJubExtended::from(GENERATOR)
+                    + JubJubExtended::from(GENERATOR))
+                .into();
+            let x = composer.add_input(GENERATOR.get_x());
+            let y = composer.add_input(GENERATOR.get_y());
+            let point_a = Point { x, y };
+            let point_b = Point { x, y };
+
+            let point = composer.point_addition_gate(point_a, point_b);
+            let point2 = classical_point_addition(composer, point_a, point_b);
+
+            composer.assert_equal_point(point, point2);
+
+            composer.assert_equal_public_point(point.into(), expected_point);
+        });
         assert!(res.is_ok());
     }
 }
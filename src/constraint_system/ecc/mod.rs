@@ -104,7 +104,7 @@ impl StandardComposer {
     /// circuit
     pub fn assert_equal_point(&mut self, point_a: Point, point_b: Point) {
         self.assert_equal(point_a.x, point_b.x);
-        self.assert_equal(point_b.y, point_b.y);
+        self.assert_equal(point_a.y, point_b.y);
     }
 
     /// Adds to the circuit description the conditional selection of the
@@ -147,6 +147,28 @@ impl StandardComposer {
 
         Point { x, y }
     }
+
+    /// Folds two committed instances into `acc + challenge * new`, as done
+    /// in Nova-style folding schemes, and returns the coordinates of the
+    /// folded commitment.
+    ///
+    /// The commitments are JubJub points given by their `(x, y)`
+    /// coordinates, since JubJub is the curve whose base field is native to
+    /// the circuit. The `challenge` is interpreted as a JubJub scalar.
+    pub fn fold_commitment(
+        &mut self,
+        acc: (Variable, Variable),
+        new: (Variable, Variable),
+        challenge: Variable,
+    ) -> (Variable, Variable) {
+        let acc = Point { x: acc.0, y: acc.1 };
+        let new = Point { x: new.0, y: new.1 };
+
+        let scaled = self.variable_base_scalar_mul(challenge, new);
+        let folded = self.point_addition_gate(acc, scaled);
+
+        (folded.x, folded.y)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -154,31 +176,89 @@ impl StandardComposer {
 mod tests {
     use super::*;
     use crate::constraint_system::helper::*;
+    use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar, GENERATOR};
 
     #[test]
     fn test_conditional_select_point() {
-        let res = gadget_tester(
-            |composer| {
-                let bit_1 = composer.add_input(BlsScalar::one());
-                let bit_0 = composer.zero_var();
-
-                let point_a = Point::identity(composer);
-                let point_b = Point {
-                    x: composer.add_input(BlsScalar::from(10u64)),
-                    y: composer.add_input(BlsScalar::from(20u64)),
-                };
-
-                let choice =
-                    composer.conditional_point_select(point_a, point_b, bit_1);
-
-                composer.assert_equal_point(point_a, choice);
-
-                let choice =
-                    composer.conditional_point_select(point_a, point_b, bit_0);
-                composer.assert_equal_point(point_b, choice);
-            },
-            32,
-        );
+        let res = gadget_tester(|composer| {
+            let bit_1 = composer.add_input(BlsScalar::one());
+            let bit_0 = composer.zero_var();
+
+            let point_a = Point::identity(composer);
+            let point_b = Point {
+                x: composer.add_input(BlsScalar::from(10u64)),
+                y: composer.add_input(BlsScalar::from(20u64)),
+            };
+
+            let choice =
+                composer.conditional_point_select(point_a, point_b, bit_1);
+
+            composer.assert_equal_point(point_a, choice);
+
+            let choice =
+                composer.conditional_point_select(point_a, point_b, bit_0);
+            composer.assert_equal_point(point_b, choice);
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_assert_equal_point() {
+        let res = gadget_tester(|composer| {
+            let point_a = Point {
+                x: composer.add_input(BlsScalar::from(10u64)),
+                y: composer.add_input(BlsScalar::from(20u64)),
+            };
+            let point_b = Point {
+                x: composer.add_input(BlsScalar::from(10u64)),
+                y: composer.add_input(BlsScalar::from(20u64)),
+            };
+            composer.assert_equal_point(point_a, point_b);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as the y coordinates differ, (x, -y) being the
+        // negation of (x, y)
+        let res = gadget_tester(|composer| {
+            let point_a = Point {
+                x: composer.add_input(BlsScalar::from(10u64)),
+                y: composer.add_input(BlsScalar::from(20u64)),
+            };
+            let point_b = Point {
+                x: composer.add_input(BlsScalar::from(10u64)),
+                y: composer.add_input(-BlsScalar::from(20u64)),
+            };
+            composer.assert_equal_point(point_a, point_b);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_fold_commitment() {
+        let res = gadget_tester(|composer| {
+            let generator = JubJubExtended::from(GENERATOR);
+            let acc: JubJubAffine =
+                (generator * JubJubScalar::from(3u64)).into();
+            let new: JubJubAffine =
+                (generator * JubJubScalar::from(5u64)).into();
+            let challenge = JubJubScalar::from(7u64);
+
+            let expected: JubJubAffine = (JubJubExtended::from(acc)
+                + JubJubExtended::from(new) * challenge)
+                .into();
+
+            let acc = composer.add_affine(acc);
+            let new = composer.add_affine(new);
+            let challenge = composer.add_input(BlsScalar::from(7u64));
+
+            let (x, y) = composer.fold_commitment(
+                (*acc.x(), *acc.y()),
+                (*new.x(), *new.y()),
+                challenge,
+            );
+
+            composer.assert_equal_public_point(Point { x, y }, expected);
+        });
         assert!(res.is_ok());
     }
 }
@@ -168,216 +168,187 @@ mod tests {
 
     #[test]
     fn test_ecc_constraint() {
-        let res = gadget_tester(
-            |composer| {
-                let scalar = JubJubScalar::from_bytes_wide(&[
-                    182, 44, 247, 214, 94, 14, 151, 208, 130, 16, 200, 204,
-                    147, 32, 104, 166, 0, 59, 52, 1, 1, 59, 103, 6, 169, 175,
-                    51, 101, 234, 180, 125, 14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-                    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-                    0, 0,
-                ]);
-                let bls_scalar =
-                    BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
-                let secret_scalar = composer.add_input(bls_scalar);
-
-                let expected_point: JubJubAffine =
-                    (GENERATOR_EXTENDED * scalar).into();
-
-                let point_scalar = composer
-                    .fixed_base_scalar_mul(secret_scalar, GENERATOR_EXTENDED);
-
-                composer
-                    .assert_equal_public_point(point_scalar, expected_point);
-            },
-            600,
-        );
+        let res = gadget_tester(|composer| {
+            let scalar = JubJubScalar::from_bytes_wide(&[
+                182, 44, 247, 214, 94, 14, 151, 208, 130, 16, 200, 204, 147,
+                32, 104, 166, 0, 59, 52, 1, 1, 59, 103, 6, 169, 175, 51, 101,
+                234, 180, 125, 14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ]);
+            let bls_scalar = BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
+            let secret_scalar = composer.add_input(bls_scalar);
+
+            let expected_point: JubJubAffine =
+                (GENERATOR_EXTENDED * scalar).into();
+
+            let point_scalar = composer
+                .fixed_base_scalar_mul(secret_scalar, GENERATOR_EXTENDED);
+
+            composer.assert_equal_public_point(point_scalar, expected_point);
+        });
         assert!(res.is_ok());
     }
 
     #[test]
     fn test_ecc_constraint_zero() {
-        let res = gadget_tester(
-            |composer| {
-                let scalar = JubJubScalar::zero();
-                let bls_scalar =
-                    BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
-                let secret_scalar = composer.add_input(bls_scalar);
-
-                let expected_point: JubJubAffine =
-                    (GENERATOR_EXTENDED * scalar).into();
-
-                let point_scalar = composer
-                    .fixed_base_scalar_mul(secret_scalar, GENERATOR_EXTENDED);
-
-                composer
-                    .assert_equal_public_point(point_scalar, expected_point);
-            },
-            600,
-        );
+        let res = gadget_tester(|composer| {
+            let scalar = JubJubScalar::zero();
+            let bls_scalar = BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
+            let secret_scalar = composer.add_input(bls_scalar);
+
+            let expected_point: JubJubAffine =
+                (GENERATOR_EXTENDED * scalar).into();
+
+            let point_scalar = composer
+                .fixed_base_scalar_mul(secret_scalar, GENERATOR_EXTENDED);
+
+            composer.assert_equal_public_point(point_scalar, expected_point);
+        });
         assert!(res.is_ok());
     }
     #[test]
     fn test_ecc_constraint_should_fail() {
-        let res = gadget_tester(
-            |composer| {
-                let scalar = JubJubScalar::from(100u64);
-                let bls_scalar =
-                    BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
-                let secret_scalar = composer.add_input(bls_scalar);
-                // Fails because we are not multiplying by the GENERATOR, it is
-                // double
-
-                let double_gen = GENERATOR_EXTENDED.double();
-
-                let expected_point: JubJubAffine = (double_gen * scalar).into();
-
-                let point_scalar = composer
-                    .fixed_base_scalar_mul(secret_scalar, GENERATOR_EXTENDED);
-
-                composer
-                    .assert_equal_public_point(point_scalar, expected_point);
-            },
-            600,
-        );
+        let res = gadget_tester(|composer| {
+            let scalar = JubJubScalar::from(100u64);
+            let bls_scalar = BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
+            let secret_scalar = composer.add_input(bls_scalar);
+            // Fails because we are not multiplying by the GENERATOR, it is
+            // double
+
+            let double_gen = GENERATOR_EXTENDED.double();
+
+            let expected_point: JubJubAffine = (double_gen * scalar).into();
+
+            let point_scalar = composer
+                .fixed_base_scalar_mul(secret_scalar, GENERATOR_EXTENDED);
+
+            composer.assert_equal_public_point(point_scalar, expected_point);
+        });
 
         assert!(res.is_err());
     }
     #[test]
     fn test_point_addition() {
-        let res = gadget_tester(
-            |composer| {
-                let point_a = GENERATOR_EXTENDED;
-                let point_b = point_a.double();
-                let expected_point = point_a + point_b;
-
-                let affine_point_a: JubJubAffine = point_a.into();
-                let affine_point_b: JubJubAffine = point_b.into();
-                let affine_expected_point: JubJubAffine = expected_point.into();
-
-                let var_point_a_x = composer.add_input(affine_point_a.get_x());
-                let var_point_a_y = composer.add_input(affine_point_a.get_y());
-                let point_a = Point {
-                    x: var_point_a_x,
-                    y: var_point_a_y,
-                };
-                let var_point_b_x = composer.add_input(affine_point_b.get_x());
-                let var_point_b_y = composer.add_input(affine_point_b.get_y());
-                let point_b = Point {
-                    x: var_point_b_x,
-                    y: var_point_b_y,
-                };
-                let new_point = composer.point_addition_gate(point_a, point_b);
-
-                composer.assert_equal_public_point(
-                    new_point,
-                    affine_expected_point,
-                );
-            },
-            600,
-        );
+        let res = gadget_tester(|composer| {
+            let point_a = GENERATOR_EXTENDED;
+            let point_b = point_a.double();
+            let expected_point = point_a + point_b;
+
+            let affine_point_a: JubJubAffine = point_a.into();
+            let affine_point_b: JubJubAffine = point_b.into();
+            let affine_expected_point: JubJubAffine = expected_point.into();
+
+            let var_point_a_x = composer.add_input(affine_point_a.get_x());
+            let var_point_a_y = composer.add_input(affine_point_a.get_y());
+            let point_a = Point {
+                x: var_point_a_x,
+                y: var_point_a_y,
+            };
+            let var_point_b_x = composer.add_input(affine_point_b.get_x());
+            let var_point_b_y = composer.add_input(affine_point_b.get_y());
+            let point_b = Point {
+                x: var_point_b_x,
+                y: var_point_b_y,
+            };
+            let new_point = composer.point_addition_gate(point_a, point_b);
+
+            composer
+                .assert_equal_public_point(new_point, affine_expected_point);
+        });
 
         assert!(res.is_ok());
     }
     #[test]
     #[allow(non_snake_case)]
     fn test_pedersen_hash() {
-        let res = gadget_tester(
-            |composer| {
-                // First component
-                let scalar_a = JubJubScalar::from(112233u64);
-                let bls_scalar =
-                    BlsScalar::from_bytes(&scalar_a.to_bytes()).unwrap();
-                let secret_scalar_a = composer.add_input(bls_scalar);
-                let point_a = GENERATOR_EXTENDED;
-                let c_a: JubJubAffine = (point_a * scalar_a).into();
-
-                // Second component
-                let scalar_b = JubJubScalar::from(445566u64);
-                let bls_scalar =
-                    BlsScalar::from_bytes(&scalar_b.to_bytes()).unwrap();
-                let secret_scalar_b = composer.add_input(bls_scalar);
-                let point_b = point_a.double() + point_a;
-                let c_b: JubJubAffine = (point_b * scalar_b).into();
-
-                // Expected pedersen hash
-                let expected_point: JubJubAffine =
-                    (point_a * scalar_a + point_b * scalar_b).into();
-
-                // To check this pedersen commitment, we will need to do:
-                // - Two scalar multiplications
-                // - One curve addition
-                //
-                // Scalar multiplications
-                let aG =
-                    composer.fixed_base_scalar_mul(secret_scalar_a, point_a);
-                let bH =
-                    composer.fixed_base_scalar_mul(secret_scalar_b, point_b);
-
-                // Depending on the context, one can check if the resulting aG
-                // and bH are as expected
-                //
-                composer.assert_equal_public_point(aG, c_a);
-                composer.assert_equal_public_point(bH, c_b);
-
-                // Curve addition
-                let commitment = composer.point_addition_gate(aG, bH);
-
-                // Add final constraints to ensure that the commitment that we
-                // computed is equal to the public point
-                composer.assert_equal_public_point(commitment, expected_point);
-            },
-            1024,
-        );
+        let res = gadget_tester(|composer| {
+            // First component
+            let scalar_a = JubJubScalar::from(112233u64);
+            let bls_scalar =
+                BlsScalar::from_bytes(&scalar_a.to_bytes()).unwrap();
+            let secret_scalar_a = composer.add_input(bls_scalar);
+            let point_a = GENERATOR_EXTENDED;
+            let c_a: JubJubAffine = (point_a * scalar_a).into();
+
+            // Second component
+            let scalar_b = JubJubScalar::from(445566u64);
+            let bls_scalar =
+                BlsScalar::from_bytes(&scalar_b.to_bytes()).unwrap();
+            let secret_scalar_b = composer.add_input(bls_scalar);
+            let point_b = point_a.double() + point_a;
+            let c_b: JubJubAffine = (point_b * scalar_b).into();
+
+            // Expected pedersen hash
+            let expected_point: JubJubAffine =
+                (point_a * scalar_a + point_b * scalar_b).into();
+
+            // To check this pedersen commitment, we will need to do:
+            // - Two scalar multiplications
+            // - One curve addition
+            //
+            // Scalar multiplications
+            let aG = composer.fixed_base_scalar_mul(secret_scalar_a, point_a);
+            let bH = composer.fixed_base_scalar_mul(secret_scalar_b, point_b);
+
+            // Depending on the context, one can check if the resulting aG
+            // and bH are as expected
+            //
+            composer.assert_equal_public_point(aG, c_a);
+            composer.assert_equal_public_point(bH, c_b);
+
+            // Curve addition
+            let commitment = composer.point_addition_gate(aG, bH);
+
+            // Add final constraints to ensure that the commitment that we
+            // computed is equal to the public point
+            composer.assert_equal_public_point(commitment, expected_point);
+        });
         assert!(res.is_ok());
     }
     #[test]
     #[allow(non_snake_case)]
     fn test_pedersen_balance() {
-        let res = gadget_tester(
-            |composer| {
-                // First component
-                let scalar_a = JubJubScalar::from(25u64);
-                let bls_scalar_a =
-                    BlsScalar::from_bytes(&scalar_a.to_bytes()).unwrap();
-                let secret_scalar_a = composer.add_input(bls_scalar_a);
-                // Second component
-                let scalar_b = JubJubScalar::from(30u64);
-                let bls_scalar_b =
-                    BlsScalar::from_bytes(&scalar_b.to_bytes()).unwrap();
-                let secret_scalar_b = composer.add_input(bls_scalar_b);
-                // Third component
-                let scalar_c = JubJubScalar::from(10u64);
-                let bls_scalar_c =
-                    BlsScalar::from_bytes(&scalar_c.to_bytes()).unwrap();
-                let secret_scalar_c = composer.add_input(bls_scalar_c);
-                // Fourth component
-                let scalar_d = JubJubScalar::from(45u64);
-                let bls_scalar_d =
-                    BlsScalar::from_bytes(&scalar_d.to_bytes()).unwrap();
-                let secret_scalar_d = composer.add_input(bls_scalar_d);
-
-                let gen = GENERATOR_EXTENDED;
-                let expected_lhs: JubJubAffine =
-                    (gen * (scalar_a + scalar_b)).into();
-                let expected_rhs: JubJubAffine =
-                    (gen * (scalar_c + scalar_d)).into();
-
-                let P1 = composer.fixed_base_scalar_mul(secret_scalar_a, gen);
-                let P2 = composer.fixed_base_scalar_mul(secret_scalar_b, gen);
-                let P3 = composer.fixed_base_scalar_mul(secret_scalar_c, gen);
-                let P4 = composer.fixed_base_scalar_mul(secret_scalar_d, gen);
-
-                let commitment_a = composer.point_addition_gate(P1, P2);
-                let commitment_b = composer.point_addition_gate(P3, P4);
-
-                composer.assert_equal_point(commitment_a, commitment_b);
-
-                composer.assert_equal_public_point(commitment_a, expected_lhs);
-                composer.assert_equal_public_point(commitment_b, expected_rhs);
-            },
-            2048,
-        );
+        let res = gadget_tester(|composer| {
+            // First component
+            let scalar_a = JubJubScalar::from(25u64);
+            let bls_scalar_a =
+                BlsScalar::from_bytes(&scalar_a.to_bytes()).unwrap();
+            let secret_scalar_a = composer.add_input(bls_scalar_a);
+            // Second component
+            let scalar_b = JubJubScalar::from(30u64);
+            let bls_scalar_b =
+                BlsScalar::from_bytes(&scalar_b.to_bytes()).unwrap();
+            let secret_scalar_b = composer.add_input(bls_scalar_b);
+            // Third component
+            let scalar_c = JubJubScalar::from(10u64);
+            let bls_scalar_c =
+                BlsScalar::from_bytes(&scalar_c.to_bytes()).unwrap();
+            let secret_scalar_c = composer.add_input(bls_scalar_c);
+            // Fourth component
+            let scalar_d = JubJubScalar::from(45u64);
+            let bls_scalar_d =
+                BlsScalar::from_bytes(&scalar_d.to_bytes()).unwrap();
+            let secret_scalar_d = composer.add_input(bls_scalar_d);
+
+            let gen = GENERATOR_EXTENDED;
+            let expected_lhs: JubJubAffine =
+                (gen * (scalar_a + scalar_b)).into();
+            let expected_rhs: JubJubAffine =
+                (gen * (scalar_c + scalar_d)).into();
+
+            let P1 = composer.fixed_base_scalar_mul(secret_scalar_a, gen);
+            let P2 = composer.fixed_base_scalar_mul(secret_scalar_b, gen);
+            let P3 = composer.fixed_base_scalar_mul(secret_scalar_c, gen);
+            let P4 = composer.fixed_base_scalar_mul(secret_scalar_d, gen);
+
+            let commitment_a = composer.point_addition_gate(P1, P2);
+            let commitment_b = composer.point_addition_gate(P3, P4);
+
+            composer.assert_equal_point(commitment_a, commitment_b);
+
+            composer.assert_equal_public_point(commitment_a, expected_lhs);
+            composer.assert_equal_public_point(commitment_b, expected_rhs);
+        });
         assert!(res.is_ok());
     }
 }
@@ -35,12 +35,48 @@ impl StandardComposer {
         let scalar_bits_var =
             self.scalar_decomposition(jubjub_var, raw_bls_scalar);
 
+        self.scalar_bits_mul(&scalar_bits_var, point)
+    }
+
+    /// Adds to the circuit description the proof that `a == [x]g` and
+    /// `b == [x]h` for the same secret scalar `x`, ie. that `a` and `b` have
+    /// the same discrete logarithm with respect to the bases `g` and `h`.
+    ///
+    /// The scalar is given by its bits `x_bits`, least significant first,
+    /// which are constrained to be boolean and shared by both scalar
+    /// multiplications.
+    pub fn assert_dleq(
+        &mut self,
+        g: Point,
+        h: Point,
+        a: Point,
+        b: Point,
+        x_bits: &[Variable],
+    ) {
+        x_bits.iter().for_each(|bit| {
+            self.boolean_gate(*bit);
+        });
+
+        let x_g = self.scalar_bits_mul(x_bits, g);
+        let x_h = self.scalar_bits_mul(x_bits, h);
+
+        self.assert_equal_point(x_g, a);
+        self.assert_equal_point(x_h, b);
+    }
+
+    /// Multiplies `point` by the scalar whose bits, least significant first,
+    /// are `scalar_bits_var`, using double-and-add.
+    fn scalar_bits_mul(
+        &mut self,
+        scalar_bits_var: &[Variable],
+        point: Point,
+    ) -> Point {
         let identity = Point::identity(self);
         let mut result = identity;
 
-        for bit in scalar_bits_var.into_iter().rev() {
+        for bit in scalar_bits_var.iter().rev() {
             result = self.point_addition_gate(result, result);
-            let point_to_add = self.conditional_select_identity(bit, point);
+            let point_to_add = self.conditional_select_identity(*bit, point);
             result = self.point_addition_gate(result, point_to_add);
         }
 
@@ -109,32 +145,69 @@ mod tests {
     use dusk_jubjub::{JubJubAffine, JubJubExtended, JubJubScalar};
     #[test]
     fn test_var_base_scalar_mul() {
-        let res = gadget_tester(
-            |composer| {
-                let scalar = JubJubScalar::from_bytes_wide(&[
-                    182, 44, 247, 214, 94, 14, 151, 208, 130, 16, 200, 204,
-                    147, 32, 104, 166, 0, 59, 52, 1, 1, 59, 103, 6, 169, 175,
-                    51, 101, 234, 180, 125, 14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-                    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-                    0, 0,
-                ]);
-                let bls_scalar =
-                    BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
-                let secret_scalar = composer.add_input(bls_scalar);
-
-                let expected_point: JubJubAffine =
-                    (JubJubExtended::from(GENERATOR) * scalar).into();
-
-                let point = composer.add_affine(GENERATOR);
-
-                let point_scalar =
-                    composer.variable_base_scalar_mul(secret_scalar, point);
-
-                composer
-                    .assert_equal_public_point(point_scalar, expected_point);
-            },
-            4096,
-        );
+        let res = gadget_tester(|composer| {
+            let scalar = JubJubScalar::from_bytes_wide(&[
+                182, 44, 247, 214, 94, 14, 151, 208, 130, 16, 200, 204, 147,
+                32, 104, 166, 0, 59, 52, 1, 1, 59, 103, 6, 169, 175, 51, 101,
+                234, 180, 125, 14, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ]);
+            let bls_scalar = BlsScalar::from_bytes(&scalar.to_bytes()).unwrap();
+            let secret_scalar = composer.add_input(bls_scalar);
+
+            let expected_point: JubJubAffine =
+                (JubJubExtended::from(GENERATOR) * scalar).into();
+
+            let point = composer.add_affine(GENERATOR);
+
+            let point_scalar =
+                composer.variable_base_scalar_mul(secret_scalar, point);
+
+            composer.assert_equal_public_point(point_scalar, expected_point);
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_dleq() {
+        // Should pass as both points share the discrete logarithm 0xb7e1
+        let res = gadget_tester(|composer| {
+            let x = 0xb7e1u64;
+            let generator = JubJubExtended::from(GENERATOR);
+            let h = generator * JubJubScalar::from(11u64);
+            let a: JubJubAffine = (generator * JubJubScalar::from(x)).into();
+            let b: JubJubAffine = (h * JubJubScalar::from(x)).into();
+
+            let x_bits: Vec<Variable> = (0..16)
+                .map(|i| composer.add_input(BlsScalar::from((x >> i) & 1)))
+                .collect();
+            let g = composer.add_affine(GENERATOR);
+            let h = composer.add_affine(h.into());
+            let a = composer.add_affine(a);
+            let b = composer.add_affine(b);
+
+            composer.assert_dleq(g, h, a, b, &x_bits);
+        });
         assert!(res.is_ok());
+
+        // Should fail as `b` has a different discrete logarithm
+        let res = gadget_tester(|composer| {
+            let x = 0xb7e1u64;
+            let generator = JubJubExtended::from(GENERATOR);
+            let h = generator * JubJubScalar::from(11u64);
+            let a: JubJubAffine = (generator * JubJubScalar::from(x)).into();
+            let b: JubJubAffine = (h * JubJubScalar::from(x + 1)).into();
+
+            let x_bits: Vec<Variable> = (0..16)
+                .map(|i| composer.add_input(BlsScalar::from((x >> i) & 1)))
+                .collect();
+            let g = composer.add_affine(GENERATOR);
+            let h = composer.add_affine(h.into());
+            let a = composer.add_affine(a);
+            let b = composer.add_affine(b);
+
+            composer.assert_dleq(g, h, a, b, &x_bits);
+        });
+        assert!(res.is_err());
     }
 }
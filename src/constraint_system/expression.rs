@@ -0,0 +1,241 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::{StandardComposer, Variable};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+/// Builder of an arithmetic expression over [`Variable`]s, lowered to
+/// [`StandardComposer::big_mul`] and [`StandardComposer::big_add`] gates,
+/// see [`StandardComposer::expr`].
+///
+/// The expression is only lowered once it no longer fits in a single gate,
+/// which holds either a product and one more term, or up to three terms,
+/// along with a constant. So `composer.expr(a).mul(b).add(c).build()` adds
+/// the same gate as
+/// `composer.big_mul(one, a, b, Some((one, c)), zero, None)`.
+#[derive(Debug)]
+pub struct Expression<'a> {
+    composer: &'a mut StandardComposer,
+    product: Option<(BlsScalar, Variable, Variable)>,
+    terms: Vec<(BlsScalar, Variable)>,
+    constant: BlsScalar,
+    pi: Option<BlsScalar>,
+}
+
+impl<'a> Expression<'a> {
+    /// Multiplies the expression by `b`.
+    ///
+    /// A single term is multiplied without any gate, otherwise the
+    /// expression is first lowered to a [`Variable`].
+    pub fn mul(mut self, b: Variable) -> Self {
+        let product = match self.single_term() {
+            Some((q, a)) => (q, a, b),
+            None => (BlsScalar::one(), self.lower(), b),
+        };
+        self.terms.clear();
+        self.product = Some(product);
+        self
+    }
+
+    /// Adds `b` to the expression.
+    pub fn add(self, b: Variable) -> Self {
+        self.add_scaled(BlsScalar::one(), b)
+    }
+
+    /// Subtracts `b` from the expression.
+    pub fn sub(self, b: Variable) -> Self {
+        self.add_scaled(-BlsScalar::one(), b)
+    }
+
+    /// Adds `q * b` to the expression, lowering it first if its gate has no
+    /// room left for another term.
+    pub fn add_scaled(mut self, q: BlsScalar, b: Variable) -> Self {
+        let room = match self.product {
+            Some(_) => 1,
+            None => 3,
+        };
+        if self.terms.len() == room {
+            let lowered = self.lower();
+            self.terms.push((BlsScalar::one(), lowered));
+        }
+        self.terms.push((q, b));
+        self
+    }
+
+    /// Adds the constant `c` to the expression.
+    pub fn add_constant(mut self, c: BlsScalar) -> Self {
+        self.constant += c;
+        self
+    }
+
+    /// Adds the public input `pi` to the next gate the expression is lowered
+    /// to.
+    pub fn public_input(mut self, pi: BlsScalar) -> Self {
+        self.pi = Some(pi);
+        self
+    }
+
+    /// Lowers the remaining expression, returning the [`Variable`] holding
+    /// its value. A bare variable is returned as is, without any gate.
+    pub fn build(mut self) -> Variable {
+        match self.single_term() {
+            Some((q, a)) if q == BlsScalar::one() => a,
+            _ => self.lower(),
+        }
+    }
+
+    /// Returns the only term of the expression, if it has neither a
+    /// product, a constant nor a public input.
+    fn single_term(&self) -> Option<(BlsScalar, Variable)> {
+        match self.terms.as_slice() {
+            [term]
+                if self.product.is_none()
+                    && self.constant == BlsScalar::zero()
+                    && self.pi.is_none() =>
+            {
+                Some(*term)
+            }
+            _ => None,
+        }
+    }
+
+    /// Adds the gate computing the pending expression, which is then reset,
+    /// and returns its output.
+    fn lower(&mut self) -> Variable {
+        let zero = (BlsScalar::zero(), self.composer.zero_var);
+        let constant = core::mem::take(&mut self.constant);
+        let pi = self.pi.take();
+        let mut terms = self.terms.drain(..);
+
+        match self.product.take() {
+            Some((q_m, a, b)) => {
+                self.composer.big_mul(q_m, a, b, terms.next(), constant, pi)
+            }
+            None => {
+                let left = terms.next().unwrap_or(zero);
+                let right = terms.next().unwrap_or(zero);
+                let fourth = terms.next();
+                self.composer.big_add(left, right, fourth, constant, pi)
+            }
+        }
+    }
+}
+
+impl StandardComposer {
+    /// Starts an [`Expression`] from `a`, to write arithmetic over
+    /// [`Variable`]s without spelling out the selectors of every gate.
+    pub fn expr(&mut self, a: Variable) -> Expression<'_> {
+        Expression {
+            composer: self,
+            product: None,
+            terms: vec![(BlsScalar::one(), a)],
+            constant: BlsScalar::zero(),
+            pi: None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+
+    /// Asserts that both composers hold the same gates over the same
+    /// variables.
+    fn assert_same_gates(left: &StandardComposer, right: &StandardComposer) {
+        assert_eq!(left.n, right.n);
+        assert_eq!(left.q_m, right.q_m);
+        assert_eq!(left.q_l, right.q_l);
+        assert_eq!(left.q_r, right.q_r);
+        assert_eq!(left.q_o, right.q_o);
+        assert_eq!(left.q_c, right.q_c);
+        assert_eq!(left.q_4, right.q_4);
+        assert_eq!(left.q_arith, right.q_arith);
+        assert_eq!(left.w_l, right.w_l);
+        assert_eq!(left.w_r, right.w_r);
+        assert_eq!(left.w_o, right.w_o);
+        assert_eq!(left.w_4, right.w_4);
+        assert_eq!(
+            left.public_inputs_sparse_store,
+            right.public_inputs_sparse_store
+        );
+        assert_eq!(left.variables, right.variables);
+    }
+
+    fn inputs(composer: &mut StandardComposer) -> [Variable; 4] {
+        let mut vars = [composer.zero_var; 4];
+        vars.iter_mut().enumerate().for_each(|(i, var)| {
+            *var = composer.add_input(BlsScalar::from(i as u64 + 2))
+        });
+        vars
+    }
+
+    #[test]
+    fn test_expression_gates() {
+        let one = BlsScalar::one();
+        let zero = BlsScalar::zero();
+        let seven = BlsScalar::from(7);
+
+        let mut manual = StandardComposer::new();
+        let [a, b, c, d] = inputs(&mut manual);
+        manual.big_mul(one, a, b, Some((one, c)), zero, None);
+        manual.big_add((one, a), (-one, b), Some((seven, c)), one, None);
+        let ab = manual.big_mul(one, a, b, None, seven, None);
+        manual.big_mul(one, ab, c, Some((one, d)), zero, Some(zero));
+        let abc =
+            manual.big_add((one, a), (one, b), Some((one, c)), zero, None);
+        manual.big_add((one, abc), (one, d), None, zero, None);
+
+        let mut built = StandardComposer::new();
+        let [a, b, c, d] = inputs(&mut built);
+        built.expr(a).mul(b).add(c).build();
+        built
+            .expr(a)
+            .sub(b)
+            .add_scaled(seven, c)
+            .add_constant(one)
+            .build();
+        built
+            .expr(a)
+            .mul(b)
+            .add_constant(seven)
+            .mul(c)
+            .add(d)
+            .public_input(zero)
+            .build();
+        built.expr(a).add(b).add(c).add(d).build();
+
+        assert_same_gates(&manual, &built);
+
+        // A bare variable adds no gate
+        let gates = built.circuit_size();
+        assert_eq!(built.expr(a).build(), a);
+        assert_eq!(built.circuit_size(), gates);
+    }
+
+    #[test]
+    fn test_expression_values() {
+        let res = gadget_tester(|composer| {
+            let [a, b, c, d] = inputs(composer);
+
+            // ((2 * 3 + 4) * 5 - 2) * 3 + 1 = 145
+            let result = composer
+                .expr(a)
+                .mul(b)
+                .add(c)
+                .mul(d)
+                .sub(a)
+                .mul(b)
+                .add_constant(BlsScalar::one())
+                .build();
+            assert_eq!(composer.variables[&result], BlsScalar::from(145));
+            composer.constrain_to_constant(result, BlsScalar::from(145), None);
+        });
+        assert!(res.is_ok());
+    }
+}
@@ -47,15 +47,14 @@ pub(crate) fn dummy_gadget_plookup(n: usize, composer: &mut StandardComposer) {
 }
 
 /// Takes a generic gadget function with no auxillary input and
-/// tests whether it passes an end-to-end test
+/// tests whether it passes an end-to-end test. The public parameters are
+/// sized after the circuit built by the gadget, see
+/// [`StandardComposer::next_power_of_two_size`].
 pub(crate) fn gadget_tester(
     gadget: fn(composer: &mut StandardComposer),
-    n: usize,
 ) -> Result<(), Error> {
-    // Common View
-    let public_parameters = PublicParameters::setup(2 * n, &mut OsRng)?;
     // Provers View
-    let (proof, public_inputs) = {
+    let (public_parameters, proof, public_inputs) = {
         // Create a prover struct
         let mut prover = Prover::new(b"demo");
 
@@ -65,9 +64,12 @@ pub(crate) fn gadget_tester(
         // Add gadgets
         gadget(&mut prover.mut_cs());
 
+        // Common View
+        let size = prover.cs.next_power_of_two_size();
+        let public_parameters = PublicParameters::setup(2 * size, &mut OsRng)?;
+
         // Commit Key
-        let (ck, _) = public_parameters
-            .trim(2 * prover.cs.circuit_size().next_power_of_two())?;
+        let (ck, _) = public_parameters.trim(2 * size)?;
 
         // Preprocess circuit
         prover.preprocess(&ck)?;
@@ -77,7 +79,7 @@ pub(crate) fn gadget_tester(
         let public_inputs = prover.cs.construct_dense_pi_vec();
 
         // Compute Proof
-        (prover.prove(&ck)?, public_inputs)
+        (public_parameters, prover.prove(&ck)?, public_inputs)
     };
     // Verifiers view
     //
@@ -91,8 +93,8 @@ pub(crate) fn gadget_tester(
     gadget(&mut verifier.mut_cs());
 
     // Compute Commit and Verifier Key
-    let (ck, vk) = public_parameters
-        .trim(verifier.cs.circuit_size().next_power_of_two())?;
+    let (ck, vk) =
+        public_parameters.trim(verifier.cs.next_power_of_two_size())?;
 
     // Preprocess circuit
     verifier.preprocess(&ck)?;
@@ -101,18 +103,53 @@ pub(crate) fn gadget_tester(
     verifier.verify(&proof, &vk, &public_inputs)
 }
 
+/// Asserts that `composer` holds exactly `expected` gates, counting the ones
+/// every composer starts with, see [`StandardComposer::circuit_size`].
+pub(crate) fn assert_gate_count(composer: &StandardComposer, expected: usize) {
+    assert_eq!(
+        composer.circuit_size(),
+        expected,
+        "the circuit has {} gates instead of {}",
+        composer.circuit_size(),
+        expected
+    );
+}
+
+/// Same as [`gadget_tester`], but first checks that the gadget adds at most
+/// `max_gates` gates to a fresh composer, so that a blowup of its gate count
+/// fails the test.
+///
+/// # Panics
+/// If the gadget adds more than `max_gates` gates.
+pub(crate) fn gadget_tester_exact(
+    gadget: fn(composer: &mut StandardComposer),
+    max_gates: usize,
+) -> Result<(), Error> {
+    let mut composer = StandardComposer::new();
+    let initial_gates = composer.circuit_size();
+    gadget(&mut composer);
+    let gates = composer.circuit_size() - initial_gates;
+    assert!(
+        gates <= max_gates,
+        "the gadget adds {} gates, more than {}",
+        gates,
+        max_gates
+    );
+
+    gadget_tester(gadget)
+}
+
 /// Takes a generic gadget function with no auxillary input and
 /// tests whether it passes an end-to-end test. If using a lookup table,
-/// all plookup gates must correspond to rows in lookup_table
+/// all plookup gates must correspond to rows in lookup_table. The public
+/// parameters are sized after the circuit and the table, as in
+/// [`gadget_tester`].
 pub(crate) fn gadget_plookup_tester(
     gadget: fn(composer: &mut StandardComposer),
-    n: usize,
     lookup_table: PlookupTable4Arity,
 ) -> Result<(), Error> {
-    // Common View
-    let public_parameters = PublicParameters::setup(2 * n, &mut OsRng)?;
     // Provers View
-    let (proof, public_inputs) = {
+    let (public_parameters, proof, public_inputs) = {
         // Create a prover struct
         let mut prover = Prover::new(b"demo");
 
@@ -125,9 +162,12 @@ pub(crate) fn gadget_plookup_tester(
         // Add gadgets
         gadget(&mut prover.mut_cs());
 
+        // Common View
+        let size = prover.cs.next_power_of_two_size();
+        let public_parameters = PublicParameters::setup(2 * size, &mut OsRng)?;
+
         // Commit Key
-        let (ck, _) = public_parameters
-            .trim(2 * prover.cs.circuit_size().next_power_of_two())?;
+        let (ck, _) = public_parameters.trim(2 * size)?;
 
         // Preprocess circuit
         prover.preprocess(&ck)?;
@@ -137,7 +177,7 @@ pub(crate) fn gadget_plookup_tester(
         let public_inputs = prover.cs.construct_dense_pi_vec();
 
         // Compute Proof
-        (prover.prove(&ck)?, public_inputs)
+        (public_parameters, prover.prove(&ck)?, public_inputs)
     };
     // Verifiers view
     //
@@ -154,8 +194,8 @@ pub(crate) fn gadget_plookup_tester(
     gadget(&mut verifier.mut_cs());
 
     // Compute Commit and Verifier Key
-    let (ck, vk) = public_parameters
-        .trim(verifier.cs.circuit_size().next_power_of_two())?;
+    let (ck, vk) =
+        public_parameters.trim(verifier.cs.next_power_of_two_size())?;
 
     // Preprocess circuit
     verifier.preprocess(&ck)?;
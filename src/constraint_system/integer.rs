@@ -0,0 +1,770 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::zelbet::conv::{scalar_to_u256, u256_to_scalar};
+use crate::constraint_system::StandardComposer;
+use crate::constraint_system::Variable;
+use bigint::U256 as u256;
+use dusk_bls12_381::BlsScalar;
+
+/// Largest number of bits for which the integer gadgets are guaranteed not
+/// to wrap around the field modulus.
+const MAX_INTEGER_BITS: usize = 124;
+
+impl StandardComposer {
+    /// Adds the constraints that check that `x` is a multiple of `divisor`,
+    /// by witnessing the quotient `k` and constraining `k * divisor = x`.
+    ///
+    /// `x`, `divisor` and the quotient are range-constrained to `num_bits`
+    /// bits, so the product can't wrap around the field modulus. A zero
+    /// `divisor` is only satisfied by `x = 0`.
+    ///
+    /// # Panics
+    /// This function will panic if `num_bits` is odd or larger than 124.
+    pub fn assert_multiple_of(
+        &mut self,
+        x: Variable,
+        divisor: Variable,
+        num_bits: usize,
+    ) {
+        assert!(num_bits <= MAX_INTEGER_BITS);
+        self.range_gate(x, num_bits);
+        self.range_gate(divisor, num_bits);
+        self.multiple_of_gate(x, divisor, num_bits);
+    }
+
+    /// Adds the constraints that check that `g` is the greatest common
+    /// divisor of `a` and `b`.
+    ///
+    /// The gadget checks that `g` divides both `a` and `b`, see
+    /// [`StandardComposer::assert_multiple_of`], and witnesses the Bezout
+    /// coefficients `s` and `t` satisfying `s * a + t * b = g`, so any common
+    /// divisor of `a` and `b` also divides `g`. The coefficients are bounded
+    /// in absolute value so that the relation holds over the integers.
+    ///
+    /// `a`, `b` and `g` are range-constrained to `num_bits` bits.
+    ///
+    /// # Panics
+    /// This function will panic if `num_bits` is odd or larger than 124.
+    pub fn assert_gcd(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        g: Variable,
+        num_bits: usize,
+    ) {
+        assert!(num_bits <= MAX_INTEGER_BITS);
+        self.range_gate(a, num_bits);
+        self.range_gate(b, num_bits);
+        self.range_gate(g, num_bits);
+
+        self.multiple_of_gate(a, g, num_bits);
+        self.multiple_of_gate(b, g, num_bits);
+
+        let (s, t) = bezout_coefficients(
+            u256(self.witness_canonical(a).0),
+            u256(self.witness_canonical(b).0),
+        );
+        let s = self.add_input(s);
+        let t = self.add_input(t);
+
+        // |s|, |t| < 2^num_bits for the honest coefficients, which we check
+        // by range-constraining s + 2^num_bits and t + 2^num_bits
+        let shift = BlsScalar::from(2).pow(&[num_bits as u64, 0, 0, 0]);
+        [s, t].iter().for_each(|coeff| {
+            let shifted = self.add(
+                (BlsScalar::one(), *coeff),
+                (BlsScalar::zero(), self.zero_var),
+                shift,
+                None,
+            );
+            self.range_gate(shifted, num_bits + 2);
+        });
+
+        // s * a + t * b - g = 0
+        let s_a = self.mul(BlsScalar::one(), s, a, BlsScalar::zero(), None);
+        self.big_mul_gate(
+            t,
+            b,
+            g,
+            Some(s_a),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            BlsScalar::one(),
+            None,
+        );
+    }
+
+    /// Adds the constraints that check that `(sign, magnitude)` is a valid
+    /// signed-magnitude integer of `num_bits` bits.
+    ///
+    /// `sign` is constrained to be boolean, being `1` for negative numbers,
+    /// and `magnitude` is range-constrained to `num_bits` bits. Zero has a
+    /// single valid representation, with positive sign: a zero `magnitude`
+    /// forces `sign` to be `0`.
+    ///
+    /// # Panics
+    /// This function will panic if `num_bits` is odd.
+    pub fn assert_signed_magnitude(
+        &mut self,
+        sign: Variable,
+        magnitude: Variable,
+        num_bits: usize,
+    ) {
+        self.boolean_gate(sign);
+        self.range_gate(magnitude, num_bits);
+
+        // sign = magnitude * w can only be satisfied with a zero sign when
+        // the magnitude is zero, while w = sign / magnitude works otherwise
+        let magnitude_value = self.variables[&magnitude];
+        let w = match magnitude_value == BlsScalar::zero() {
+            true => BlsScalar::zero(),
+            false => self.variables[&sign] * magnitude_value.invert().unwrap(),
+        };
+        let w = self.add_input(w);
+        self.mul_gate(
+            magnitude,
+            w,
+            sign,
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            None,
+        );
+    }
+
+    /// Adds the constraints that check that the counter `new` is the
+    /// increment of `old`, ie. `new = old + 1`, and that it doesn't exceed
+    /// the cap `max`.
+    ///
+    /// `new` and `max - new` are range-constrained to `num_bits` bits, so the
+    /// comparison can't wrap around the field modulus.
+    ///
+    /// # Panics
+    /// This function will panic if `num_bits` is odd or larger than 124.
+    pub fn assert_counter_step(
+        &mut self,
+        old: Variable,
+        new: Variable,
+        max: Variable,
+        num_bits: usize,
+    ) {
+        assert!(num_bits <= MAX_INTEGER_BITS);
+        self.assert_affine(old, new, BlsScalar::one(), BlsScalar::one());
+        self.less_or_equal_gate(new, max, num_bits);
+    }
+
+    /// Returns the [`Variable`] packing `lo` and `hi` into a single field
+    /// element, `packed = lo + hi * 2^shift_bits`, which is the inverse of
+    /// their decomposition.
+    ///
+    /// `lo` is range-constrained to `shift_bits` bits, so it doesn't overlap
+    /// with `hi`, and `hi` to the `254 - shift_bits` remaining bits, so the
+    /// packing can't wrap around the field modulus.
+    ///
+    /// # Panics
+    /// This function will panic if `shift_bits` is odd, zero or larger than
+    /// 252.
+    pub fn pack_two(
+        &mut self,
+        lo: Variable,
+        hi: Variable,
+        shift_bits: usize,
+    ) -> Variable {
+        assert!(
+            shift_bits % 2 == 0 && shift_bits > 0 && shift_bits <= 252,
+            "the shift must be even and between 2 and 252 bits, got {}",
+            shift_bits
+        );
+        self.range_gate(lo, shift_bits);
+        self.range_gate(hi, 254 - shift_bits);
+
+        self.add(
+            (BlsScalar::one(), lo),
+            (BlsScalar::from(2).pow(&[shift_bits as u64, 0, 0, 0]), hi),
+            BlsScalar::zero(),
+            None,
+        )
+    }
+
+    /// Returns the [`Variable`] holding `a + b`, with the inputs and the sum
+    /// all range-constrained to `bits` bits, so that the addition has the
+    /// semantics of the integers rather than wrapping around the field
+    /// modulus. A sum which doesn't fit in `bits` bits makes the circuit
+    /// unsatisfiable.
+    ///
+    /// # Panics
+    /// This function will panic if `bits` is odd, zero or larger than 252.
+    pub fn checked_add(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        bits: usize,
+    ) -> Variable {
+        assert!(
+            bits % 2 == 0 && bits > 0 && bits <= 252,
+            "the range must be even and between 2 and 252 bits, got {}",
+            bits
+        );
+        self.range_gate(a, bits);
+        self.range_gate(b, bits);
+
+        let sum = self.add(
+            (BlsScalar::one(), a),
+            (BlsScalar::one(), b),
+            BlsScalar::zero(),
+            None,
+        );
+        self.range_gate(sum, bits);
+        sum
+    }
+
+    /// Returns a boolean [`Variable`] which is `1` if `a < b`, and `0`
+    /// otherwise.
+    ///
+    /// `a` and `b` are range-constrained to `bits` bits with
+    /// [`StandardComposer::to_bits`]. The difference `a - b + 2^bits` is then
+    /// decomposed into `bits + 1` bits, and its most significant bit, which
+    /// is the complement of the borrow of `a - b`, gives the result.
+    ///
+    /// # Panics
+    /// This function will panic if `bits` is larger than 124.
+    pub fn less_than(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        bits: usize,
+    ) -> Variable {
+        assert!(bits <= MAX_INTEGER_BITS);
+        self.to_bits(a, bits);
+        self.to_bits(b, bits);
+
+        let offset = u256_to_scalar(u256::one() << bits);
+        let diff = self.add(
+            (BlsScalar::one(), a),
+            (-BlsScalar::one(), b),
+            offset,
+            None,
+        );
+        let no_borrow = self.to_bits(diff, bits + 1)[0];
+
+        self.add(
+            (-BlsScalar::one(), no_borrow),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::one(),
+            None,
+        )
+    }
+
+    /// Returns the quotient `q` and the remainder `r` of the euclidean
+    /// division of `x` by the constant `m`, constraining `x = q * m + r` and
+    /// `r < m` over the integers.
+    ///
+    /// `q` is bounded by `(p - 1) / m` and `r` by `m - 1` with range gates,
+    /// and when `q` reaches its bound `r` is further bounded by
+    /// `(p - 1) % m`, so `q * m + r` can't wrap around the field modulus
+    /// `p` and the division is the only solution. This is the in-circuit
+    /// counterpart of the `% s_i` steps of the decomposition gadgets.
+    ///
+    /// # Panics
+    /// This function will panic if `m` is zero or doesn't fit in 252 bits.
+    pub fn divmod_constant(
+        &mut self,
+        x: Variable,
+        m: BlsScalar,
+    ) -> (Variable, Variable) {
+        let m_value = scalar_to_u256(m);
+        assert!(
+            !m_value.is_zero() && m_value < u256::one() << 252,
+            "the modulus must be non-zero and fit in 252 bits"
+        );
+
+        let x_value = u256(self.witness_canonical(x).0);
+        let q = self.add_input(u256_to_scalar(x_value / m_value));
+        let r = self.add_input(u256_to_scalar(x_value % m_value));
+        self.divmod_gate(x, q, r, m_value);
+
+        (q, r)
+    }
+
+    /// Constrains `q` and `r` to be the quotient and the remainder of the
+    /// division of `x` by `m`, see [`StandardComposer::divmod_constant`].
+    fn divmod_gate(&mut self, x: Variable, q: Variable, r: Variable, m: u256) {
+        let max = scalar_to_u256(-BlsScalar::one());
+        let (q_max, r_max) = (max / m, max % m);
+        let m_minus_one = m - u256::one();
+
+        // x = q * m + r
+        self.add_gate(
+            q,
+            r,
+            x,
+            u256_to_scalar(m),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            None,
+        );
+
+        // 0 <= q <= q_max and 0 <= r <= m - 1
+        let q_bits = even_bit_length(q_max);
+        let r_bits = even_bit_length(m_minus_one);
+        let q_diff = self.upper_bound_gate(q, q_max, q_bits);
+        self.upper_bound_gate(r, m_minus_one, r_bits);
+
+        // Only q = q_max with r > r_max wraps around the modulus, so
+        // at_max * (r_max - r) is range constrained, where at_max is 1 if
+        // q_diff is zero and 0 otherwise
+        if r_max != m_minus_one {
+            let q_diff_value = self.variables[&q_diff];
+            let inverse = match q_diff_value == BlsScalar::zero() {
+                true => BlsScalar::zero(),
+                false => q_diff_value.invert().unwrap(),
+            };
+            let inverse = self.add_input(inverse);
+            let at_max = self.big_mul(
+                -BlsScalar::one(),
+                q_diff,
+                inverse,
+                None,
+                BlsScalar::one(),
+                None,
+            );
+            self.mul_gate(
+                q_diff,
+                at_max,
+                self.zero_var,
+                BlsScalar::one(),
+                BlsScalar::zero(),
+                BlsScalar::zero(),
+                None,
+            );
+
+            let slack = self.big_mul(
+                -BlsScalar::one(),
+                at_max,
+                r,
+                Some((u256_to_scalar(r_max), at_max)),
+                BlsScalar::zero(),
+                None,
+            );
+            self.range_gate(slack, r_bits);
+        }
+    }
+
+    /// Range-constrains `a` and `bound - a` to `num_bits` bits, which
+    /// implies `0 <= a <= bound` when `num_bits` is the even bit length of
+    /// `bound` and `bound` is either below `2^254` or `p - 1`, and returns
+    /// `bound - a`.
+    fn upper_bound_gate(
+        &mut self,
+        a: Variable,
+        bound: u256,
+        num_bits: usize,
+    ) -> Variable {
+        self.range_gate(a, num_bits);
+        let diff = self.add(
+            (-BlsScalar::one(), a),
+            (BlsScalar::zero(), self.zero_var),
+            u256_to_scalar(bound),
+            None,
+        );
+        self.range_gate(diff, num_bits);
+        diff
+    }
+
+    /// Range-constrains `a` and `b - a` to `num_bits` bits, which implies
+    /// `a <= b` over the integers as long as `2^(num_bits + 1)` doesn't
+    /// exceed the field modulus.
+    fn less_or_equal_gate(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        num_bits: usize,
+    ) {
+        self.range_gate(a, num_bits);
+        let diff = self.add(
+            (-BlsScalar::one(), a),
+            (BlsScalar::one(), b),
+            BlsScalar::zero(),
+            None,
+        );
+        self.range_gate(diff, num_bits);
+    }
+
+    /// Witnesses the quotient `k = x / divisor`, range-constrains it to
+    /// `num_bits` bits and constrains `k * divisor = x`.
+    fn multiple_of_gate(
+        &mut self,
+        x: Variable,
+        divisor: Variable,
+        num_bits: usize,
+    ) {
+        let x_value = u256(self.witness_canonical(x).0);
+        let divisor_value = u256(self.witness_canonical(divisor).0);
+        let quotient = match divisor_value.is_zero() {
+            true => u256::zero(),
+            false => x_value / divisor_value,
+        };
+        let quotient = self.add_input(u256_to_scalar(quotient));
+        self.range_gate(quotient, num_bits);
+
+        self.mul_gate(
+            quotient,
+            divisor,
+            x,
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            BlsScalar::zero(),
+            None,
+        );
+    }
+}
+
+/// Returns the number of bits of `x` rounded up to the next even number, and
+/// at least 2, as expected by the range gates.
+fn even_bit_length(x: u256) -> usize {
+    let bits = (0..4)
+        .rev()
+        .find(|&i| x.0[i] != 0)
+        .map_or(0, |i| 64 * i + 64 - x.0[i].leading_zeros() as usize);
+    core::cmp::max(2, (bits + 1) & !1)
+}
+
+/// Runs the extended Euclidean algorithm over `a` and `b`, returning the
+/// Bezout coefficients `(s, t)` such that `s * a + t * b = gcd(a, b)` as
+/// elements of the scalar field.
+fn bezout_coefficients(a: u256, b: u256) -> (BlsScalar, BlsScalar) {
+    let (mut r_0, mut r_1) = (a, b);
+    let (mut s_0, mut s_1) = (BlsScalar::one(), BlsScalar::zero());
+    let (mut t_0, mut t_1) = (BlsScalar::zero(), BlsScalar::one());
+
+    while !r_1.is_zero() {
+        let q = r_0 / r_1;
+        let q_scalar = u256_to_scalar(q);
+
+        let r_2 = r_0 - q * r_1;
+        r_0 = r_1;
+        r_1 = r_2;
+
+        let s_2 = s_0 - q_scalar * s_1;
+        s_0 = s_1;
+        s_1 = s_2;
+
+        let t_2 = t_0 - q_scalar * t_1;
+        t_0 = t_1;
+        t_1 = t_2;
+    }
+
+    (s_0, t_0)
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use dusk_bls12_381::BlsScalar;
+
+    #[test]
+    fn test_multiple_of() {
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(91u64));
+            let divisor = composer.add_input(BlsScalar::from(13u64));
+            composer.assert_multiple_of(x, divisor, 8);
+        });
+        assert!(res.is_ok());
+
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(92u64));
+            let divisor = composer.add_input(BlsScalar::from(13u64));
+            composer.assert_multiple_of(x, divisor, 8);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_gcd() {
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(84u64));
+            let b = composer.add_input(BlsScalar::from(180u64));
+            let g = composer.add_input(BlsScalar::from(12u64));
+            composer.assert_gcd(a, b, g, 8);
+        });
+        assert!(res.is_ok());
+
+        // 6 divides both 84 and 180 but is not their gcd
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(84u64));
+            let b = composer.add_input(BlsScalar::from(180u64));
+            let g = composer.add_input(BlsScalar::from(6u64));
+            composer.assert_gcd(a, b, g, 8);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_counter_step() {
+        // Should pass as the counter reaches the cap
+        let res = gadget_tester(|composer| {
+            let old = composer.add_input(BlsScalar::from(5u64));
+            let new = composer.add_input(BlsScalar::from(6u64));
+            let max = composer.add_input(BlsScalar::from(6u64));
+            composer.assert_counter_step(old, new, max, 8);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as the counter exceeds the cap
+        let res = gadget_tester(|composer| {
+            let old = composer.add_input(BlsScalar::from(6u64));
+            let new = composer.add_input(BlsScalar::from(7u64));
+            let max = composer.add_input(BlsScalar::from(6u64));
+            composer.assert_counter_step(old, new, max, 8);
+        });
+        assert!(res.is_err());
+
+        // Should fail as the step is not one
+        let res = gadget_tester(|composer| {
+            let old = composer.add_input(BlsScalar::from(5u64));
+            let new = composer.add_input(BlsScalar::from(7u64));
+            let max = composer.add_input(BlsScalar::from(10u64));
+            composer.assert_counter_step(old, new, max, 8);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_checked_add() {
+        // Should pass as the sum fits in 8 bits
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(200));
+            let b = composer.add_input(BlsScalar::from(55));
+            let sum = composer.checked_add(a, b, 8);
+            composer.constrain_to_constant(sum, BlsScalar::from(255), None);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as the sum needs 9 bits
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(200));
+            let b = composer.add_input(BlsScalar::from(56));
+            composer.checked_add(a, b, 8);
+        });
+        assert!(res.is_err());
+
+        // Should fail as an input is a negative field element, which would
+        // wrap the sum back into the range
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(200));
+            let b = composer.add_input(-BlsScalar::from(100));
+            let sum = composer.checked_add(a, b, 8);
+            composer.constrain_to_constant(sum, BlsScalar::from(100), None);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_pack_two() {
+        // Should pass as both parts fit in their bits
+        let res = gadget_tester(|composer| {
+            let lo = composer.add_input(BlsScalar::from(0xab));
+            let hi = composer.add_input(BlsScalar::from(0x1234));
+            let packed = composer.pack_two(lo, hi, 8);
+            composer.constrain_to_constant(
+                packed,
+                BlsScalar::from(0x1234ab),
+                None,
+            );
+
+            // The whole field is covered with the largest shift
+            let lo = composer.add_input(BlsScalar::from(5));
+            let hi = composer.add_input(BlsScalar::from(3));
+            let packed = composer.pack_two(lo, hi, 252);
+            let expected = BlsScalar::from(5)
+                + BlsScalar::from(3) * BlsScalar::from(2).pow(&[252, 0, 0, 0]);
+            assert_eq!(composer.variables[&packed], expected);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as lo overflows into hi
+        let res = gadget_tester(|composer| {
+            let lo = composer.add_input(BlsScalar::from(0x100));
+            let hi = composer.add_input(BlsScalar::from(0x1233));
+            let packed = composer.pack_two(lo, hi, 8);
+            composer.constrain_to_constant(
+                packed,
+                BlsScalar::from(0x123400),
+                None,
+            );
+        });
+        assert!(res.is_err());
+
+        // Should fail as hi doesn't fit in the remaining 2 bits
+        let res = gadget_tester(|composer| {
+            let lo = composer.add_input(BlsScalar::zero());
+            let hi = composer.add_input(BlsScalar::from(4));
+            composer.pack_two(lo, hi, 252);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_signed_magnitude() {
+        // Positive
+        let res = gadget_tester(|composer| {
+            let sign = composer.zero_var();
+            let magnitude = composer.add_input(BlsScalar::from(5u64));
+            composer.assert_signed_magnitude(sign, magnitude, 8);
+        });
+        assert!(res.is_ok());
+
+        // Negative
+        let res = gadget_tester(|composer| {
+            let sign = composer.add_input(BlsScalar::one());
+            let magnitude = composer.add_input(BlsScalar::from(5u64));
+            composer.assert_signed_magnitude(sign, magnitude, 8);
+        });
+        assert!(res.is_ok());
+
+        // Zero with a positive sign is the canonical form
+        let res = gadget_tester(|composer| {
+            let sign = composer.zero_var();
+            let magnitude = composer.add_input(BlsScalar::zero());
+            composer.assert_signed_magnitude(sign, magnitude, 8);
+        });
+        assert!(res.is_ok());
+
+        // Negative zero is rejected
+        let res = gadget_tester(|composer| {
+            let sign = composer.add_input(BlsScalar::one());
+            let magnitude = composer.add_input(BlsScalar::zero());
+            composer.assert_signed_magnitude(sign, magnitude, 8);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_less_than() {
+        // 3 < 5
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(3u64));
+            let b = composer.add_input(BlsScalar::from(5u64));
+            let lt = composer.less_than(a, b, 8);
+            composer.constrain_to_constant(lt, BlsScalar::one(), None);
+        });
+        assert!(res.is_ok());
+
+        // 5 = 5, and the largest values of 8 bits
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(5u64));
+            let lt = composer.less_than(a, a, 8);
+            composer.constrain_to_constant(lt, BlsScalar::zero(), None);
+
+            let max = composer.add_input(BlsScalar::from(255u64));
+            let lt = composer.less_than(max, max, 8);
+            composer.constrain_to_constant(lt, BlsScalar::zero(), None);
+        });
+        assert!(res.is_ok());
+
+        // 255 > 0, in both directions
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(255u64));
+            let b = composer.zero_var();
+            let lt = composer.less_than(a, b, 8);
+            composer.constrain_to_constant(lt, BlsScalar::zero(), None);
+            let lt = composer.less_than(b, a, 8);
+            composer.constrain_to_constant(lt, BlsScalar::one(), None);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as the result is wrong
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(7u64));
+            let b = composer.add_input(BlsScalar::from(5u64));
+            let lt = composer.less_than(a, b, 8);
+            composer.constrain_to_constant(lt, BlsScalar::one(), None);
+        });
+        assert!(res.is_err());
+
+        // Should fail as 256 doesn't fit in 8 bits
+        let res = gadget_tester(|composer| {
+            let a = composer.add_input(BlsScalar::from(256u64));
+            let b = composer.zero_var();
+            composer.less_than(a, b, 8);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_divmod_constant() {
+        use crate::constraint_system::zelbet::conv::{
+            scalar_to_u256, u256_to_scalar,
+        };
+        use crate::plookup::table::hash_tables::constants::DECOMPOSITION_S_I;
+        use bigint::U256 as u256;
+
+        let res = gadget_tester(|composer| {
+            let first_base = BlsScalar::from(DECOMPOSITION_S_I[0].0[0]);
+            let moduli = [
+                BlsScalar::one(),
+                BlsScalar::from(2),
+                first_base,
+                BlsScalar::from(1_000_003),
+                BlsScalar::from(u64::MAX),
+            ];
+            let values = [
+                BlsScalar::zero(),
+                BlsScalar::from(692),
+                BlsScalar::from(693),
+                BlsScalar::from(0xdead_beef),
+                -BlsScalar::one(),
+            ];
+
+            for m in moduli.iter() {
+                for x in values.iter() {
+                    let x_value = scalar_to_u256(*x);
+                    let m_value = scalar_to_u256(*m);
+                    let x = composer.add_input(*x);
+                    let (q, r) = composer.divmod_constant(x, *m);
+                    assert_eq!(
+                        composer.variables[&q],
+                        u256_to_scalar(x_value / m_value)
+                    );
+                    assert_eq!(
+                        composer.variables[&r],
+                        u256_to_scalar(x_value % m_value)
+                    );
+                }
+            }
+        });
+        assert!(res.is_ok());
+
+        // Should fail as the remainder isn't below the modulus
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(BlsScalar::from(2000));
+            let q = composer.add_input(BlsScalar::one());
+            let r = composer.add_input(BlsScalar::from(2000 - 693));
+            composer.divmod_gate(x, q, r, u256::from(693));
+        });
+        assert!(res.is_err());
+
+        // Should fail as q * m + r wraps around the modulus, with the
+        // largest quotient and 0 = q_max * 693 + r - p
+        let res = gadget_tester(|composer| {
+            let m = u256::from(693);
+            let q_max = scalar_to_u256(-BlsScalar::one()) / m;
+            let q_max = u256_to_scalar(q_max);
+            let r = -q_max * BlsScalar::from(693);
+            assert_eq!(r, BlsScalar::from(661));
+
+            let x = composer.zero_var();
+            let q = composer.add_input(q_max);
+            let r = composer.add_input(r);
+            composer.divmod_gate(x, q, r, m);
+        });
+        assert!(res.is_err());
+    }
+}
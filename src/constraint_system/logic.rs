@@ -7,6 +7,7 @@
 use crate::bit_iterator::*;
 use crate::constraint_system::StandardComposer;
 use crate::constraint_system::{Variable, WireData};
+use crate::plookup::PlookupTable4Arity;
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
 use dusk_bytes::Serializable;
@@ -322,103 +323,259 @@ impl StandardComposer {
     ) -> Variable {
         self.logic_gate(a, b, num_bits, false)
     }
+
+    /// Adds a plookup gate computing the XOR between `a` and `b`, returning a
+    /// [`Variable`] holding the result. The table built by
+    /// [`PlookupTable4Arity::create_xor_table`] for the number of bits of
+    /// the inputs must be appended to the composer's lookup table.
+    ///
+    /// [`PlookupTable4Arity::create_xor_table`]:
+    /// crate::plookup::PlookupTable4Arity::create_xor_table
+    pub fn xor_lookup(&mut self, a: Variable, b: Variable) -> Variable {
+        self.bitwise_lookup(a, b, |a, b| a ^ b)
+    }
+
+    /// Adds a plookup gate computing the bitwise AND between `a` and `b`,
+    /// returning a [`Variable`] holding the result. The table built by
+    /// [`PlookupTable4Arity::create_and_table`] for the number of bits of
+    /// the inputs must be appended to the composer's lookup table.
+    ///
+    /// [`PlookupTable4Arity::create_and_table`]:
+    /// crate::plookup::PlookupTable4Arity::create_and_table
+    pub fn and_lookup(&mut self, a: Variable, b: Variable) -> Variable {
+        self.bitwise_lookup(a, b, |a, b| a & b)
+    }
+
+    /// Adds a plookup gate per nibble computing the XOR between the nibbles
+    /// of the same position of two decompositions, such as the ones of
+    /// [`StandardComposer::decomposition_gadget`], returning the nibbles of
+    /// the result.
+    ///
+    /// Every output and tag is read from `xor_table`, which must be appended
+    /// to the composer's lookup table, such as the table of
+    /// [`PlookupTable4Arity::create_xor_table`] for the number of bits of the
+    /// largest nibble. Since the resulting nibbles are only looked up, they
+    /// aren't checked to be below the base of their position.
+    ///
+    /// [`PlookupTable4Arity::create_xor_table`]:
+    /// crate::plookup::PlookupTable4Arity::create_xor_table
+    pub fn xor_nibbles(
+        &mut self,
+        a: &[Variable; 27],
+        b: &[Variable; 27],
+        xor_table: &PlookupTable4Arity,
+    ) -> [Variable; 27] {
+        let mut nibbles = [self.zero_var; 27];
+        nibbles.iter_mut().zip(a.iter().zip(b.iter())).for_each(
+            |(nibble, (a, b))| *nibble = self.plookup_query(*a, *b, xor_table),
+        );
+        nibbles
+    }
+
+    fn bitwise_lookup<F: Fn(u64, u64) -> u64>(
+        &mut self,
+        a: Variable,
+        b: Variable,
+        op: F,
+    ) -> Variable {
+        let a_value = self.witness_canonical(a).0[0];
+        let b_value = self.witness_canonical(b).0[0];
+        let c = self.add_input(BlsScalar::from(op(a_value, b_value)));
+
+        self.plookup_gate((a, b, c, None), BlsScalar::zero())
+    }
 }
 
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
     use super::super::helper::*;
+    use crate::constraint_system::StandardComposer;
+    use crate::plookup::PlookupTable4Arity;
     use dusk_bls12_381::BlsScalar;
 
     #[test]
     fn test_logic_xor_and_constraint() {
         // Should pass since the XOR result is correct and the bit-num is even.
-        let res = gadget_tester(
-            |composer| {
-                let witness_a = composer.add_input(BlsScalar::from(500u64));
-                let witness_b = composer.add_input(BlsScalar::from(357u64));
-                let xor_res = composer.xor_gate(witness_a, witness_b, 10);
-                // Check that the XOR result is indeed what we are expecting.
-                composer.constrain_to_constant(
-                    xor_res,
-                    BlsScalar::from(500u64 ^ 357u64),
-                    None,
-                );
-            },
-            200,
-        );
+        let res = gadget_tester(|composer| {
+            let witness_a = composer.add_input(BlsScalar::from(500u64));
+            let witness_b = composer.add_input(BlsScalar::from(357u64));
+            let xor_res = composer.xor_gate(witness_a, witness_b, 10);
+            // Check that the XOR result is indeed what we are expecting.
+            composer.constrain_to_constant(
+                xor_res,
+                BlsScalar::from(500u64 ^ 357u64),
+                None,
+            );
+        });
         assert!(res.is_ok());
 
         // Should pass since the AND result is correct even the bit-num is even.
-        let res = gadget_tester(
+        let res = gadget_tester(|composer| {
+            let witness_a = composer.add_input(BlsScalar::from(469u64));
+            let witness_b = composer.add_input(BlsScalar::from(321u64));
+            let xor_res = composer.and_gate(witness_a, witness_b, 10);
+            // Check that the AND result is indeed what we are expecting.
+            composer.constrain_to_constant(
+                xor_res,
+                BlsScalar::from(469u64 & 321u64),
+                None,
+            );
+        });
+        assert!(res.is_ok());
+
+        // Should not pass since the XOR result is not correct even the bit-num
+        // is even.
+        let res = gadget_tester(|composer| {
+            let witness_a = composer.add_input(BlsScalar::from(139u64));
+            let witness_b = composer.add_input(BlsScalar::from(33u64));
+            let xor_res = composer.xor_gate(witness_a, witness_b, 10);
+            // Check that the XOR result is indeed what we are expecting.
+            composer.constrain_to_constant(
+                xor_res,
+                BlsScalar::from(139u64 & 33u64),
+                None,
+            );
+        });
+        assert!(res.is_err());
+
+        // Should pass even the bitnum is less than the number bit-size
+        let res = gadget_tester(|composer| {
+            let witness_a = composer.add_input(BlsScalar::from(256u64));
+            let witness_b = composer.add_input(BlsScalar::from(235u64));
+            let xor_res = composer.xor_gate(witness_a, witness_b, 2);
+            // Check that the XOR result is indeed what we are expecting.
+            composer.constrain_to_constant(
+                xor_res,
+                BlsScalar::from(256 ^ 235),
+                None,
+            );
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_logical_gate_odd_bit_num() {
+        // Should fail since the bit-num is odd.
+        let _ = gadget_tester(|composer| {
+            let witness_a = composer.add_input(BlsScalar::from(500u64));
+            let witness_b = composer.add_input(BlsScalar::from(499u64));
+            let xor_res = composer.xor_gate(witness_a, witness_b, 9);
+            // Check that the XOR result is indeed what we are expecting.
+            composer.constrain_to_constant(
+                xor_res,
+                BlsScalar::from(7u64),
+                None,
+            );
+        });
+    }
+
+    #[test]
+    fn test_xor_and_lookup() {
+        // Should pass since every result is read from the tables
+        let res = gadget_plookup_tester(
             |composer| {
-                let witness_a = composer.add_input(BlsScalar::from(469u64));
-                let witness_b = composer.add_input(BlsScalar::from(321u64));
-                let xor_res = composer.and_gate(witness_a, witness_b, 10);
-                // Check that the AND result is indeed what we are expecting.
-                composer.constrain_to_constant(
-                    xor_res,
-                    BlsScalar::from(469u64 & 321u64),
-                    None,
-                );
+                (0..16u64).for_each(|a| {
+                    (0..16u64).for_each(|b| {
+                        let a_var = composer.add_input(BlsScalar::from(a));
+                        let b_var = composer.add_input(BlsScalar::from(b));
+                        let xor = composer.xor_lookup(a_var, b_var);
+                        assert_eq!(
+                            composer.variables[&xor],
+                            BlsScalar::from(a ^ b)
+                        );
+                    })
+                });
             },
-            200,
+            PlookupTable4Arity::create_xor_table(4).unwrap(),
         );
         assert!(res.is_ok());
 
-        // Should not pass since the XOR result is not correct even the bit-num
-        // is even.
-        let res = gadget_tester(
+        let res = gadget_plookup_tester(
             |composer| {
-                let witness_a = composer.add_input(BlsScalar::from(139u64));
-                let witness_b = composer.add_input(BlsScalar::from(33u64));
-                let xor_res = composer.xor_gate(witness_a, witness_b, 10);
-                // Check that the XOR result is indeed what we are expecting.
-                composer.constrain_to_constant(
-                    xor_res,
-                    BlsScalar::from(139u64 & 33u64),
-                    None,
-                );
+                (0..16u64).for_each(|a| {
+                    (0..16u64).for_each(|b| {
+                        let a_var = composer.add_input(BlsScalar::from(a));
+                        let b_var = composer.add_input(BlsScalar::from(b));
+                        let and = composer.and_lookup(a_var, b_var);
+                        assert_eq!(
+                            composer.variables[&and],
+                            BlsScalar::from(a & b)
+                        );
+                    })
+                });
             },
-            200,
+            PlookupTable4Arity::create_and_table(4).unwrap(),
         );
-        assert!(res.is_err());
+        assert!(res.is_ok());
 
-        // Should pass even the bitnum is less than the number bit-size
-        let res = gadget_tester(
+        // Should fail since the output isn't the XOR of the inputs
+        let res = gadget_plookup_tester(
             |composer| {
-                let witness_a = composer.add_input(BlsScalar::from(256u64));
-                let witness_b = composer.add_input(BlsScalar::from(235u64));
-                let xor_res = composer.xor_gate(witness_a, witness_b, 2);
-                // Check that the XOR result is indeed what we are expecting.
-                composer.constrain_to_constant(
-                    xor_res,
-                    BlsScalar::from(256 ^ 235),
-                    None,
-                );
+                let a = composer.add_input(BlsScalar::from(5));
+                let b = composer.add_input(BlsScalar::from(9));
+                let wrong = composer.add_input(BlsScalar::from(5 & 9));
+                composer.plookup_gate((a, b, wrong, None), BlsScalar::zero());
             },
-            200,
+            PlookupTable4Arity::create_xor_table(4).unwrap(),
         );
         assert!(res.is_err());
     }
 
     #[test]
-    #[should_panic]
-    fn test_logical_gate_odd_bit_num() {
-        // Should fail since the bit-num is odd.
-        let _ = gadget_tester(
+    fn test_xor_nibbles() {
+        fn nibbles(seed: u64) -> [u64; 27] {
+            let mut nibbles = [0u64; 27];
+            nibbles
+                .iter_mut()
+                .enumerate()
+                .for_each(|(k, nibble)| *nibble = (k as u64 * seed + 3) % 16);
+            nibbles
+        }
+
+        fn xor_gadget(composer: &mut StandardComposer, b_seed: u64) {
+            let table = PlookupTable4Arity::create_xor_table(4).unwrap();
+            let mut a = [composer.zero_var; 27];
+            let mut b = [composer.zero_var; 27];
+            a.iter_mut()
+                .zip(b.iter_mut())
+                .zip(nibbles(5).iter().zip(nibbles(b_seed).iter()))
+                .for_each(|((a, b), (a_value, b_value))| {
+                    *a = composer.add_input(BlsScalar::from(*a_value));
+                    *b = composer.add_input(BlsScalar::from(*b_value));
+                });
+
+            let xor = composer.xor_nibbles(&a, &b, &table);
+            xor.iter()
+                .zip(nibbles(5).iter().zip(nibbles(b_seed).iter()))
+                .for_each(|(nibble, (a, b))| {
+                    assert_eq!(
+                        composer.variables[nibble],
+                        BlsScalar::from(a ^ b)
+                    )
+                });
+        }
+
+        // Should pass as every nibble is in the table
+        let res = gadget_plookup_tester(
+            |composer| xor_gadget(composer, 7),
+            PlookupTable4Arity::create_xor_table(4).unwrap(),
+        );
+        assert!(res.is_ok());
+
+        // Should fail as a nibble is out of the table
+        let res = gadget_plookup_tester(
             |composer| {
-                let witness_a = composer.add_input(BlsScalar::from(500u64));
-                let witness_b = composer.add_input(BlsScalar::from(499u64));
-                let xor_res = composer.xor_gate(witness_a, witness_b, 9);
-                // Check that the XOR result is indeed what we are expecting.
-                composer.constrain_to_constant(
-                    xor_res,
-                    BlsScalar::from(7u64),
-                    None,
-                );
+                xor_gadget(composer, 7);
+                let table = PlookupTable4Arity::create_xor_table(4).unwrap();
+                let mut a = [composer.zero_var; 27];
+                a[3] = composer.add_input(BlsScalar::from(16));
+                let b = [composer.zero_var; 27];
+                composer.xor_nibbles(&a, &b, &table);
             },
-            200,
+            PlookupTable4Arity::create_xor_table(4).unwrap(),
         );
+        assert!(res.is_err());
     }
 }
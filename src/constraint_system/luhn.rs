@@ -0,0 +1,138 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::StandardComposer;
+use crate::constraint_system::Variable;
+use dusk_bls12_381::BlsScalar;
+
+impl StandardComposer {
+    /// Adds the constraints that check that `digits` form a decimal number
+    /// satisfying the Luhn (mod 10) checksum.
+    ///
+    /// The digits are ordered as they are written, most significant first,
+    /// so the last [`Variable`] of `digits` is the check digit. Starting from
+    /// the check digit and moving left, every second digit is doubled
+    /// (subtracting 9 whenever the result exceeds 9) and the sum of all the
+    /// resulting values must be a multiple of 10.
+    ///
+    /// Every digit is also range-constrained to `0..=9`.
+    pub fn assert_luhn_valid(&mut self, digits: &[Variable]) {
+        let mut sum = self.zero_var;
+        for (i, digit) in digits.iter().rev().enumerate() {
+            self.decimal_digit_gate(*digit);
+
+            let term = if i % 2 == 1 {
+                self.luhn_double(*digit)
+            } else {
+                *digit
+            };
+
+            sum = self.add(
+                (BlsScalar::one(), sum),
+                (BlsScalar::one(), term),
+                BlsScalar::zero(),
+                None,
+            );
+        }
+
+        // The sum is at most 9 * digits.len(), so bounding the quotient
+        // guarantees that `sum = 10 * quotient` holds over the integers and
+        // not only modulo the field characteristic
+        let sum_value = self.witness_canonical(sum).0[0];
+        let quotient = self.add_input(BlsScalar::from(sum_value / 10));
+        let max_quotient = (9 * digits.len() / 10) as u64;
+        let num_bits = (64 - max_quotient.leading_zeros()) as usize;
+        self.range_gate(quotient, (num_bits + num_bits % 2).max(2));
+
+        self.add_gate(
+            sum,
+            quotient,
+            self.zero_var,
+            BlsScalar::one(),
+            -BlsScalar::from(10),
+            BlsScalar::zero(),
+            BlsScalar::zero(),
+            None,
+        );
+    }
+
+    /// Constrains `digit` to lie in `0..=9` by range checking both `digit`
+    /// and `digit + 6` to 4 bits.
+    fn decimal_digit_gate(&mut self, digit: Variable) {
+        self.range_gate(digit, 4);
+        let shifted = self.add(
+            (BlsScalar::one(), digit),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::from(6),
+            None,
+        );
+        self.range_gate(shifted, 4);
+    }
+
+    /// Returns the Luhn doubling of a decimal digit, `2 * digit` if the
+    /// digit is smaller than 5 and `2 * digit - 9` otherwise.
+    fn luhn_double(&mut self, digit: Variable) -> Variable {
+        let is_large = self.witness_canonical(digit).0[0] >= 5;
+        let is_large = self.add_input(BlsScalar::from(is_large as u64));
+        self.boolean_gate(is_large);
+
+        // `digit - 5 * is_large` must lie in 0..=4, which forces `is_large`
+        // to be set exactly when the digit is at least 5
+        let low = self.add(
+            (BlsScalar::one(), digit),
+            (-BlsScalar::from(5), is_large),
+            BlsScalar::zero(),
+            None,
+        );
+        self.range_gate(low, 4);
+        let shifted = self.add(
+            (BlsScalar::one(), low),
+            (BlsScalar::zero(), self.zero_var),
+            BlsScalar::from(11),
+            None,
+        );
+        self.range_gate(shifted, 4);
+
+        self.add(
+            (BlsScalar::from(2), digit),
+            (-BlsScalar::from(9), is_large),
+            BlsScalar::zero(),
+            None,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use alloc::vec::Vec;
+    use dusk_bls12_381::BlsScalar;
+
+    #[test]
+    fn test_luhn_valid() {
+        let res = gadget_tester(|composer| {
+            let digits: Vec<_> = [7u64, 9, 9, 2, 7, 3, 9, 8, 7, 1, 3]
+                .iter()
+                .map(|d| composer.add_input(BlsScalar::from(*d)))
+                .collect();
+            composer.assert_luhn_valid(&digits);
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_luhn_corrupted_digit() {
+        let res = gadget_tester(|composer| {
+            let digits: Vec<_> = [7u64, 9, 9, 2, 7, 3, 9, 8, 7, 1, 4]
+                .iter()
+                .map(|d| composer.add_input(BlsScalar::from(*d)))
+                .collect();
+            composer.assert_luhn_valid(&digits);
+        });
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,296 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::StandardComposer;
+use crate::constraint_system::Variable;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+
+impl StandardComposer {
+    /// Adds the constraints that check that `leaf` belongs to the Merkle
+    /// tree of root `root`, given the authentication `path` from the leaf up
+    /// to the root.
+    ///
+    /// Every element of `path` is a sibling along with a direction bit,
+    /// which is `1` if the sibling is the left child, and `0` if it's the
+    /// right one. Each node is the
+    /// [`StandardComposer::zelbet_sponge_hash`] of its left and right
+    /// children, so the tree must be built out of circuit with the same
+    /// sponge. The direction bits are constrained to be boolean, and an
+    /// empty path checks that `leaf` is the root.
+    ///
+    /// The lookups of the hash need the table of
+    /// [`PlookupTable4Arity::create_hash_table`] to be appended to the
+    /// composer.
+    ///
+    /// [`PlookupTable4Arity::create_hash_table`]:
+    /// crate::plookup::PlookupTable4Arity::create_hash_table
+    pub fn merkle_verify(
+        &mut self,
+        leaf: Variable,
+        path: &[(Variable, Variable)],
+        root: Variable,
+    ) {
+        let computed = path.iter().fold(leaf, |node, (sibling, bit)| {
+            self.merkle_parent(node, *sibling, *bit)
+        });
+
+        self.assert_equal(computed, root);
+    }
+
+    /// Adds the constraints that check that every leaf of `leaves` belongs
+    /// to the Merkle tree of root `root`, given its authentication path, as
+    /// [`StandardComposer::merkle_verify`] does for each of them.
+    ///
+    /// The paths of leaves in the same subtree share their elements above
+    /// it. When the last elements of a path are the same [`Variable`]s as
+    /// the last ones of the path of a previous leaf, only the nodes below
+    /// them are hashed, and the node they start from is constrained to be
+    /// the one of the previous leaf, so the hashes of the common part are
+    /// computed once. Paths given with distinct [`Variable`]s are verified
+    /// independently up to the root.
+    ///
+    /// The lookups of the hash need the table of
+    /// [`PlookupTable4Arity::create_hash_table`] to be appended to the
+    /// composer, once for the whole batch.
+    ///
+    /// [`PlookupTable4Arity::create_hash_table`]:
+    /// crate::plookup::PlookupTable4Arity::create_hash_table
+    pub fn merkle_verify_batch(
+        &mut self,
+        leaves: &[(Variable, Vec<(Variable, Variable)>)],
+        root: Variable,
+    ) {
+        // Nodes from every leaf up to the root, leaf included
+        let mut nodes: Vec<Vec<Variable>> = Vec::with_capacity(leaves.len());
+
+        leaves.iter().enumerate().for_each(|(i, (leaf, path))| {
+            // Previous path sharing the most elements with this one
+            let shared = leaves[..i]
+                .iter()
+                .map(|(_, other)| {
+                    path.iter()
+                        .rev()
+                        .zip(other.iter().rev())
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                })
+                .enumerate()
+                .max_by_key(|(_, shared)| *shared)
+                .filter(|(_, shared)| *shared > 0);
+
+            let mut node = *leaf;
+            let mut leaf_nodes = vec![node];
+            let unshared = path.len() - shared.map_or(0, |(_, shared)| shared);
+            path[..unshared].iter().for_each(|(sibling, bit)| {
+                node = self.merkle_parent(node, *sibling, *bit);
+                leaf_nodes.push(node);
+            });
+
+            match shared {
+                Some((j, shared)) => {
+                    let other = &nodes[j];
+                    let start = other.len() - 1 - shared;
+                    self.assert_equal(node, other[start]);
+                    leaf_nodes.extend_from_slice(&other[start + 1..]);
+                }
+                None => self.assert_equal(node, root),
+            }
+            nodes.push(leaf_nodes);
+        });
+    }
+
+    /// Computes the parent of `node` and `sibling`, `sibling` being the
+    /// left child if `bit` is `1` and the right one if it's `0`.
+    fn merkle_parent(
+        &mut self,
+        node: Variable,
+        sibling: Variable,
+        bit: Variable,
+    ) -> Variable {
+        // The bit is constrained to be boolean by the selection
+        let left = self.conditional_select(bit, sibling, node);
+        // node + sibling - left
+        let right = self.big_add(
+            (BlsScalar::one(), node),
+            (BlsScalar::one(), sibling),
+            Some((-BlsScalar::one(), left)),
+            BlsScalar::zero(),
+            None,
+        );
+
+        self.zelbet_sponge_hash(&[left, right])
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::super::helper::*;
+    use super::*;
+    use crate::plookup::PlookupTable4Arity;
+
+    /// Out of circuit hash of the children of a node
+    fn hash_pair(left: BlsScalar, right: BlsScalar) -> BlsScalar {
+        let mut composer = StandardComposer::new();
+        let left = composer.add_input(left);
+        let right = composer.add_input(right);
+        let digest = composer.zelbet_sponge_hash(&[left, right]);
+        composer.variables[&digest]
+    }
+
+    /// Returns the root of the tree of depth 3 over the leaves `0..8`, and
+    /// the path of the leaf `index`
+    fn depth_3_tree(index: usize) -> (BlsScalar, Vec<(BlsScalar, u64)>) {
+        let mut level: Vec<BlsScalar> =
+            (0..8u64).map(|leaf| BlsScalar::from(leaf + 100)).collect();
+        let mut index = index;
+        let mut path = Vec::new();
+
+        while level.len() > 1 {
+            path.push((level[index ^ 1], (index & 1) as u64));
+            level = level
+                .chunks(2)
+                .map(|children| hash_pair(children[0], children[1]))
+                .collect();
+            index >>= 1;
+        }
+
+        (level[0], path)
+    }
+
+    fn merkle_gadget(
+        composer: &mut StandardComposer,
+        leaf: BlsScalar,
+        path: &[(BlsScalar, u64)],
+        root: BlsScalar,
+    ) {
+        let leaf = composer.add_input(leaf);
+        let path: Vec<_> = path
+            .iter()
+            .map(|(sibling, bit)| {
+                (
+                    composer.add_input(*sibling),
+                    composer.add_input(BlsScalar::from(*bit)),
+                )
+            })
+            .collect();
+        let root = composer.add_input(root);
+        composer.merkle_verify(leaf, &path, root);
+    }
+
+    /// Verifies the leaves `4` and `5` of the tree, sharing the path above
+    /// their parent, along with the leaf `0` given a path of its own
+    fn merkle_batch_gadget(composer: &mut StandardComposer, leaf_4: u64) {
+        let (root, path) = depth_3_tree(5);
+        let leaf_5 = composer.add_input(BlsScalar::from(105));
+        let path_5: Vec<_> = path
+            .iter()
+            .map(|(sibling, bit)| {
+                (
+                    composer.add_input(*sibling),
+                    composer.add_input(BlsScalar::from(*bit)),
+                )
+            })
+            .collect();
+
+        // The leaf 4 is the left sibling of the leaf 5
+        let leaf_4 = composer.add_input(BlsScalar::from(leaf_4));
+        let mut path_4 = path_5.clone();
+        path_4[0] = (leaf_5, composer.zero_var());
+
+        let (_, path) = depth_3_tree(0);
+        let leaf_0 = composer.add_input(BlsScalar::from(100));
+        let path_0: Vec<_> = path
+            .iter()
+            .map(|(sibling, bit)| {
+                (
+                    composer.add_input(*sibling),
+                    composer.add_input(BlsScalar::from(*bit)),
+                )
+            })
+            .collect();
+
+        let root = composer.add_input(root);
+        composer.merkle_verify_batch(
+            &[(leaf_5, path_5), (leaf_4, path_4), (leaf_0, path_0)],
+            root,
+        );
+    }
+
+    #[test]
+    fn test_merkle_verify_batch() {
+        // Should pass as every leaf belongs to the tree
+        let res = gadget_plookup_tester(
+            |composer| merkle_batch_gadget(composer, 104),
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+
+        // Should fail as the leaf 4 is tampered with
+        let res = gadget_plookup_tester(
+            |composer| merkle_batch_gadget(composer, 103),
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+
+        // The leaf 4 only hashes its parent, instead of its whole path
+        let mut batch = StandardComposer::new();
+        merkle_batch_gadget(&mut batch, 104);
+        let mut independent = StandardComposer::new();
+        (0..3).for_each(|_| {
+            let (root, path) = depth_3_tree(5);
+            merkle_gadget(&mut independent, BlsScalar::from(105), &path, root);
+        });
+        assert!(batch.circuit_size() < independent.circuit_size());
+    }
+
+    #[test]
+    fn test_merkle_verify() {
+        // Should pass as the leaf 5 is a right, left then right child
+        let res = gadget_plookup_tester(
+            |composer| {
+                let (root, path) = depth_3_tree(5);
+                assert_eq!(path.iter().map(|(_, bit)| *bit).sum::<u64>(), 2);
+                merkle_gadget(composer, BlsScalar::from(105), &path, root);
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+
+        // Should fail as the leaf 5 isn't at the position 4
+        let res = gadget_plookup_tester(
+            |composer| {
+                let (root, mut path) = depth_3_tree(5);
+                path[0].1 = 0;
+                merkle_gadget(composer, BlsScalar::from(105), &path, root);
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+
+        // Should fail as the leaf doesn't belong to the tree
+        let res = gadget_plookup_tester(
+            |composer| {
+                let (root, path) = depth_3_tree(5);
+                merkle_gadget(composer, BlsScalar::from(104), &path, root);
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+
+        // Should fail as a direction bit isn't boolean
+        let res = gadget_plookup_tester(
+            |composer| {
+                let (root, mut path) = depth_3_tree(5);
+                path[1].1 = 2;
+                merkle_gadget(composer, BlsScalar::from(105), &path, root);
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,223 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Tooling to shrink a circuit whose proofs don't verify into a minimal
+//! reproduction, so it can be attached to a bug report.
+
+use crate::commitment_scheme::kzg10::PublicParameters;
+use crate::constraint_system::{StandardComposer, Variable};
+use crate::proof_system::{Prover, Verifier};
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use rand_core::OsRng;
+
+/// Builds a circuit with `builder` and removes as many of its gates as
+/// possible while the proofs generated for it still fail to verify against
+/// `public_inputs`, returning the [`StandardComposer`] holding the remaining
+/// gates.
+///
+/// `public_inputs` is the dense vector of public inputs of the full circuit,
+/// as given to the [`Verifier`]. The gates are removed by bisection, then one
+/// at a time until none can be removed, so the resulting circuit is minimal
+/// in the sense that removing any single one of its gates makes its proofs
+/// verify. The variables and the lookup table of
+/// the full circuit are kept as they are.
+///
+/// If the proofs of the full circuit verify, it is returned unchanged.
+///
+/// # Note
+/// Every candidate circuit is proved and verified, which is slow, and gates
+/// reading the wires of the next gate, such as the range gates, may make a
+/// candidate fail for a different reason than the full circuit.
+pub fn minimize_failing_circuit<F>(
+    builder: F,
+    public_inputs: &[BlsScalar],
+) -> StandardComposer
+where
+    F: Fn(&mut StandardComposer),
+{
+    let mut circuit = StandardComposer::new();
+    let first_gate = circuit.circuit_size();
+    builder(&mut circuit);
+
+    let n = circuit.next_power_of_two_size();
+    let public_parameters = match PublicParameters::setup(4 * n, &mut OsRng) {
+        Ok(public_parameters) => public_parameters,
+        Err(_) => return circuit,
+    };
+
+    let fails = |gates: &[usize]| {
+        let mut prover = Prover::new(b"minimize");
+        *prover.mut_cs() = circuit.gate_subset(gates, public_inputs);
+        let mut verifier = Verifier::new(b"minimize");
+        *verifier.mut_cs() = circuit.gate_subset(gates, public_inputs);
+
+        let result = public_parameters.trim(2 * n).and_then(|(ck, vk)| {
+            let proof = prover.prove(&ck)?;
+            verifier.preprocess(&ck)?;
+            let subset_inputs = verifier.cs.construct_dense_pi_vec();
+            verifier.verify(&proof, &vk, &subset_inputs)
+        });
+
+        result.is_err()
+    };
+
+    let mut gates: Vec<usize> = (first_gate..circuit.circuit_size()).collect();
+    if !fails(&gates) {
+        return circuit;
+    }
+
+    // Try to remove chunks of gates of decreasing size, keeping every
+    // removal after which the circuit still fails
+    let mut chunk = core::cmp::max(gates.len() / 2, 1);
+    loop {
+        let mut removed = false;
+        let mut start = 0;
+        while start < gates.len() {
+            let end = core::cmp::min(start + chunk, gates.len());
+            let candidate: Vec<usize> = gates[..start]
+                .iter()
+                .chain(gates[end..].iter())
+                .copied()
+                .collect();
+
+            match fails(&candidate) {
+                true => {
+                    gates = candidate;
+                    removed = true;
+                }
+                false => start = end,
+            }
+        }
+
+        // A removal may let a gate kept earlier in the pass be removed, so
+        // single gates are tried again until a pass removes none
+        if chunk == 1 && !removed {
+            break;
+        }
+        chunk = core::cmp::max(chunk / 2, 1);
+    }
+
+    circuit.gate_subset(&gates, public_inputs)
+}
+
+impl StandardComposer {
+    /// Returns a [`StandardComposer`] holding only the given `gates` of this
+    /// one, on top of the gates every composer starts with, and the public
+    /// inputs of `public_inputs` at the positions of the kept gates.
+    fn gate_subset(
+        &self,
+        gates: &[usize],
+        public_inputs: &[BlsScalar],
+    ) -> StandardComposer {
+        let mut subset = StandardComposer::new();
+
+        // Variables are allocated sequentially, so adding the remaining ones
+        // in order preserves their indices
+        (subset.variables.len()..self.variables.len()).for_each(|i| {
+            subset.add_input(self.variables[&Variable(i)]);
+        });
+        subset.lookup_table = self.lookup_table.clone();
+        subset.shared_lookup_tables = self.shared_lookup_tables.clone();
+
+        gates.iter().for_each(|&gate| {
+            subset.q_m.push(self.q_m[gate]);
+            subset.q_l.push(self.q_l[gate]);
+            subset.q_r.push(self.q_r[gate]);
+            subset.q_o.push(self.q_o[gate]);
+            subset.q_4.push(self.q_4[gate]);
+            subset.q_c.push(self.q_c[gate]);
+            subset.q_arith.push(self.q_arith[gate]);
+            subset.q_range.push(self.q_range[gate]);
+            subset.q_logic.push(self.q_logic[gate]);
+            subset.q_fixed_group_add.push(self.q_fixed_group_add[gate]);
+            subset
+                .q_variable_group_add
+                .push(self.q_variable_group_add[gate]);
+            subset.q_lookup.push(self.q_lookup[gate]);
+
+            subset.w_l.push(self.w_l[gate]);
+            subset.w_r.push(self.w_r[gate]);
+            subset.w_o.push(self.w_o[gate]);
+            subset.w_4.push(self.w_4[gate]);
+            subset.perm.add_variables_to_map(
+                self.w_l[gate],
+                self.w_r[gate],
+                self.w_o[gate],
+                self.w_4[gate],
+                subset.n,
+            );
+
+            match public_inputs.get(gate) {
+                Some(pi) if pi != &BlsScalar::zero() => {
+                    subset.public_inputs_sparse_store.insert(subset.n, *pi);
+                }
+                _ => (),
+            }
+
+            subset.n += 1;
+        });
+
+        subset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimize_failing_circuit() {
+        let builder = |composer: &mut StandardComposer| {
+            let one = composer.add_input(BlsScalar::one());
+            let two = composer.add_input(BlsScalar::from(2u64));
+            let four = composer.add_input(BlsScalar::from(4u64));
+
+            (0..6).for_each(|_| {
+                composer.add(
+                    (BlsScalar::one(), one),
+                    (BlsScalar::one(), two),
+                    BlsScalar::zero(),
+                    None,
+                );
+            });
+
+            // 1 + 2 != 4
+            composer.add_gate(
+                one,
+                two,
+                four,
+                BlsScalar::one(),
+                BlsScalar::one(),
+                -BlsScalar::one(),
+                BlsScalar::zero(),
+                None,
+            );
+
+            (0..6).for_each(|_| {
+                composer.mul(
+                    BlsScalar::one(),
+                    two,
+                    two,
+                    BlsScalar::zero(),
+                    None,
+                );
+            });
+        };
+
+        let mut circuit = StandardComposer::new();
+        let first_gate = circuit.circuit_size();
+        builder(&mut circuit);
+        let public_inputs = circuit.construct_dense_pi_vec();
+
+        let minimized = minimize_failing_circuit(builder, &public_inputs);
+
+        assert_eq!(minimized.circuit_size(), first_gate + 1);
+        assert_eq!(minimized.w_l[first_gate], circuit.w_l[first_gate + 6]);
+        assert_eq!(minimized.w_r[first_gate], circuit.w_r[first_gate + 6]);
+        assert_eq!(minimized.w_o[first_gate], circuit.w_o[first_gate + 6]);
+    }
+}
@@ -12,24 +12,48 @@ pub(crate) mod composer;
 pub(crate) mod divide_w_recip;
 pub(crate) mod variable;
 
+/// Hash-based accumulator gadget
+mod accumulator;
 /// Simple Arithmetic gates
 mod arithmetic;
 /// Boolean gate
 mod boolean;
+/// Graphviz export of circuits
+mod dot;
 /// Elliptic Curve Crypto gates
 pub mod ecc;
+/// Arithmetic expression builder
+mod expression;
 
 #[cfg(feature = "std")]
 #[cfg(test)]
 pub(crate) mod helper;
+/// Integer arithmetic gadgets
+mod integer;
 /// XOR and AND gates
 pub mod logic;
+/// Luhn checksum gadget
+mod luhn;
+/// Merkle tree gadget
+mod merkle;
+/// Failing circuit minimization
+#[cfg(all(feature = "trace", feature = "std"))]
+mod minimize;
 /// Range gate
 pub mod range;
+/// Property testing of gadgets
+#[cfg(feature = "std")]
+pub mod testing;
 /// Zelbet Functionality
 pub mod zelbet;
 
-pub use composer::StandardComposer;
+pub use composer::{
+    CircuitReport, ComposerSnapshot, SelectorPolys, StandardComposer,
+    WitnessAssignment,
+};
 pub use ecc::Point;
+pub use expression::Expression;
+#[cfg(all(feature = "trace", feature = "std"))]
+pub use minimize::minimize_failing_circuit;
 pub use variable::Variable;
 pub(crate) use variable::WireData;
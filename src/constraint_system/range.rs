@@ -192,61 +192,164 @@ impl StandardComposer {
         self.assert_equal(accumulators[last_accumulator], witness);
         accumulators[last_accumulator] = witness;
     }
+
+    /// Constrains `x` to be in the range \[0, 2^bits\) with a single lookup
+    /// in the table built by [`PlookupTable4Arity::create_range_table`],
+    /// which must be appended to the composer's lookup table.
+    ///
+    /// [`PlookupTable4Arity::create_range_table`]:
+    /// crate::plookup::PlookupTable4Arity::create_range_table
+    pub fn range_check_lookup(&mut self, x: Variable, bits: u32) {
+        let bits = self.add_constant_var(BlsScalar::from(bits as u64));
+        self.plookup_gate((x, self.zero_var, x, Some(bits)), BlsScalar::zero());
+    }
+
+    /// Constrains `value` to be a valid unsigned fixed-point number with
+    /// `int_bits` integer bits and `frac_bits` fractional bits.
+    ///
+    /// The value is expected to be scaled by `2^frac_bits`, ie. the real
+    /// number `r` is represented by the integer `r * 2^frac_bits`, so this
+    /// gadget checks that `value` lies in the range
+    /// \[0, 2^(int_bits + frac_bits)\).
+    ///
+    /// Unlike [`StandardComposer::range_gate`], the total number of bits is
+    /// not required to be even.
+    pub fn assert_fixed_point(
+        &mut self,
+        value: Variable,
+        int_bits: usize,
+        frac_bits: usize,
+    ) {
+        let num_bits = int_bits + frac_bits;
+        if num_bits % 2 == 0 {
+            self.range_gate(value, num_bits);
+        } else {
+            // Knowing that `value < 2^(num_bits + 1)`, we have
+            // `value < 2^num_bits` if and only if
+            // `value + 2^num_bits < 2^(num_bits + 1)`
+            self.range_gate(value, num_bits + 1);
+            let shift = BlsScalar::from(2).pow(&[num_bits as u64, 0, 0, 0]);
+            let shifted = self.add(
+                (BlsScalar::one(), value),
+                (BlsScalar::zero(), self.zero_var),
+                shift,
+                None,
+            );
+            self.range_gate(shifted, num_bits + 1);
+        }
+    }
 }
 
 #[cfg(feature = "std")]
 #[cfg(test)]
 mod tests {
     use super::super::helper::*;
+    use crate::constraint_system::StandardComposer;
+    use crate::plookup::PlookupTable4Arity;
     use dusk_bls12_381::BlsScalar;
 
     #[test]
     fn test_range_constraint() {
         // Should fail as the number is not 32 bits
-        let res = gadget_tester(
-            |composer| {
-                let witness = composer
-                    .add_input(BlsScalar::from((u32::max_value() as u64) + 1));
-                composer.range_gate(witness, 32);
-            },
-            200,
-        );
+        let res = gadget_tester(|composer| {
+            let witness = composer
+                .add_input(BlsScalar::from((u32::max_value() as u64) + 1));
+            composer.range_gate(witness, 32);
+        });
         assert!(res.is_err());
 
         // Should fail as number is greater than 32 bits
-        let res = gadget_tester(
-            |composer| {
-                let witness =
-                    composer.add_input(BlsScalar::from(u64::max_value()));
-                composer.range_gate(witness, 32);
-            },
-            200,
-        );
+        let res = gadget_tester(|composer| {
+            let witness = composer.add_input(BlsScalar::from(u64::max_value()));
+            composer.range_gate(witness, 32);
+        });
         assert!(res.is_err());
 
         // Should pass as the number is within 34 bits
-        let res = gadget_tester(
+        let res = gadget_tester(|composer| {
+            let witness = composer.add_input(BlsScalar::from(2u64.pow(34) - 1));
+            composer.range_gate(witness, 34);
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        // Should pass as it is the largest value with 8 integer bits and 8
+        // fractional bits
+        let res = gadget_tester(|composer| {
+            let value = composer.add_input(BlsScalar::from((1 << 16) - 1));
+            composer.assert_fixed_point(value, 8, 8);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as it needs 17 bits
+        let res = gadget_tester(|composer| {
+            let value = composer.add_input(BlsScalar::from(1 << 16));
+            composer.assert_fixed_point(value, 8, 8);
+        });
+        assert!(res.is_err());
+
+        // Should pass as it fits in 5 integer bits and 4 fractional bits
+        let res = gadget_tester(|composer| {
+            let value = composer.add_input(BlsScalar::from((1 << 9) - 1));
+            composer.assert_fixed_point(value, 5, 4);
+        });
+        assert!(res.is_ok());
+
+        // Should fail as it needs 10 bits
+        let res = gadget_tester(|composer| {
+            let value = composer.add_input(BlsScalar::from(1 << 9));
+            composer.assert_fixed_point(value, 5, 4);
+        });
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_range_check_lookup() {
+        let table = PlookupTable4Arity::create_range_table(8).unwrap();
+
+        // Should pass as 255 fits in 8 bits
+        let res = gadget_plookup_tester(
             |composer| {
-                let witness =
-                    composer.add_input(BlsScalar::from(2u64.pow(34) - 1));
-                composer.range_gate(witness, 34);
+                let zero = composer.add_input(BlsScalar::zero());
+                let max = composer.add_input(BlsScalar::from(255));
+                composer.range_check_lookup(zero, 8);
+                composer.range_check_lookup(max, 8);
             },
-            200,
+            table.clone(),
         );
         assert!(res.is_ok());
+
+        // Should fail as 256 needs 9 bits
+        let res = gadget_plookup_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(256));
+                composer.range_check_lookup(x, 8);
+            },
+            table.clone(),
+        );
+        assert!(res.is_err());
+
+        // The bit length is a constant shared by the checks, so each one only
+        // adds its lookup gate
+        let mut composer = StandardComposer::new();
+        composer.append_lookup_table(&table);
+        let x = composer.add_input(BlsScalar::from(3));
+        let gates = composer.circuit_size();
+        composer.range_check_lookup(x, 8);
+        composer.range_check_lookup(x, 8);
+        assert_gate_count(&composer, gates + 3);
     }
 
     #[test]
     #[should_panic]
     fn test_odd_bit_range() {
         // Should fail as the number we we need a even number of bits
-        let _ok = gadget_tester(
-            |composer| {
-                let witness = composer
-                    .add_input(BlsScalar::from(u32::max_value() as u64));
-                composer.range_gate(witness, 33);
-            },
-            200,
-        );
+        let _ok = gadget_tester(|composer| {
+            let witness =
+                composer.add_input(BlsScalar::from(u32::max_value() as u64));
+            composer.range_gate(witness, 33);
+        });
     }
 }
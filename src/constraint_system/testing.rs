@@ -0,0 +1,177 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Property testing of gadgets over randomized witnesses.
+
+use super::StandardComposer;
+use crate::commitment_scheme::kzg10::PublicParameters;
+use crate::error::Error;
+use crate::proof_system::{Prover, Verifier};
+use rand_core::{impls, OsRng, RngCore};
+
+/// Deterministic RNG handed to the witness generator of
+/// [`prop_gadget_tester`], so that a failing case can be replayed from its
+/// seed.
+#[derive(Debug, Clone)]
+pub struct PropRng(u64);
+
+impl PropRng {
+    /// Creates the RNG of the case `seed`.
+    pub fn new(seed: u64) -> Self {
+        PropRng(seed)
+    }
+}
+
+impl RngCore for PropRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    // SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(
+        &mut self,
+        dest: &mut [u8],
+    ) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// First case of [`prop_gadget_tester`] whose proof didn't verify.
+#[derive(Debug)]
+pub struct PropFailure {
+    /// Seed of the [`PropRng`] the witness was generated with
+    pub seed: u64,
+    /// Error returned when proving or verifying the case
+    pub error: Error,
+}
+
+/// Runs `gadget` end-to-end over `cases` randomized witnesses, proving and
+/// verifying each of them, and returns the first failing case.
+///
+/// The witness of the case `seed` is generated by `witness` from
+/// [`PropRng::new(seed)`](PropRng::new), for every `seed` in `0..cases`,
+/// and then passed to `gadget` to build both the prover and the verifier
+/// circuits. `n` is a hint of the size of the circuits, including any
+/// lookup table appended by `gadget`, and bounds the public parameters.
+pub fn prop_gadget_tester<T, W, G>(
+    witness: W,
+    gadget: G,
+    n: usize,
+    cases: u64,
+) -> Result<(), PropFailure>
+where
+    W: Fn(&mut PropRng) -> T,
+    G: Fn(&mut StandardComposer, &T),
+{
+    let public_parameters = PublicParameters::setup(2 * n, &mut OsRng)
+        .map_err(|error| PropFailure { seed: 0, error })?;
+
+    (0..cases).try_for_each(|seed| {
+        let witness = witness(&mut PropRng::new(seed));
+        prove_and_verify(&public_parameters, |composer| {
+            gadget(composer, &witness)
+        })
+        .map_err(|error| PropFailure { seed, error })
+    })
+}
+
+/// Proves and verifies the circuit built by `gadget`.
+fn prove_and_verify<G>(
+    public_parameters: &PublicParameters,
+    gadget: G,
+) -> Result<(), Error>
+where
+    G: Fn(&mut StandardComposer),
+{
+    let mut prover = Prover::new(b"prop");
+    gadget(prover.mut_cs());
+    let (ck, _) =
+        public_parameters.trim(2 * prover.cs.next_power_of_two_size())?;
+    prover.preprocess(&ck)?;
+    let public_inputs = prover.cs.construct_dense_pi_vec();
+    let proof = prover.prove(&ck)?;
+
+    let mut verifier = Verifier::new(b"prop");
+    gadget(verifier.mut_cs());
+    let (ck, ok) =
+        public_parameters.trim(verifier.cs.next_power_of_two_size())?;
+    verifier.preprocess(&ck)?;
+    verifier.verify(&proof, &ok, &public_inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plookup::table::hash_tables::constants::DECOMPOSITION_S_I;
+    use crate::plookup::table::hash_tables::SboxTable;
+    use crate::plookup::PlookupTable4Arity;
+    use dusk_bls12_381::BlsScalar;
+
+    #[test]
+    fn test_prop_rng_replay() {
+        let mut rng = PropRng::new(7);
+        let values = [rng.next_u64(), rng.next_u64()];
+
+        let mut replay = PropRng::new(7);
+        assert_eq!([replay.next_u64(), replay.next_u64()], values);
+        assert_ne!(PropRng::new(8).next_u64(), values[0]);
+    }
+
+    #[test]
+    fn test_prop_s_box() {
+        let table = PlookupTable4Arity::create_hash_table();
+
+        // Random nibbles of the least significant position, below its s_i
+        let s = DECOMPOSITION_S_I[0].0[0];
+        let res = prop_gadget_tester(
+            |rng| (rng.next_u64() % s, rng.next_u32() % 2 == 0),
+            |composer, (nibble, conditional)| {
+                composer.append_lookup_table(&table);
+                let x = composer.add_input(BlsScalar::from(*nibble));
+                composer.s_box_and_constraints(
+                    x,
+                    27,
+                    *conditional,
+                    &SboxTable::default(),
+                );
+            },
+            2048,
+            4,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // Nibbles out of the table are reported with their seed
+        let res = prop_gadget_tester(
+            |rng| s + rng.next_u64() % 100,
+            |composer, nibble| {
+                composer.append_lookup_table(&table);
+                let x = composer.add_input(BlsScalar::from(*nibble));
+                composer.s_box_and_constraints(
+                    x,
+                    27,
+                    false,
+                    &SboxTable::default(),
+                );
+            },
+            2048,
+            4,
+        );
+        assert!(matches!(res, Err(PropFailure { seed: 0, .. })));
+    }
+}
@@ -10,6 +10,7 @@
 /// The value is a reference to the actual value that was added to the
 /// constraint system
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variable(pub(crate) usize);
 
 /// Stores the data for a specific wire in an arithmetic circuit
@@ -8,13 +8,294 @@
 
 use super::divide_w_recip;
 use crate::constraint_system::{StandardComposer, Variable};
+use crate::error::Error;
 use crate::plookup::table::hash_tables::constants::{
-    BLS_DIVISORS, BLS_RECIP, REMAINDER_MONT,
+    BLS_DIVISORS, BLS_RECIP, DECOMPOSITION_S_I, INVERSES_S_I, REMAINDER_MONT,
+    S_I_DECOMPOSITION_MONTGOMERY,
 };
+use crate::plookup::table::hash_tables::SboxTable;
+use crate::plookup::LookupRow;
+use alloc::vec::Vec;
 use bigint::U256 as u256;
 use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+pub mod conv;
+
+use conv::scalar_to_u256;
+
+/// Number of state elements of the Zelbet sponge absorbing the inputs
+pub const SPONGE_RATE: usize = 2;
+/// Number of state elements of the Zelbet sponge never touched by the inputs
+pub const SPONGE_CAPACITY: usize = 1;
+/// Number of state elements of the Zelbet sponge
+pub const SPONGE_WIDTH: usize = SPONGE_RATE + SPONGE_CAPACITY;
+/// Number of bytes packed into each field element by [`pack_bytes`], which
+/// keeps them below `2^248`, well within the 254 bits of the field
+pub const BYTES_PER_ELEMENT: usize = 31;
+/// Domain of the hashes of [`StandardComposer::accumulator_add`], packed by
+/// [`pack_bytes`] into the first element absorbed by every update
+pub const ACCUMULATOR_DOMAIN: &[u8] = b"zelbet-accumulator";
+
+/// Out of circuit bar decomposition of `x`, returning the same 27 nibbles
+/// as [`StandardComposer::decomposition_gadget`], from the least significant
+/// one to the most significant one.
+///
+/// Each nibble is the remainder of the division by the corresponding `s_i`
+/// from [`DECOMPOSITION_S_I`], and the quotient is then obtained in the field
+/// by multiplying the difference with the inverse from [`INVERSES_S_I`].
+pub fn decompose_scalar(x: BlsScalar) -> [BlsScalar; 27] {
+    let mut nibbles = [BlsScalar::zero(); 27];
+    let mut intermediate = x;
+
+    (0..26).for_each(|k| {
+        let s = DECOMPOSITION_S_I[k].0[0];
+        let remainder = scalar_to_u256(intermediate) % u256::from(s);
+        nibbles[k] = BlsScalar::from(remainder.as_u64());
+        intermediate = (intermediate - nibbles[k]) * INVERSES_S_I[k];
+    });
+    nibbles[26] = intermediate;
+
+    nibbles
+}
+
+/// Returns the nibbles of the bar decomposition of `p - 1`, the largest
+/// element of the field, from the least significant one to the most
+/// significant one, which bound the nibbles of every other element.
+///
+/// They're computed with [`decompose_scalar`], and are the bounds hardcoded
+/// in [`BLS_SCALAR_REAL`] that the s-box compares each nibble against,
+/// from counter 1 for the most significant one.
+///
+/// [`BLS_SCALAR_REAL`]:
+/// crate::plookup::table::hash_tables::constants::BLS_SCALAR_REAL
+pub fn field_modulus_nibble_bounds() -> [BlsScalar; 27] {
+    decompose_scalar(-BlsScalar::one())
+}
+
+/// Packs `bytes` into field elements of [`BYTES_PER_ELEMENT`] bytes each,
+/// read in little-endian order, as hashed by
+/// [`StandardComposer::hash_bytes`].
+///
+/// The bytes are padded with a `0x01` byte followed by as many zero bytes as
+/// needed to fill the last element, so byte strings of different lengths
+/// never pack into the same elements, and the empty string packs into the
+/// single element `1`.
+pub fn pack_bytes(bytes: &[u8]) -> Vec<BlsScalar> {
+    let mut padded = bytes.to_vec();
+    padded.push(1);
+    while padded.len() % BYTES_PER_ELEMENT != 0 {
+        padded.push(0);
+    }
+
+    padded
+        .chunks(BYTES_PER_ELEMENT)
+        .map(|chunk| {
+            let mut element = [0u8; BlsScalar::SIZE];
+            element[..BYTES_PER_ELEMENT].copy_from_slice(chunk);
+            BlsScalar::from_bytes(&element)
+                .expect("31 bytes are below the field modulus")
+        })
+        .collect()
+}
+
+/// Returns whether `a < b` without branching on them, from the borrow of
+/// `a - b`.
+fn ct_less_than(a: u64, b: u64) -> Choice {
+    let borrow = (!a & b) | (!(a ^ b) & a.wrapping_sub(b));
+    Choice::from((borrow >> 63) as u8)
+}
+
+/// Converts `x` into a [`BlsScalar`], returning [`Error::ScalarOverflow`]
+/// instead of reducing it if it isn't smaller than the field modulus.
+pub fn try_scalar_from_u256(x: u256) -> Result<BlsScalar, Error> {
+    let mut bytes = [0u8; BlsScalar::SIZE];
+    x.to_little_endian(&mut bytes);
+    conv::bytes_to_scalar(&bytes)
+}
+
+/// State of the comparison between the nibbles of a decomposition and the
+/// ones of `p - 1`, going from the most significant nibble to the least
+/// significant one, used by [`StandardComposer::s_box_chained`].
+#[derive(Debug, Clone, Copy)]
+pub struct SboxChain {
+    /// Position of the next nibble, from 1 to 27
+    counter: u64,
+    /// Boolean [`Variable`] which is 1 while every nibble so far equals the
+    /// corresponding nibble of `p - 1`
+    tight: Variable,
+}
+
+impl SboxChain {
+    /// Starts a chain at the most significant nibble.
+    pub fn new(composer: &mut StandardComposer) -> Self {
+        let tight =
+            composer.add_witness_to_circuit_description(BlsScalar::one());
+        SboxChain { counter: 1, tight }
+    }
+
+    /// Returns the position of the next nibble, from 1 to 27.
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
+
+    /// Returns the boolean [`Variable`] which is 1 while every nibble so far
+    /// equals the corresponding nibble of `p - 1`.
+    pub fn tight(&self) -> Variable {
+        self.tight
+    }
+}
+
+/// Outputs of [`StandardComposer::s_box_and_constraints`] for a single
+/// nibble.
+///
+/// Converts into the `(y_i, c_i, conditional, z_i)` tuple the gadget used to
+/// return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SboxOutput {
+    /// Output of the s-box
+    pub y_i: Variable,
+    /// Tag comparing the nibble with the one of `p - 1`
+    pub c_i: Variable,
+    /// Whether every nibble so far equals the corresponding nibble of `p - 1`
+    pub conditional: bool,
+    /// Witness holding `conditional`
+    pub z_i: Variable,
+}
+
+impl From<SboxOutput> for (Variable, Variable, bool, Variable) {
+    fn from(output: SboxOutput) -> Self {
+        (output.y_i, output.c_i, output.conditional, output.z_i)
+    }
+}
+
+/// Outputs of [`StandardComposer::s_box_word`] for every nibble of a word,
+/// indexed like the nibbles of [`StandardComposer::decomposition_gadget`],
+/// from the least significant one to the most significant one.
+#[derive(Debug, Clone, Copy)]
+pub struct SboxWordResult {
+    /// Outputs `y_i` of the s-box
+    pub y: [Variable; 27],
+    /// Tags `c_i` comparing the nibbles with the ones of `p - 1`
+    pub c: [Variable; 27],
+    /// Witnesses `z_i` of the conditional flag after every nibble
+    pub z: [Variable; 27],
+    /// Whether every nibble equals the corresponding nibble of `p - 1`
+    pub conditional: bool,
+}
+
+/// Stateful Zelbet sponge in duplex mode, over the same permutation as
+/// [`StandardComposer::zelbet_sponge_hash`], so absorptions and squeezes can
+/// be interleaved.
+///
+/// The sponge tracks its position in the rate part of the state: absorbing
+/// permutes the state as soon as a block of [`SPONGE_RATE`] inputs is full,
+/// and squeezing permutes it before reading past the rate. Switching from
+/// absorbing to squeezing permutes a partially absorbed block. No padding is
+/// applied, it's up to the caller.
+#[derive(Debug, Clone)]
+pub struct ZelbetSponge {
+    state: [Variable; SPONGE_WIDTH],
+    s_i_decomposition: [Variable; 27],
+    /// Position of the next element to absorb or squeeze in the rate part
+    position: usize,
+    squeezing: bool,
+}
+
+impl ZelbetSponge {
+    /// Creates a sponge whose state starts at zero, adding the `s_i` of the
+    /// permutation to the circuit description.
+    pub fn new(composer: &mut StandardComposer) -> Self {
+        let s_i_decomposition = composer.s_i_decomposition_vars();
+
+        ZelbetSponge {
+            state: [composer.zero_var; SPONGE_WIDTH],
+            s_i_decomposition,
+            position: 0,
+            squeezing: false,
+        }
+    }
+
+    /// Adds `inputs` to the rate part of the state, one element at a time,
+    /// permuting it whenever a block is full.
+    pub fn absorb(
+        &mut self,
+        composer: &mut StandardComposer,
+        inputs: &[Variable],
+    ) {
+        self.squeezing = false;
+        for input in inputs {
+            // Only left at the end of the rate by a squeeze
+            if self.position == SPONGE_RATE {
+                self.permute(composer);
+            }
+
+            let element = &mut self.state[self.position];
+            *element = composer.add(
+                (BlsScalar::one(), *element),
+                (BlsScalar::one(), *input),
+                BlsScalar::zero(),
+                None,
+            );
+            self.position += 1;
+
+            if self.position == SPONGE_RATE {
+                self.permute(composer);
+            }
+        }
+    }
+
+    /// Reads `n` elements from the rate part of the state, permuting it
+    /// before the first one if inputs were absorbed since the last
+    /// permutation, and whenever the rate is exhausted.
+    pub fn squeeze(
+        &mut self,
+        composer: &mut StandardComposer,
+        n: usize,
+    ) -> Vec<Variable> {
+        if !self.squeezing && self.position != 0 {
+            self.permute(composer);
+        }
+        self.squeezing = true;
+
+        (0..n)
+            .map(|_| {
+                if self.position == SPONGE_RATE {
+                    self.permute(composer);
+                }
+                self.position += 1;
+                self.state[self.position - 1]
+            })
+            .collect()
+    }
+
+    fn permute(&mut self, composer: &mut StandardComposer) {
+        self.state = composer
+            .zelbet_sponge_permutation(self.state, self.s_i_decomposition);
+        self.position = 0;
+    }
+}
 
 impl StandardComposer {
+    /// Adds the `s_i` of the decomposition, in Montgomery form as expected
+    /// by [`StandardComposer::decomposition_gadget`], to the circuit
+    /// description, returning their [`Variable`]s from `s_1` to `s_27`.
+    ///
+    /// Every `s_i` is constrained to its constant from
+    /// [`S_I_DECOMPOSITION_MONTGOMERY`] through
+    /// [`StandardComposer::add_constant_var`], so the `s_i` sharing a value
+    /// share a [`Variable`] and a single gate, and the following calls
+    /// return the same variables without adding any gate.
+    pub fn s_i_decomposition_vars(&mut self) -> [Variable; 27] {
+        let mut s_i_decomposition = [self.zero_var; 27];
+        s_i_decomposition
+            .iter_mut()
+            .zip(S_I_DECOMPOSITION_MONTGOMERY.iter())
+            .for_each(|(var, s)| *var = self.add_constant_var(*s));
+        s_i_decomposition
+    }
+
     /// Gadget that conducts the bar decomposition, returning the 27-entry
     /// breakdown and adding relevant gates. The input and output variables
     /// are all in Montgomery form, but non-Montgomery form is used within.
@@ -24,11 +305,79 @@ impl StandardComposer {
         &mut self,
         x: Variable,
         s_i_decomposition: [Variable; 27],
+    ) -> ([Variable; 27], [u256; 27]) {
+        let (nibbles_mont, nibbles_reduced, _) =
+            self.decomposition_gadget_with_acc(x, s_i_decomposition);
+        (nibbles_mont, nibbles_reduced)
+    }
+
+    /// Same as [`StandardComposer::decomposition_gadget`], but also returns
+    /// the 26 accumulators of the Horner recomposition of the nibbles, to
+    /// check it step by step.
+    ///
+    /// The accumulator `k` is the previous one, starting from the last
+    /// nibble, times `s_i_decomposition[25 - k]` plus the nibble `25 - k`, so
+    /// the last accumulator is the recomposition constrained to be `x`.
+    pub fn decomposition_gadget_with_acc(
+        &mut self,
+        x: Variable,
+        s_i_decomposition: [Variable; 27],
+    ) -> ([Variable; 27], [u256; 27], [Variable; 26]) {
+        let (nibbles_mont, nibbles_reduced) = self.decomposition_nibbles(x);
+
+        let mut accumulators = [x; 26];
+        let mut accumulator = accumulators.iter_mut();
+        let acc = self.compose_nibbles_with(
+            &nibbles_mont,
+            &s_i_decomposition,
+            |acc| *accumulator.next().expect("there are 26 accumulators") = acc,
+        );
+        self.assert_equal(acc, x);
+
+        (nibbles_mont, nibbles_reduced, accumulators)
+    }
+
+    /// Same as [`StandardComposer::decomposition_gadget`], but with the
+    /// `s_i` of [`S_I_DECOMPOSITION_MONTGOMERY`] as the selectors of the
+    /// recomposition gates instead of as [`Variable`]s. This is
+    /// [`StandardComposer::decomposition_gadget_with_bases`] with the `s_i`
+    /// and [`INVERSES_S_I`].
+    ///
+    /// The recomposition takes as many gates as with
+    /// [`StandardComposer::s_i_decomposition_vars`], but the gates
+    /// constraining the `s_i` to their constants aren't needed.
+    pub fn decomposition_gadget_const(
+        &mut self,
+        x: Variable,
+    ) -> ([Variable; 27], [u256; 27]) {
+        let nibbles = self
+            .decomposition_gadget_with_bases(
+                x,
+                &S_I_DECOMPOSITION_MONTGOMERY,
+                &INVERSES_S_I,
+            )
+            .expect("the s_i are nonzero and match their inverses");
+
+        let mut nibbles_mont = [x; 27];
+        let mut nibbles_reduced = [u256::zero(); 27];
+        nibbles.iter().enumerate().for_each(|(k, nibble)| {
+            nibbles_mont[k] = *nibble;
+            nibbles_reduced[k] = scalar_to_u256(self.variables[nibble]);
+        });
+
+        (nibbles_mont, nibbles_reduced)
+    }
+
+    /// Witnesses the 27 nibbles of the bar decomposition of `x`, in
+    /// Montgomery form and in reduced form, without constraining them.
+    fn decomposition_nibbles(
+        &mut self,
+        x: Variable,
     ) -> ([Variable; 27], [u256; 27]) {
         let mut nibbles_mont = [x; 27];
         let mut nibbles_reduced = [u256::zero(); 27];
         // Reduced form needed for the modular operations
-        let mut intermediate = self.variables[&x].reduce().0;
+        let mut intermediate = self.witness_canonical(x).0;
         let mut remainder = 0u16;
         // s should be set to the number of leading zeros of div in each
         // iteration of the loop below, but under BLS conditions this value is
@@ -59,12 +408,323 @@ impl StandardComposer {
             nibbles_reduced[k] = u256([remainder as u64, 0, 0, 0]);
         });
 
-        // x' = x_1 * s_2 + x_2, this is the start of the composition
+        (nibbles_mont, nibbles_reduced)
+    }
+
+    /// Decomposes `x` in the mixed-radix system of the given `bases`,
+    /// returning one nibble per base, from the least significant one to the
+    /// most significant one, in Montgomery form.
+    ///
+    /// The nibble `k` is the remainder of the division by `bases[k]` of what
+    /// is left of `x`, except for the last one which is the remaining
+    /// quotient, so that the last base is unused. `inverses[k]` must be the
+    /// inverse of `bases[k]`, which is used to compute the quotients in the
+    /// field. The recomposition is constrained in the same way as
+    /// [`StandardComposer::decomposition_gadget`], but with the bases as
+    /// selectors of the gates rather than as [`Variable`]s, and the nibbles
+    /// aren't range constrained.
+    ///
+    /// Returns [`Error::InputLengthMismatch`] if `bases` and `inverses`
+    /// have different lengths, and [`Error::InvalidBase`] if one of the
+    /// bases but the last one is zero or isn't the inverse of its element
+    /// of `inverses`.
+    ///
+    /// # Panics
+    /// If `bases` is empty.
+    pub fn decomposition_gadget_with_bases(
+        &mut self,
+        x: Variable,
+        bases: &[BlsScalar],
+        inverses: &[BlsScalar],
+    ) -> Result<Vec<Variable>, Error> {
+        if bases.len() != inverses.len() {
+            return Err(Error::InputLengthMismatch {
+                left: bases.len(),
+                right: inverses.len(),
+            });
+        }
+        assert!(!bases.is_empty(), "a decomposition needs at least one base");
+
+        let last = bases.len() - 1;
+        if let Some(index) =
+            bases[..last]
+                .iter()
+                .zip(inverses)
+                .position(|(base, inverse)| {
+                    *base == BlsScalar::zero()
+                        || base * inverse != BlsScalar::one()
+                })
+        {
+            return Err(Error::InvalidBase { index });
+        }
+
+        let mut intermediate = self.variables[&x];
+        let mut nibbles: Vec<Variable> = bases[..last]
+            .iter()
+            .zip(inverses)
+            .map(|(base, inverse)| {
+                // The remainder is below the base, so it fits in the field
+                let remainder =
+                    scalar_to_u256(intermediate) % scalar_to_u256(*base);
+                let nibble = conv::u256_to_scalar(remainder);
+                intermediate = (intermediate - nibble) * inverse;
+                self.add_input(nibble)
+            })
+            .collect();
+        nibbles.push(self.add_input(intermediate));
+
+        let acc = self.compose_nibbles_const(&nibbles, &bases[..last]);
+        self.assert_equal(acc, x);
+
+        Ok(nibbles)
+    }
+
+    /// Conducts the bar decomposition of every element of `xs` with
+    /// [`StandardComposer::decomposition_gadget`], sharing the same
+    /// `s_i_decomposition` variables across all of them. The composition
+    /// of every decomposition is still constrained to its own input.
+    pub fn decomposition_gadget_batch(
+        &mut self,
+        xs: &[Variable],
+        s_i_decomposition: [Variable; 27],
+    ) -> Vec<[Variable; 27]> {
+        xs.iter()
+            .map(|x| self.decomposition_gadget(*x, s_i_decomposition).0)
+            .collect()
+    }
+
+    /// S-box `sbox` applied to the nibble `x` at position `counter`, from 1
+    /// for the most significant nibble to 27 for the least significant one,
+    /// which also computes the constraint tag `c_i` comparing `x` with the
+    /// nibble `v_i` of `p - 1` from `sbox`.
+    ///
+    /// `conditional` must be `true` while every nibble before `x` equals the
+    /// corresponding nibble of `p - 1`. Returns the [`SboxOutput`] where
+    /// `y_i` is the output of the s-box, `c_i` is `1` if `x < v_i`, `0` if
+    /// `x = v_i` and `conditional` holds and `2` otherwise, `conditional` is
+    /// updated to include `x`, and `z_i` is a witness holding the updated
+    /// `conditional`.
+    ///
+    /// # Note
+    /// `conditional` and `z_i` are not constrained, so the caller is trusted
+    /// to thread them correctly. See [`StandardComposer::s_box_chained`] for
+    /// a variant tracking them in the circuit.
+    ///
+    /// The lookups only succeed against a table built from the same `sbox`,
+    /// see [`PlookupTable4Arity::create_hash_table_with_sbox`]. The gadgets
+    /// of Reinforced Concrete use [`SboxTable::REINFORCED_CONCRETE`].
+    ///
+    /// The witnesses are computed in constant time with respect to the value
+    /// of `x` and to `conditional`, with [`SboxTable::ct_output`] and
+    /// comparisons free of data-dependent branches, so the time spent
+    /// building the circuit doesn't reveal the magnitude of a secret nibble.
+    /// Only `counter`, which is part of the circuit description, is
+    /// branched on.
+    ///
+    /// # Panics
+    /// If `counter` is not in `1..=27`, see [`SboxTable::bound`].
+    ///
+    /// [`PlookupTable4Arity::create_hash_table_with_sbox`]:
+    /// crate::plookup::PlookupTable4Arity::create_hash_table_with_sbox
+    pub fn s_box_and_constraints(
+        &mut self,
+        x: Variable,
+        counter: u64,
+        conditional: bool,
+        sbox: &SboxTable,
+    ) -> SboxOutput {
+        self.s_box_and_constraints_traced(x, counter, conditional, sbox)
+            .0
+    }
+
+    /// Same as [`StandardComposer::s_box_and_constraints`], but returns
+    /// [`Error::CounterOutOfRange`] if `counter` is not in `1..=27`, and
+    /// [`Error::TableMissing`] if no lookup table was added to the composer,
+    /// instead of panicking. No gate is appended on error.
+    pub fn try_s_box_and_constraints(
+        &mut self,
+        x: Variable,
+        counter: u64,
+        conditional: bool,
+        sbox: &SboxTable,
+    ) -> Result<SboxOutput, Error> {
+        if sbox.bound(counter).is_none() {
+            return Err(Error::CounterOutOfRange { counter });
+        }
+        if !self.has_lookup_table() {
+            return Err(Error::TableMissing);
+        }
+
+        Ok(self.s_box_and_constraints(x, counter, conditional, sbox))
+    }
+
+    /// Same as [`StandardComposer::s_box_and_constraints`], but also returns
+    /// the index of the plookup gate it appends, so that a failing
+    /// constraint can be traced back to the nibble that produced it.
+    pub fn s_box_and_constraints_traced(
+        &mut self,
+        x: Variable,
+        counter: u64,
+        conditional: bool,
+        sbox: &SboxTable,
+    ) -> (SboxOutput, usize) {
+        let (y_i, c_i, conditional, gate_index) =
+            self.s_box_lookup(x, counter, conditional, sbox);
+        let z_i = self.add_input(BlsScalar::from(conditional as u64));
+
+        (
+            SboxOutput {
+                y_i,
+                c_i,
+                conditional,
+                z_i,
+            },
+            gate_index,
+        )
+    }
+
+    /// S-box of Reinforced Concrete applied to the next nibble `x` of
+    /// `chain`, see [`StandardComposer::s_box_and_constraints`], returning
+    /// `(y_i, c_i)`.
+    ///
+    /// Instead of trusting the caller with the comparison with `p - 1`, the
+    /// chain keeps it as a boolean [`Variable`] and the gadget constrains
+    /// that `c_i` is not `2` while the previous nibbles equal the ones of
+    /// `p - 1`, so a decomposition of a value larger than `p - 1` can't
+    /// satisfy the circuit. The chain is then updated to the next nibble.
+    ///
+    /// # Panics
+    /// This function will panic if the chain is already past the 27th
+    /// nibble.
+    pub fn s_box_chained(
+        &mut self,
+        chain: &mut SboxChain,
+        x: Variable,
+    ) -> (Variable, Variable) {
+        let tight = self.variables[&chain.tight] == BlsScalar::one();
+        let (y, c, _, _) = self.s_box_lookup(
+            x,
+            chain.counter,
+            tight,
+            &SboxTable::REINFORCED_CONCRETE,
+        );
+
+        // u = c * (c - 1) is 0 if and only if c is 0 or 1
+        let u = self.big_mul(
+            BlsScalar::one(),
+            c,
+            c,
+            Some((-BlsScalar::one(), c)),
+            BlsScalar::zero(),
+            None,
+        );
+        // tight * u = 0
+        self.mul_gate(
+            chain.tight,
+            u,
+            self.zero_var,
+            BlsScalar::one(),
+            BlsScalar::zero(),
+            BlsScalar::zero(),
+            None,
+        );
+
+        // w = (c - 1) * (c - 2) / 2 = u / 2 - c + 1 is 1 if c is 0, and 0 if
+        // c is 1 or 2, so the chain stays tight only for equal nibbles
+        let w = self.add(
+            (BlsScalar::from(2).invert().unwrap(), u),
+            (-BlsScalar::one(), c),
+            BlsScalar::one(),
+            None,
+        );
+        chain.tight =
+            self.mul(BlsScalar::one(), chain.tight, w, BlsScalar::zero(), None);
+        chain.counter += 1;
+
+        (y, c)
+    }
+
+    /// Applies [`StandardComposer::s_box_and_constraints`] to the 27 nibbles
+    /// of a word, in the order of
+    /// [`StandardComposer::decomposition_gadget`], threading the counter
+    /// from 1 for the most significant nibble `nibbles[26]` to 27 for the
+    /// least significant one `nibbles[0]`, and the conditional flag from a
+    /// starting `true`, and collects the outputs of every nibble.
+    pub fn s_box_word(
+        &mut self,
+        nibbles: &[Variable; 27],
+        sbox: &SboxTable,
+    ) -> SboxWordResult {
+        let mut word = SboxWordResult {
+            y: [self.zero_var; 27],
+            c: [self.zero_var; 27],
+            z: [self.zero_var; 27],
+            conditional: true,
+        };
+        (0..27).rev().for_each(|k| {
+            let counter = 27 - k as u64;
+            let output = self.s_box_and_constraints(
+                nibbles[k],
+                counter,
+                word.conditional,
+                sbox,
+            );
+            word.y[k] = output.y_i;
+            word.c[k] = output.c_i;
+            word.z[k] = output.z_i;
+            word.conditional = output.conditional;
+        });
+
+        word
+    }
+
+    /// Constrains the 27 `nibbles` of a bar decomposition, from the least
+    /// significant one to the most significant one, to represent a value
+    /// below the field modulus, so that no element has two decompositions.
+    ///
+    /// The nibbles are compared lexicographically with the ones of `p - 1`,
+    /// given by [`field_modulus_nibble_bounds`], through
+    /// [`StandardComposer::s_box_chained`] from the most significant one:
+    /// the tag `c_i` of a nibble can't mark it larger than its bound while
+    /// every previous nibble equals its own. The lookups need the table of
+    /// [`PlookupTable4Arity::create_hash_table`] to be appended to the
+    /// composer.
+    ///
+    /// [`PlookupTable4Arity::create_hash_table`]:
+    /// crate::plookup::PlookupTable4Arity::create_hash_table
+    pub fn assert_canonical_decomposition(&mut self, nibbles: &[Variable; 27]) {
+        let mut chain = SboxChain::new(self);
+        nibbles.iter().rev().for_each(|nibble| {
+            self.s_box_chained(&mut chain, *nibble);
+        });
+    }
+
+    /// Bar function of Reinforced Concrete applied to `x`: decomposes `x`
+    /// with [`StandardComposer::decomposition_gadget`], maps every nibble
+    /// through [`StandardComposer::s_box_word`], from the most
+    /// significant one at counter 1 down to the least significant one at
+    /// counter 27 with [`SboxTable::REINFORCED_CONCRETE`], and recomposes the
+    /// s-box outputs with the same `s_i`.
+    ///
+    /// The `conditional` flag starts at `true` and is carried from each
+    /// nibble to the next one across the whole word. Like the
+    /// decomposition, `s_i_decomposition` should be input in Montgomery
+    /// form.
+    pub fn zelbet_permutation(
+        &mut self,
+        x: Variable,
+        s_i_decomposition: [Variable; 27],
+    ) -> Variable {
+        let (nibbles, _) = self.decomposition_gadget(x, s_i_decomposition);
+        let outputs =
+            self.s_box_word(&nibbles, &SboxTable::REINFORCED_CONCRETE).y;
+
+        // y' = y_1 * s_2 + y_2, this is the start of the composition
         let mut acc = self.big_mul(
             BlsScalar::one(),
-            nibbles_mont[26],
+            outputs[26],
             s_i_decomposition[25],
-            Some((BlsScalar::one(), nibbles_mont[25])),
+            Some((BlsScalar::one(), outputs[25])),
             BlsScalar::zero(),
             Some(BlsScalar::zero()),
         );
@@ -74,78 +734,1575 @@ impl StandardComposer {
                 BlsScalar::one(),
                 acc,
                 s_i_decomposition[25 - k],
-                Some((BlsScalar::one(), nibbles_mont[25 - k])),
+                Some((BlsScalar::one(), outputs[25 - k])),
                 BlsScalar::zero(),
                 Some(BlsScalar::zero()),
             );
         });
 
-        self.constrain_to_constant(
-            acc,
-            self.variables[&x],
-            Some(BlsScalar::zero()),
+        acc
+    }
+
+    /// Applies [`StandardComposer::zelbet_permutation`] to `x` `rounds`
+    /// times, each round starting over from counter 1 and a `true`
+    /// conditional flag. Zero rounds return `x` unchanged.
+    pub fn zelbet_permutation_rounds(
+        &mut self,
+        x: Variable,
+        s_i_decomposition: [Variable; 27],
+        rounds: usize,
+    ) -> Variable {
+        self.fold_rounds(x, rounds, |composer, acc| {
+            composer.zelbet_permutation(acc, s_i_decomposition)
+        })
+    }
+
+    /// Applies `round` `rounds` times, starting from `state` and feeding
+    /// each round the output of the previous one, and returns the final
+    /// state. Zero rounds return `state` unchanged.
+    ///
+    /// The gates are appended exactly as by the equivalent loop, so that
+    /// multi-round permutations, such as a decomposition, s-box and
+    /// recomposition cycle, are written without threading the state by
+    /// hand.
+    pub fn fold_rounds<F>(
+        &mut self,
+        state: Variable,
+        rounds: usize,
+        mut round: F,
+    ) -> Variable
+    where
+        F: FnMut(&mut StandardComposer, Variable) -> Variable,
+    {
+        (0..rounds).fold(state, |acc, _| round(self, acc))
+    }
+
+    /// Hashes `inputs` into a single [`Variable`] with a sponge over
+    /// [`StandardComposer::zelbet_permutation`], with a state of
+    /// [`SPONGE_RATE`] + [`SPONGE_CAPACITY`] elements starting at zero.
+    ///
+    /// The inputs are padded with a one followed by as many zeros as needed
+    /// to reach a multiple of the rate, so inputs of different lengths never
+    /// share their padded blocks, and an empty input hashes the single block
+    /// `[1, 0]`. Each block is added to the rate part of the state before
+    /// permuting it, and the first element of the final state is the
+    /// digest.
+    pub fn zelbet_sponge_hash(&mut self, inputs: &[Variable]) -> Variable {
+        let mut sponge = ZelbetSponge::new(self);
+
+        let mut padded: Vec<Variable> = inputs.to_vec();
+        padded.push(self.add_witness_to_circuit_description(BlsScalar::one()));
+        while padded.len() % SPONGE_RATE != 0 {
+            padded.push(self.zero_var);
+        }
+
+        // The last block is permuted as soon as it's absorbed
+        sponge.absorb(self, &padded);
+        sponge.squeeze(self, 1)[0]
+    }
+
+    /// Same as [`StandardComposer::zelbet_sponge_hash`], but returns
+    /// [`Error::TableMissing`] instead of panicking if no lookup table was
+    /// added to the composer, in which case no gate is appended.
+    pub fn try_zelbet_sponge_hash(
+        &mut self,
+        inputs: &[Variable],
+    ) -> Result<Variable, Error> {
+        match self.has_lookup_table() {
+            true => Ok(self.zelbet_sponge_hash(inputs)),
+            false => Err(Error::TableMissing),
+        }
+    }
+
+    /// Hashes `bytes` into a single [`Variable`], witnessing the elements
+    /// packed by [`pack_bytes`] and hashing them with
+    /// [`StandardComposer::zelbet_sponge_hash`].
+    ///
+    /// Every element is range constrained to `8 * BYTES_PER_ELEMENT` bits,
+    /// so it can only be the packing of some bytes. The number of elements,
+    /// and so the shape of the circuit, depends on the length of `bytes`.
+    pub fn hash_bytes(&mut self, bytes: &[u8]) -> Variable {
+        let elements: Vec<Variable> = pack_bytes(bytes)
+            .iter()
+            .map(|element| {
+                let var = self.add_input(*element);
+                self.range_gate(var, 8 * BYTES_PER_ELEMENT);
+                var
+            })
+            .collect();
+
+        self.zelbet_sponge_hash(&elements)
+    }
+
+    /// Hashes `inputs` into a single [`Variable`] separated by `domain_tag`,
+    /// so that protocols hashing the same inputs under different tags get
+    /// independent digests.
+    ///
+    /// The tag is a constant of the circuit description, absorbed by
+    /// [`StandardComposer::zelbet_sponge_hash`] before the inputs, so the
+    /// digest is the sponge hash of `[domain_tag, inputs..]`. Untagged sponge
+    /// hashes whose first input can be chosen should then not be mixed with
+    /// tagged ones.
+    pub fn hash_to_field(
+        &mut self,
+        domain_tag: u64,
+        inputs: &[Variable],
+    ) -> Variable {
+        let tag = self
+            .add_witness_to_circuit_description(BlsScalar::from(domain_tag));
+
+        let mut tagged = Vec::with_capacity(inputs.len() + 1);
+        tagged.push(tag);
+        tagged.extend_from_slice(inputs);
+        self.zelbet_sponge_hash(&tagged)
+    }
+
+    /// Permutation of the sponge state: applies the bar function to every
+    /// element, then mixes them with the circulant matrix `circ(2, 1, 1)`.
+    fn zelbet_sponge_permutation(
+        &mut self,
+        state: [Variable; SPONGE_WIDTH],
+        s_i_decomposition: [Variable; 27],
+    ) -> [Variable; SPONGE_WIDTH] {
+        let mut bars = state;
+        for element in bars.iter_mut() {
+            *element = self.zelbet_permutation(*element, s_i_decomposition);
+        }
+
+        let mut mixed = bars;
+        (0..SPONGE_WIDTH).for_each(|i| {
+            mixed[i] = self.big_add(
+                (BlsScalar::from(2), bars[i]),
+                (BlsScalar::one(), bars[(i + 1) % SPONGE_WIDTH]),
+                Some((BlsScalar::one(), bars[(i + 2) % SPONGE_WIDTH])),
+                BlsScalar::zero(),
+                None,
+            );
+        });
+
+        mixed
+    }
+
+    /// Recomposes `nibbles`, from the least significant one to the most
+    /// significant one, in the mixed-radix system of `bases`, which is the
+    /// inverse of [`StandardComposer::decomposition_gadget`] when given the
+    /// same `s_i_decomposition`.
+    ///
+    /// Horner's rule is used, appending one gate `acc * bases[k] +
+    /// nibbles[k]` per nibble but the last one, exactly as the decomposition
+    /// gadgets do. It can then recompose nibbles modified, for example, by an
+    /// s-box pass.
+    ///
+    /// # Panics
+    /// If `nibbles` is empty, or if there are less than `nibbles.len() - 1`
+    /// bases.
+    pub fn recompose_from_nibbles(
+        &mut self,
+        nibbles: &[Variable],
+        bases: &[Variable],
+    ) -> Variable {
+        assert!(!nibbles.is_empty(), "there must be at least one nibble");
+        assert!(
+            bases.len() + 1 >= nibbles.len(),
+            "{} nibbles need at least {} bases, got {}",
+            nibbles.len(),
+            nibbles.len() - 1,
+            bases.len()
         );
+        self.compose_nibbles(nibbles, bases)
+    }
 
-        (nibbles_mont, nibbles_reduced)
+    /// Horner recomposition of [`StandardComposer::recompose_from_nibbles`],
+    /// for callers which already ensure the lengths are right.
+    fn compose_nibbles(
+        &mut self,
+        nibbles: &[Variable],
+        bases: &[Variable],
+    ) -> Variable {
+        self.compose_nibbles_with(nibbles, bases, |_| ())
+    }
+
+    /// Same as [`StandardComposer::compose_nibbles`], but with constant
+    /// `bases`, so that each step `acc * bases[k] + nibbles[k]` is a single
+    /// addition gate with the base as the selector of the accumulator.
+    /// Going through [`StandardComposer::mul_const`] would take a second
+    /// gate for the addition of the nibble.
+    fn compose_nibbles_const(
+        &mut self,
+        nibbles: &[Variable],
+        bases: &[BlsScalar],
+    ) -> Variable {
+        let last = nibbles.len() - 1;
+        nibbles[..last].iter().zip(bases).rev().fold(
+            nibbles[last],
+            |acc, (nibble, base)| {
+                self.add(
+                    (*base, acc),
+                    (BlsScalar::one(), *nibble),
+                    BlsScalar::zero(),
+                    None,
+                )
+            },
+        )
+    }
+
+    /// Same as [`StandardComposer::compose_nibbles`], passing every
+    /// accumulator to `on_acc` as it's built.
+    fn compose_nibbles_with(
+        &mut self,
+        nibbles: &[Variable],
+        bases: &[Variable],
+        mut on_acc: impl FnMut(Variable),
+    ) -> Variable {
+        let last = nibbles.len() - 1;
+        nibbles[..last].iter().zip(bases).rev().fold(
+            nibbles[last],
+            |acc, (nibble, base)| {
+                let acc = self
+                    .expr(acc)
+                    .mul(*base)
+                    .add(*nibble)
+                    .public_input(BlsScalar::zero())
+                    .build();
+                on_acc(acc);
+                acc
+            },
+        )
+    }
+
+    /// Witnesses the s-box output `y_i` and the tag `c_i` of the nibble `x`
+    /// at position `counter` through `sbox` and looks them up in the
+    /// Reinforced Concrete table, returning them along with the updated
+    /// `conditional` and the index of the lookup gate.
+    fn s_box_lookup(
+        &mut self,
+        x: Variable,
+        counter: u64,
+        conditional: bool,
+        sbox: &SboxTable,
+    ) -> (Variable, Variable, bool, usize) {
+        let v = match sbox.bound(counter) {
+            Some(v) => v,
+            None => panic!(
+                "the counter of a nibble must be in 1..=27, got {}",
+                counter
+            ),
+        };
+        let value = self.witness_canonical(x).0[0];
+
+        // The table maps the nibbles below SBOX_DOMAIN_SIZE through the s-box
+        // with a zero position column, and the larger nibbles to themselves
+        let in_domain = ct_less_than(value, SBOX_DOMAIN_SIZE as u64);
+        let y = BlsScalar::conditional_select(
+            &BlsScalar::from(value),
+            &sbox.ct_output(value),
+            in_domain,
+        );
+        let position = u64::conditional_select(&counter, &0, in_domain);
+
+        // c is 1 below v, 0 at v while the previous nibbles are at their
+        // bounds, and 2 otherwise
+        let at_bound = value.ct_eq(&v) & Choice::from(conditional as u8);
+        let c = u64::conditional_select(&2, &1, ct_less_than(value, v));
+        let c = u64::conditional_select(&c, &0, at_bound);
+        let conditional = bool::from(at_bound);
+
+        let y = self.add_input(y);
+        let c = self.add_input(BlsScalar::from(c));
+        let position_var = self.add_input(BlsScalar::from(position));
+
+        // The position column is either zero or the counter:
+        // position * (position - counter) = 0
+        self.mul_gate(
+            position_var,
+            position_var,
+            position_var,
+            BlsScalar::one(),
+            -BlsScalar::from(counter),
+            BlsScalar::zero(),
+            None,
+        );
+        let gate_index = self.circuit_size();
+        self.plookup_gate(
+            LookupRow {
+                query: x,
+                aux: position_var,
+                output: y,
+                tag: Some(c),
+            },
+            BlsScalar::zero(),
+        );
+
+        (y, c, conditional, gate_index)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::super::helper::*;
-    use crate::plookup::table::hash_tables::constants::S_I_DECOMPOSITION_MONTGOMERY;
+    use super::{
+        ct_less_than, decompose_scalar, field_modulus_nibble_bounds,
+        pack_bytes, SboxChain, BYTES_PER_ELEMENT, SPONGE_RATE, SPONGE_WIDTH,
+    };
+    use crate::constraint_system::{StandardComposer, Variable};
+    use crate::error::Error;
+    use crate::plookup::table::hash_tables::constants::{
+        bls_scalar_real_nibble, BLS_SCALAR_REAL, DECOMPOSITION_S_I,
+        SBOX_DOMAIN_SIZE, SBOX_U256, S_I_DECOMPOSITION_MONTGOMERY,
+    };
+    use crate::plookup::table::hash_tables::SboxTable;
+    use crate::plookup::{PlookupTable3Arity, PlookupTable4Arity};
+    use alloc::vec::Vec;
+    use bigint::U256 as u256;
     use dusk_bls12_381::BlsScalar;
+    use rand_core::OsRng;
+
+    /// Number of gates of [`StandardComposer::s_i_decomposition_vars`], one
+    /// per distinct `s_i`
+    fn distinct_s_i() -> usize {
+        use dusk_bytes::Serializable;
+        use hashbrown::HashSet;
+
+        S_I_DECOMPOSITION_MONTGOMERY
+            .iter()
+            .map(|s| s.to_bytes())
+            .collect::<HashSet<_>>()
+            .len()
+    }
 
     #[test]
-    fn test_decomposition() {
-        let res = gadget_tester(
-            |composer| {
-                let one = composer.add_input(BlsScalar::one());
-                let mut s_i_decomposition = [one; 27];
-                (0..27).for_each(|k| {
-                    s_i_decomposition[k] =
-                        composer.add_input(S_I_DECOMPOSITION_MONTGOMERY[k]);
-                });
-                let (output_mont, _output_reduced) =
-                    composer.decomposition_gadget(one, s_i_decomposition);
-                (1..27).for_each(|k| {
-                    composer.constrain_to_constant(
-                        output_mont[k],
-                        BlsScalar::zero(),
-                        Some(BlsScalar::zero()),
-                    );
-                });
-                // Check x_27 = 1, bearing in mind that x_1 is not in Montgomery
-                // form
-                composer.constrain_to_constant(
-                    output_mont[0],
-                    BlsScalar::one(),
-                    Some(BlsScalar::zero()),
+    fn test_s_i_decomposition_vars() {
+        let res = gadget_tester(|composer| {
+            let s_i_decomposition = composer.s_i_decomposition_vars();
+            (0..27).for_each(|k| {
+                assert_eq!(
+                    composer.variables[&s_i_decomposition[k]],
+                    S_I_DECOMPOSITION_MONTGOMERY[k]
                 );
+            });
+        });
+        assert!(res.is_ok());
 
-                let minus_three = composer.add_input(-BlsScalar::from(3));
-                let output2 = composer
-                    .decomposition_gadget(minus_three, s_i_decomposition);
-                // Expetced output derived from out of circuit version
-                let expected_output = [
-                    658, 660, 673, 663, 674, 682, 687, 683, 669, 684, 672, 666,
-                    680, 662, 686, 668, 661, 678, 692, 686, 689, 660, 690, 687,
-                    683, 674, 678, 658, 660, 673, 663, 674, 682, 687, 683, 669,
-                    684, 672, 666, 680, 662, 686, 668, 661, 678, 692, 686, 689,
-                    660, 690, 687, 683, 674, 678, 658, 660, 673, 663, 674, 682,
-                    687, 683, 669, 684, 672, 666, 680, 662, 686, 668, 661, 678,
-                    692, 686, 689, 660, 690, 687, 683, 674, 678,
-                ];
-                (0..27).for_each(|k| {
-                    composer.constrain_to_constant(
-                        output2.0[k],
-                        BlsScalar::from(expected_output[k]),
-                        Some(BlsScalar::zero()),
-                    );
-                })
+        let mut composer = StandardComposer::new();
+        let gates = composer.circuit_size();
+        let s_i_decomposition = composer.s_i_decomposition_vars();
+        assert_eq!(distinct_s_i(), 15);
+        assert_eq!(composer.circuit_size(), gates + distinct_s_i());
+
+        // The constants are shared by the following calls
+        assert_eq!(composer.s_i_decomposition_vars(), s_i_decomposition);
+        assert_eq!(composer.circuit_size(), gates + distinct_s_i());
+    }
+
+    #[test]
+    fn test_decomposition_gate_count() {
+        // The recomposition takes one gate per base but the last one and
+        // one assert_equal gate wiring it to the input, on top of the
+        // distinct constants
+        let res = gadget_tester_exact(
+            |composer| {
+                let x = composer.add_input(-BlsScalar::from(3));
+                let s_i_decomposition = composer.s_i_decomposition_vars();
+                composer.decomposition_gadget(x, s_i_decomposition);
+            },
+            15 + 27,
+        );
+        assert!(res.is_ok());
+
+        let mut composer = StandardComposer::new();
+        let x = composer.add_input(BlsScalar::from(7));
+        let gates = composer.circuit_size();
+        let s_i_decomposition = composer.s_i_decomposition_vars();
+        composer.decomposition_gadget(x, s_i_decomposition);
+        assert_gate_count(&composer, gates + distinct_s_i() + 27);
+    }
+
+    #[test]
+    fn test_decomposition_const() {
+        let res = gadget_tester_exact(
+            |composer| {
+                let x = composer.add_input(-BlsScalar::from(3));
+                let (nibbles, nibbles_reduced) =
+                    composer.decomposition_gadget_const(x);
+                nibbles.iter().zip(MINUS_THREE_NIBBLES.iter()).for_each(
+                    |(nibble, v)| {
+                        assert_eq!(
+                            composer.variables[nibble],
+                            BlsScalar::from(*v)
+                        );
+                    },
+                );
+                let s_i_decomposition = composer.s_i_decomposition_vars();
+                assert_eq!(
+                    composer.decomposition_gadget(x, s_i_decomposition).1,
+                    nibbles_reduced
+                );
             },
-            500,
+            27 + 15 + 27,
         );
         assert!(res.is_ok());
+
+        let mut composer = StandardComposer::new();
+        let x = composer.add_input(BlsScalar::from(7));
+        let gates = composer.circuit_size();
+        composer.decomposition_gadget_const(x);
+        assert_gate_count(&composer, gates + 27);
+    }
+
+    #[test]
+    fn test_decomposition_with_acc() {
+        let res = gadget_tester(|composer| {
+            let x = composer.add_input(-BlsScalar::from(3));
+            let s_i_decomposition = composer.s_i_decomposition_vars();
+            let (nibbles, _, accumulators) =
+                composer.decomposition_gadget_with_acc(x, s_i_decomposition);
+
+            // Every accumulator is a step of the Horner recomposition
+            let value = |var: &Variable| composer.variables[var];
+            let mut expected = value(&nibbles[26]);
+            (0..26).for_each(|k| {
+                expected = expected * value(&s_i_decomposition[25 - k])
+                    + value(&nibbles[25 - k]);
+                assert_eq!(value(&accumulators[k]), expected);
+            });
+            assert_eq!(value(&accumulators[25]), value(&x));
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_decomposition() {
+        let res = gadget_tester(|composer| {
+            let one = composer.add_input(BlsScalar::one());
+            let s_i_decomposition = composer.s_i_decomposition_vars();
+            let (output_mont, _output_reduced) =
+                composer.decomposition_gadget(one, s_i_decomposition);
+            (1..27).for_each(|k| {
+                composer.constrain_to_constant(
+                    output_mont[k],
+                    BlsScalar::zero(),
+                    Some(BlsScalar::zero()),
+                );
+            });
+            // Check x_27 = 1, bearing in mind that x_1 is not in Montgomery
+            // form
+            composer.constrain_to_constant(
+                output_mont[0],
+                BlsScalar::one(),
+                Some(BlsScalar::zero()),
+            );
+
+            let minus_three = composer.add_input(-BlsScalar::from(3));
+            let output2 =
+                composer.decomposition_gadget(minus_three, s_i_decomposition);
+            // Expected output derived from out of circuit version
+            let expected_output = MINUS_THREE_NIBBLES;
+            (0..27).for_each(|k| {
+                composer.constrain_to_constant(
+                    output2.0[k],
+                    BlsScalar::from(expected_output[k]),
+                    Some(BlsScalar::zero()),
+                );
+            })
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_decomposition_with_bases() {
+        let res = gadget_tester(|composer| {
+            let base = BlsScalar::from(256);
+            let bases = [base; 4];
+            let inverses = [base.invert().unwrap(); 4];
+
+            let x = composer.add_input(BlsScalar::from(0x0102_0304));
+            let nibbles = composer
+                .decomposition_gadget_with_bases(x, &bases, &inverses)
+                .unwrap();
+            assert_eq!(nibbles.len(), 4);
+            nibbles.iter().zip(&[4, 3, 2, 1]).for_each(|(nibble, v)| {
+                composer.constrain_to_constant(
+                    *nibble,
+                    BlsScalar::from(*v),
+                    None,
+                );
+            });
+
+            // The last nibble takes whatever doesn't fit in the others
+            let y = composer.add_input(BlsScalar::from(0x0501_0203));
+            let nibbles = composer
+                .decomposition_gadget_with_bases(y, &bases[..3], &inverses[..3])
+                .unwrap();
+            composer.constrain_to_constant(
+                nibbles[2],
+                BlsScalar::from(0x0501),
+                None,
+            );
+
+            assert!(matches!(
+                composer.decomposition_gadget_with_bases(
+                    x,
+                    &bases,
+                    &inverses[..3]
+                ),
+                Err(Error::InputLengthMismatch { left: 4, right: 3 })
+            ));
+
+            let zero_base = [base, BlsScalar::zero(), base];
+            assert!(matches!(
+                composer.decomposition_gadget_with_bases(
+                    x,
+                    &zero_base,
+                    &inverses[..3]
+                ),
+                Err(Error::InvalidBase { index: 1 })
+            ));
+            let wrong_inverses = [inverses[0], base, inverses[0]];
+            assert!(matches!(
+                composer.decomposition_gadget_with_bases(
+                    x,
+                    &bases[..3],
+                    &wrong_inverses
+                ),
+                Err(Error::InvalidBase { index: 1 })
+            ));
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_try_scalar_from_u256() {
+        let modulus = u256((-BlsScalar::one()).reduce().0) + u256::one();
+
+        assert_eq!(
+            try_scalar_from_u256(modulus - u256::one()).unwrap(),
+            -BlsScalar::one()
+        );
+        assert_eq!(
+            try_scalar_from_u256(u256::from(659)).unwrap(),
+            BlsScalar::from(659)
+        );
+        assert!(matches!(
+            try_scalar_from_u256(modulus),
+            Err(Error::ScalarOverflow)
+        ));
+        assert!(matches!(
+            try_scalar_from_u256(u256::max_value()),
+            Err(Error::ScalarOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_recompose_from_nibbles() {
+        let res = gadget_tester(|composer| {
+            let s_i_decomposition = composer.s_i_decomposition_vars();
+
+            let value = -BlsScalar::from(3);
+            let x = composer.add_input(value);
+            let (nibbles, _) =
+                composer.decomposition_gadget(x, s_i_decomposition);
+            let y =
+                composer.recompose_from_nibbles(&nibbles, &s_i_decomposition);
+
+            assert_eq!(composer.variables[&y], value);
+            composer.assert_equal(x, y);
+        });
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "3 nibbles need at least 2 bases, got 1")]
+    fn test_recompose_missing_bases() {
+        let mut composer = StandardComposer::new();
+        let one = composer.add_input(BlsScalar::one());
+        composer.recompose_from_nibbles(&[one; 3], &[one]);
+    }
+
+    #[test]
+    fn test_decomposition_batch() {
+        let res = gadget_tester(|composer| {
+            let s_i_decomposition = composer.s_i_decomposition_vars();
+
+            let xs: Vec<Variable> = [
+                BlsScalar::one(),
+                -BlsScalar::from(3),
+                BlsScalar::from(0xdead_beef),
+                BlsScalar::from_raw([1, 2, 3, 4]),
+            ]
+            .iter()
+            .map(|x| composer.add_input(*x))
+            .collect();
+            let batch =
+                composer.decomposition_gadget_batch(&xs, s_i_decomposition);
+            assert_eq!(batch.len(), xs.len());
+
+            xs.iter().zip(batch.iter()).for_each(|(x, nibbles)| {
+                let (single, _) =
+                    composer.decomposition_gadget(*x, s_i_decomposition);
+                (0..27).for_each(|k| {
+                    assert_eq!(
+                        composer.variables[&nibbles[k]],
+                        composer.variables[&single[k]]
+                    );
+                });
+            });
+        });
+        assert!(res.is_ok());
+    }
+
+    /// Nibbles of `-3`, from the least significant one to the most
+    /// significant one, computed independently with integer divisions of
+    /// `p - 3` by the `s_i`. They're the ones of `p - 1` from
+    /// [`BLS_SCALAR_REAL`] but for the least significant one, which is
+    /// lowered by 2 without borrowing.
+    const MINUS_THREE_NIBBLES: [u64; 27] = [
+        658, 660, 673, 663, 674, 682, 687, 683, 669, 684, 672, 666, 680, 662,
+        686, 668, 661, 678, 692, 686, 689, 660, 690, 687, 683, 674, 678,
+    ];
+
+    #[test]
+    fn test_field_modulus_nibble_bounds() {
+        let bounds = field_modulus_nibble_bounds();
+        bounds
+            .iter()
+            .zip(BLS_SCALAR_REAL.iter())
+            .for_each(|(bound, real)| {
+                assert_eq!(*bound, BlsScalar::from(real.as_u64()));
+            });
+
+        // The bounds recompose into p - 1
+        let recomposed = (0..26).rev().fold(bounds[26], |acc, k| {
+            acc * BlsScalar::from(DECOMPOSITION_S_I[k].0[0]) + bounds[k]
+        });
+        assert_eq!(recomposed, -BlsScalar::one());
+    }
+
+    #[test]
+    fn test_decompose_minus_three() {
+        let minus_three = -BlsScalar::from(3);
+        let expected = decompose_scalar(minus_three);
+        (0..27).for_each(|k| {
+            assert_eq!(expected[k], BlsScalar::from(MINUS_THREE_NIBBLES[k]));
+            if k > 0 {
+                assert_eq!(MINUS_THREE_NIBBLES[k], BLS_SCALAR_REAL[k].as_u64());
+            }
+        });
+        assert_eq!(MINUS_THREE_NIBBLES[0] + 2, BLS_SCALAR_REAL[0].as_u64());
+
+        // The gadget witnesses the same nibbles in both of its outputs, the
+        // Montgomery one holding the nibble as a field element
+        let mut composer = StandardComposer::new();
+        let s_i_decomposition = composer.s_i_decomposition_vars();
+        let x = composer.add_input(minus_three);
+        let (nibbles_mont, nibbles_reduced) =
+            composer.decomposition_gadget(x, s_i_decomposition);
+        (0..27).for_each(|k| {
+            assert_eq!(composer.variables[&nibbles_mont[k]], expected[k]);
+            assert_eq!(nibbles_reduced[k].as_u64(), MINUS_THREE_NIBBLES[k]);
+        });
+    }
+
+    #[test]
+    fn test_decompose_scalar() {
+        let mut composer = StandardComposer::new();
+        let s_i_decomposition = composer.s_i_decomposition_vars();
+
+        let mut inputs = vec![
+            BlsScalar::zero(),
+            BlsScalar::one(),
+            -BlsScalar::one(),
+            -BlsScalar::from(3),
+        ];
+        (0..16).for_each(|_| inputs.push(BlsScalar::random(&mut OsRng)));
+
+        inputs.iter().for_each(|input| {
+            let x = composer.add_input(*input);
+            let (nibbles, _) =
+                composer.decomposition_gadget(x, s_i_decomposition);
+            let expected = decompose_scalar(*input);
+            (0..27).for_each(|k| {
+                assert_eq!(composer.variables[&nibbles[k]], expected[k]);
+            });
+        });
+    }
+
+    #[test]
+    fn test_ct_less_than() {
+        let values =
+            [0, 1, 658, 659, 660, u64::MAX >> 1, u64::MAX - 1, u64::MAX];
+        values.iter().for_each(|a| {
+            values.iter().for_each(|b| {
+                assert_eq!(bool::from(ct_less_than(*a, *b)), a < b);
+            });
+        });
+    }
+
+    #[test]
+    fn test_s_box_witnesses() {
+        use core::cmp::Ordering;
+
+        // Branching definition of the witnesses of the s-box
+        fn expected(
+            sbox: &SboxTable,
+            value: u64,
+            v: u64,
+            conditional: bool,
+        ) -> (BlsScalar, u64, bool) {
+            let y =
+                sbox.output(value).unwrap_or_else(|| BlsScalar::from(value));
+            let (c, conditional) = match value.cmp(&v) {
+                Ordering::Less => (1, false),
+                Ordering::Equal if conditional => (0, true),
+                _ => (2, false),
+            };
+            (y, c, conditional)
+        }
+
+        // The witnesses match it for every nibble, position and conditional
+        // flag
+        let sbox = SboxTable::default();
+        let mut composer = StandardComposer::new();
+        for counter in [1, 14, 27].iter() {
+            let v = sbox.bound(*counter).unwrap();
+            let s = DECOMPOSITION_S_I[27 - *counter as usize].0[0];
+            for value in 0..s {
+                for conditional in [false, true].iter() {
+                    let x = composer.add_input(BlsScalar::from(value));
+                    let output = composer.s_box_and_constraints(
+                        x,
+                        *counter,
+                        *conditional,
+                        &sbox,
+                    );
+
+                    let (expected_y, expected_c, expected_conditional) =
+                        expected(&sbox, value, v, *conditional);
+                    assert_eq!(composer.variables[&output.y_i], expected_y);
+                    assert_eq!(
+                        composer.variables[&output.c_i],
+                        BlsScalar::from(expected_c)
+                    );
+                    assert_eq!(output.conditional, expected_conditional);
+                    assert_eq!(
+                        composer.variables[&output.z_i],
+                        BlsScalar::from(expected_conditional as u64)
+                    );
+
+                    // The tuple keeps the order of the fields
+                    let (y, c, got_conditional, z) = output.into();
+                    assert_eq!(
+                        (y, c, got_conditional, z),
+                        (
+                            output.y_i,
+                            output.c_i,
+                            output.conditional,
+                            output.z_i
+                        )
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_s_box_bounds() {
+        assert_eq!(SBOX_U256.len(), SBOX_DOMAIN_SIZE);
+        assert_eq!(PlookupTable3Arity::s_box_table().0.len(), SBOX_DOMAIN_SIZE);
+
+        assert_eq!(
+            bls_scalar_real_nibble(1),
+            Some(BLS_SCALAR_REAL[26].as_u64())
+        );
+        assert_eq!(
+            bls_scalar_real_nibble(27),
+            Some(BLS_SCALAR_REAL[0].as_u64())
+        );
+        assert_eq!(bls_scalar_real_nibble(0), None);
+        assert_eq!(bls_scalar_real_nibble(28), None);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the counter of a nibble must be in 1..=27, got 0"
+    )]
+    fn test_s_box_counter_zero() {
+        let mut composer = StandardComposer::new();
+        let x = composer.add_input(BlsScalar::one());
+        composer.s_box_and_constraints(x, 0, true, &SboxTable::default());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the counter of a nibble must be in 1..=27, got 28"
+    )]
+    fn test_s_box_counter_out_of_range() {
+        let mut composer = StandardComposer::new();
+        let x = composer.add_input(BlsScalar::one());
+        composer.s_box_and_constraints(x, 28, true, &SboxTable::default());
+    }
+
+    #[test]
+    fn test_s_box_traced() {
+        let mut composer = StandardComposer::new();
+        composer.append_lookup_table(&PlookupTable4Arity::create_hash_table());
+
+        let nibbles = [
+            composer.add_input(BlsScalar::from(100)),
+            composer.add_input(BlsScalar::from(BLS_SCALAR_REAL[26].as_u64())),
+        ];
+        let mut conditional = true;
+        nibbles.iter().enumerate().for_each(|(i, x)| {
+            let (output, gate_index) = composer.s_box_and_constraints_traced(
+                *x,
+                i as u64 + 1,
+                conditional,
+                &SboxTable::default(),
+            );
+            conditional = output.conditional;
+
+            assert_eq!(composer.q_lookup[gate_index], BlsScalar::one());
+            assert_eq!(composer.w_l[gate_index], *x);
+            assert_eq!(composer.w_o[gate_index], output.y_i);
+            assert_eq!(composer.w_4[gate_index], output.c_i);
+        });
+        assert!(composer.precheck_lookups().is_ok());
+    }
+
+    /// S-box mapping every nibble to itself
+    fn identity_sbox() -> [u256; SBOX_DOMAIN_SIZE] {
+        let mut sbox = [u256::zero(); SBOX_DOMAIN_SIZE];
+        sbox.iter_mut()
+            .enumerate()
+            .for_each(|(k, y)| *y = u256::from(k as u64));
+        sbox
+    }
+
+    #[test]
+    fn test_s_box_identity_table() {
+        let sbox = SboxTable::new(identity_sbox(), BLS_SCALAR_REAL);
+        let table = PlookupTable4Arity::create_hash_table_with_sbox(&sbox);
+
+        // Every nibble is mapped to itself, with the same tags as before
+        let res = gadget_plookup_tester(
+            |composer| {
+                let sbox = SboxTable::new(identity_sbox(), BLS_SCALAR_REAL);
+                [
+                    0,
+                    5,
+                    100,
+                    SBOX_DOMAIN_SIZE as u64 - 1,
+                    SBOX_DOMAIN_SIZE as u64,
+                ]
+                .iter()
+                .for_each(|nibble| {
+                    let x = composer.add_input(BlsScalar::from(*nibble));
+                    let output =
+                        composer.s_box_and_constraints(x, 27, false, &sbox);
+                    assert_eq!(
+                        composer.variables[&output.y_i],
+                        BlsScalar::from(*nibble)
+                    );
+                    assert_eq!(
+                        composer.variables[&output.c_i],
+                        BlsScalar::one()
+                    );
+                });
+            },
+            table.clone(),
+        );
+        assert!(res.is_ok());
+
+        // The default s-box doesn't match the identity table
+        let res = gadget_plookup_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(5));
+                composer.s_box_and_constraints(
+                    x,
+                    27,
+                    false,
+                    &SboxTable::default(),
+                );
+            },
+            table,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_s_box_chained() {
+        let res = gadget_plookup_tester(
+            |composer| {
+                let s_i_decomposition = composer.s_i_decomposition_vars();
+                let minus_three = composer.add_input(-BlsScalar::from(3));
+                let (nibbles, _) = composer
+                    .decomposition_gadget(minus_three, s_i_decomposition);
+
+                // Both gadgets agree on every position, from the most
+                // significant nibble to the least significant one
+                let mut conditional = true;
+                let mut chain = SboxChain::new(composer);
+                (0..27).rev().for_each(|k| {
+                    let counter = 27 - k as u64;
+                    assert_eq!(chain.counter(), counter);
+
+                    let output = composer.s_box_and_constraints(
+                        nibbles[k],
+                        counter,
+                        conditional,
+                        &SboxTable::default(),
+                    );
+                    conditional = output.conditional;
+                    let (y_chained, c_chained) =
+                        composer.s_box_chained(&mut chain, nibbles[k]);
+
+                    assert_eq!(
+                        composer.variables[&output.y_i],
+                        composer.variables[&y_chained]
+                    );
+                    assert_eq!(
+                        composer.variables[&output.c_i],
+                        composer.variables[&c_chained]
+                    );
+                    assert_eq!(
+                        composer.variables[&output.z_i],
+                        composer.variables[&chain.tight()]
+                    );
+                });
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_s_box_word() {
+        let mut composer = StandardComposer::new();
+        composer.append_lookup_table(&PlookupTable4Arity::create_hash_table());
+        let s_i_decomposition = composer.s_i_decomposition_vars();
+
+        // -3 shares every nibble of `p - 1` but the least significant one
+        [-BlsScalar::from(3), BlsScalar::from(5)]
+            .iter()
+            .for_each(|x| {
+                let x = composer.add_input(*x);
+                let (nibbles, _) =
+                    composer.decomposition_gadget(x, s_i_decomposition);
+
+                let gates = composer.circuit_size();
+                let word = composer.s_box_word(&nibbles, &SboxTable::default());
+                let word_gates = composer.circuit_size() - gates;
+
+                let mut conditional = true;
+                (0..27).rev().for_each(|k| {
+                    let output = composer.s_box_and_constraints(
+                        nibbles[k],
+                        27 - k as u64,
+                        conditional,
+                        &SboxTable::default(),
+                    );
+                    conditional = output.conditional;
+
+                    assert_eq!(
+                        composer.variables[&word.y[k]],
+                        composer.variables[&output.y_i]
+                    );
+                    assert_eq!(
+                        composer.variables[&word.c[k]],
+                        composer.variables[&output.c_i]
+                    );
+                    assert_eq!(
+                        composer.variables[&word.z[k]],
+                        composer.variables[&output.z_i]
+                    );
+                });
+                assert_eq!(word.conditional, conditional);
+                assert_eq!(composer.circuit_size() - gates, 2 * word_gates);
+            });
+        assert!(composer.precheck_lookups().is_ok());
+    }
+
+    /// Returns the nibble equal to the most significant nibble of `p - 1`,
+    /// followed by a nibble larger than the second most significant one, so
+    /// the decomposition exceeds `p - 1`
+    fn overflowing_nibbles(composer: &mut StandardComposer) -> [Variable; 2] {
+        let first = BLS_SCALAR_REAL[26].as_u64();
+        let second = BLS_SCALAR_REAL[25].as_u64() + 1;
+        [
+            composer.add_input(BlsScalar::from(first)),
+            composer.add_input(BlsScalar::from(second)),
+        ]
+    }
+
+    #[test]
+    fn test_assert_canonical_decomposition() {
+        // Should pass for the decompositions of any element, up to p - 1
+        let res = gadget_plookup_tester(
+            |composer| {
+                let s_i_decomposition = composer.s_i_decomposition_vars();
+                [BlsScalar::from(0xdead_beef), -BlsScalar::one()]
+                    .iter()
+                    .for_each(|value| {
+                        let x = composer.add_input(*value);
+                        let (nibbles, _) =
+                            composer.decomposition_gadget(x, s_i_decomposition);
+                        composer.assert_canonical_decomposition(&nibbles);
+                    });
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+
+        // Should fail as the nibbles of p - 1 with the least significant one
+        // increased represent p, which is zero in the field
+        let res = gadget_plookup_tester(
+            |composer| {
+                let mut bounds = field_modulus_nibble_bounds();
+                bounds[0] += BlsScalar::one();
+                let mut nibbles = [composer.zero_var; 27];
+                nibbles.iter_mut().zip(bounds.iter()).for_each(
+                    |(nibble, bound)| *nibble = composer.add_input(*bound),
+                );
+                composer.assert_canonical_decomposition(&nibbles);
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_s_box_chained_overflow() {
+        // The caller-managed conditional doesn't stop the second nibble from
+        // exceeding the one of `p - 1`
+        let res = gadget_plookup_tester(
+            |composer| {
+                let nibbles = overflowing_nibbles(composer);
+                let first = composer.s_box_and_constraints(
+                    nibbles[0],
+                    1,
+                    true,
+                    &SboxTable::default(),
+                );
+                composer.s_box_and_constraints(
+                    nibbles[1],
+                    2,
+                    first.conditional,
+                    &SboxTable::default(),
+                );
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+
+        // The chain tracks the comparison in the circuit and catches it
+        let res = gadget_plookup_tester(
+            |composer| {
+                let nibbles = overflowing_nibbles(composer);
+                let mut chain = SboxChain::new(composer);
+                composer.s_box_chained(&mut chain, nibbles[0]);
+                composer.s_box_chained(&mut chain, nibbles[1]);
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+    }
+
+    /// Out of circuit bar function, decomposing `x` by repeated division by
+    /// the `s_i`
+    fn reference_permutation(x: BlsScalar) -> BlsScalar {
+        let sbox = |nibble: u64| match nibble < SBOX_DOMAIN_SIZE as u64 {
+            true => BlsScalar::from_raw(SBOX_U256[nibble as usize].0),
+            false => BlsScalar::from(nibble),
+        };
+
+        let mut intermediate = u256(x.reduce().0);
+        let mut nibbles = [0u64; 27];
+        (0..26).for_each(|k| {
+            let s = u256::from(DECOMPOSITION_S_I[k].0[0]);
+            nibbles[k] = (intermediate % s).as_u64();
+            intermediate = intermediate / s;
+        });
+        nibbles[26] = intermediate.as_u64();
+
+        (0..26).rev().fold(sbox(nibbles[26]), |acc, k| {
+            acc * BlsScalar::from(DECOMPOSITION_S_I[k].0[0]) + sbox(nibbles[k])
+        })
+    }
+
+    #[test]
+    fn test_zelbet_permutation() {
+        let res = gadget_plookup_tester(
+            |composer| {
+                let s_i_decomposition = composer.s_i_decomposition_vars();
+
+                let inputs = [
+                    -BlsScalar::from(3),
+                    BlsScalar::zero(),
+                    BlsScalar::one(),
+                    BlsScalar::from(0xdead_beef),
+                    BlsScalar::from_raw([1, 2, 3, 4]),
+                ];
+                inputs.iter().for_each(|input| {
+                    let x = composer.add_input(*input);
+                    let output =
+                        composer.zelbet_permutation(x, s_i_decomposition);
+
+                    let expected = reference_permutation(*input);
+                    assert_eq!(composer.variables[&output], expected);
+                    composer.constrain_to_constant(
+                        output,
+                        expected,
+                        Some(BlsScalar::zero()),
+                    );
+                });
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_zelbet_permutation_wired() {
+        // The output of a permutation is wired to the input of the next one
+        // with assert_equal, so a different input must fail
+        fn chain(composer: &mut StandardComposer, offset: BlsScalar) {
+            let s_i_decomposition = composer.s_i_decomposition_vars();
+            let x = composer.add_input(BlsScalar::from(7));
+            let y = composer.zelbet_permutation(x, s_i_decomposition);
+            let value = composer.variables[&y] + offset;
+            let next = composer.add_input(value);
+            composer.assert_equal(y, next);
+            composer.zelbet_permutation(next, s_i_decomposition);
+        }
+
+        let res = gadget_plookup_tester(
+            |composer| chain(composer, BlsScalar::zero()),
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+
+        let res = gadget_plookup_tester(
+            |composer| chain(composer, BlsScalar::one()),
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_zelbet_permutation_other_input() {
+        use crate::commitment_scheme::kzg10::PublicParameters;
+        use crate::proof_system::{Prover, Verifier};
+
+        // The circuit doesn't depend on the input, so it can be preprocessed
+        // with one input and then proven and verified with another one
+        fn circuit(composer: &mut StandardComposer, input: BlsScalar) {
+            composer
+                .append_lookup_table(&PlookupTable4Arity::create_hash_table());
+            let s_i_decomposition = composer.s_i_decomposition_vars();
+            let x = composer.add_input(input);
+            composer.zelbet_permutation(x, s_i_decomposition);
+        }
+
+        let mut prover = Prover::new(b"zelbet");
+        circuit(prover.mut_cs(), BlsScalar::from(7));
+        let size = prover.cs.next_power_of_two_size();
+        let public_parameters =
+            PublicParameters::setup(2 * size, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(2 * size).unwrap();
+        prover.preprocess(&ck).unwrap();
+
+        prover.clear_witness();
+        circuit(prover.mut_cs(), -BlsScalar::from(3));
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"zelbet");
+        circuit(verifier.mut_cs(), BlsScalar::from(0xdead_beef));
+        let (ck, vk) = public_parameters
+            .trim(verifier.cs.next_power_of_two_size())
+            .unwrap();
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+    }
+
+    #[test]
+    fn test_zelbet_permutation_rounds() {
+        let res = gadget_plookup_tester(
+            |composer| {
+                let s_i_decomposition = composer.s_i_decomposition_vars();
+
+                let input = BlsScalar::from(0xdead_beef);
+                let x = composer.add_input(input);
+
+                assert_eq!(
+                    composer.zelbet_permutation_rounds(x, s_i_decomposition, 0),
+                    x
+                );
+
+                let output =
+                    composer.zelbet_permutation_rounds(x, s_i_decomposition, 2);
+                let once = composer.zelbet_permutation(x, s_i_decomposition);
+                let twice =
+                    composer.zelbet_permutation(once, s_i_decomposition);
+
+                let expected =
+                    reference_permutation(reference_permutation(input));
+                assert_eq!(composer.variables[&output], expected);
+                assert_eq!(composer.variables[&twice], expected);
+                composer.assert_equal(output, twice);
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+    }
+
+    /// Decomposition, s-box and recomposition cycle of a round of
+    /// [`StandardComposer::fold_rounds`]
+    fn bar_round(
+        composer: &mut StandardComposer,
+        x: Variable,
+        s_i_decomposition: &[Variable; 27],
+    ) -> Variable {
+        let (nibbles, _) = composer.decomposition_gadget(x, *s_i_decomposition);
+        let outputs = composer
+            .s_box_word(&nibbles, &SboxTable::REINFORCED_CONCRETE)
+            .y;
+        composer.recompose_from_nibbles(&outputs, s_i_decomposition)
+    }
+
+    #[test]
+    fn test_fold_rounds() {
+        let input = BlsScalar::from(0xdead_beef);
+        let table = PlookupTable4Arity::create_hash_table();
+
+        let mut manual = StandardComposer::new();
+        manual.append_lookup_table(&table);
+        let s_i_decomposition = manual.s_i_decomposition_vars();
+        let mut state = manual.add_input(input);
+        for _ in 0..3 {
+            state = bar_round(&mut manual, state, &s_i_decomposition);
+        }
+
+        let mut folded = StandardComposer::new();
+        folded.append_lookup_table(&table);
+        let s_i_decomposition = folded.s_i_decomposition_vars();
+        let x = folded.add_input(input);
+        let output = folded.fold_rounds(x, 3, |composer, state| {
+            bar_round(composer, state, &s_i_decomposition)
+        });
+
+        assert_eq!(folded.circuit_size(), manual.circuit_size());
+        assert_eq!(folded.w_l, manual.w_l);
+        assert_eq!(folded.w_r, manual.w_r);
+        assert_eq!(folded.w_o, manual.w_o);
+        assert_eq!(folded.w_4, manual.w_4);
+        assert_eq!(output, state);
+        assert_eq!(folded.variables[&output], manual.variables[&state]);
+
+        // Zero rounds never call the round
+        assert_eq!(folded.fold_rounds(x, 0, |_, _| unreachable!()), x);
+
+        let res = gadget_plookup_tester(
+            |composer| {
+                let s_i_decomposition = composer.s_i_decomposition_vars();
+                let x = composer.add_input(BlsScalar::from(0xdead_beef));
+                composer.fold_rounds(x, 3, |composer, state| {
+                    bar_round(composer, state, &s_i_decomposition)
+                });
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+    }
+
+    /// Out of circuit Zelbet sponge, see
+    /// [`StandardComposer::zelbet_sponge_hash`]
+    fn reference_sponge_hash(inputs: &[BlsScalar]) -> BlsScalar {
+        let mut padded = inputs.to_vec();
+        padded.push(BlsScalar::one());
+        while padded.len() % SPONGE_RATE != 0 {
+            padded.push(BlsScalar::zero());
+        }
+
+        let mut state = [BlsScalar::zero(); SPONGE_WIDTH];
+        padded.chunks(SPONGE_RATE).for_each(|block| {
+            state
+                .iter_mut()
+                .zip(block.iter())
+                .for_each(|(element, input)| {
+                    *element += *input;
+                });
+            let mut bars = state;
+            bars.iter_mut()
+                .for_each(|element| *element = reference_permutation(*element));
+            (0..SPONGE_WIDTH).for_each(|i| {
+                state[i] = BlsScalar::from(2) * bars[i]
+                    + bars[(i + 1) % SPONGE_WIDTH]
+                    + bars[(i + 2) % SPONGE_WIDTH];
+            });
+        });
+
+        state[0]
+    }
+
+    /// Hashes `inputs` in the circuit, checking the digest against the out
+    /// of circuit sponge
+    fn sponge_hash(
+        composer: &mut StandardComposer,
+        inputs: &[BlsScalar],
+    ) -> BlsScalar {
+        let inputs_var: Vec<Variable> = inputs
+            .iter()
+            .map(|input| composer.add_input(*input))
+            .collect();
+        let digest = composer.zelbet_sponge_hash(&inputs_var);
+
+        let expected = reference_sponge_hash(inputs);
+        assert_eq!(composer.variables[&digest], expected);
+        composer.constrain_to_constant(
+            digest,
+            expected,
+            Some(BlsScalar::zero()),
+        );
+        expected
+    }
+
+    #[test]
+    fn test_zelbet_sponge_hash() {
+        let res = gadget_plookup_tester(
+            |composer| {
+                let inputs = [
+                    BlsScalar::from(7),
+                    -BlsScalar::from(3),
+                    BlsScalar::from(0xdead_beef),
+                ];
+
+                // The empty input still has a padded block to absorb
+                let empty = sponge_hash(composer, &[]);
+                assert_ne!(empty, BlsScalar::zero());
+
+                // Hashing the same inputs twice gives the same digest
+                let first = sponge_hash(composer, &inputs);
+                let second = sponge_hash(composer, &inputs);
+                assert_eq!(first, second);
+                assert_ne!(first, empty);
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        let res = gadget_plookup_tester(
+            |composer| {
+                let values = [BlsScalar::from(7), BlsScalar::from(11)];
+                let inputs: Vec<Variable> = values
+                    .iter()
+                    .map(|value| composer.add_input(*value))
+                    .collect();
+
+                let first = composer.hash_to_field(1, &inputs);
+                let second = composer.hash_to_field(2, &inputs);
+                assert_ne!(
+                    composer.variables[&first],
+                    composer.variables[&second]
+                );
+
+                // The tag is absorbed before the inputs
+                let expected = reference_sponge_hash(&[
+                    BlsScalar::from(2),
+                    values[0],
+                    values[1],
+                ]);
+                assert_eq!(composer.variables[&second], expected);
+                assert_ne!(
+                    composer.variables[&second],
+                    reference_sponge_hash(&values)
+                );
+                composer.constrain_to_constant(
+                    second,
+                    expected,
+                    Some(BlsScalar::zero()),
+                );
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_pack_bytes() {
+        assert_eq!(pack_bytes(&[]), vec![BlsScalar::one()]);
+        assert_eq!(pack_bytes(b"abc"), vec![BlsScalar::from(0x0163_6261)]);
+
+        // A full element is followed by an element holding the padding
+        let full = [0xffu8; BYTES_PER_ELEMENT];
+        let packed = pack_bytes(&full);
+        assert_eq!(packed.len(), 2);
+        assert_eq!(packed[1], BlsScalar::one());
+        assert_eq!(
+            packed[0] + BlsScalar::one(),
+            BlsScalar::from(2).pow(&[8 * BYTES_PER_ELEMENT as u64, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        let res = gadget_plookup_tester(
+            |composer| {
+                // The digest of "abc" is the one of its packed element
+                let digest = composer.hash_bytes(b"abc");
+                let expected =
+                    reference_sponge_hash(&[BlsScalar::from(0x0163_6261)]);
+                assert_eq!(composer.variables[&digest], expected);
+                // Known vector, which changes with the packing, the padding
+                // or the permutation of the sponge
+                assert_eq!(
+                    expected,
+                    BlsScalar::from_raw([
+                        0xecf5_54ab_7cf8_e4e4,
+                        0x275a_2a90_dd70_3cae,
+                        0x0317_3c25_41fa_6a85,
+                        0x0a42_5ced_618c_a22d,
+                    ])
+                );
+                composer.constrain_to_constant(
+                    digest,
+                    expected,
+                    Some(BlsScalar::zero()),
+                );
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+
+        assert_eq!(
+            reference_sponge_hash(&pack_bytes(b"")),
+            BlsScalar::from_raw([
+                0xecf5_54ab_7342_d10c,
+                0x275a_2a90_dd70_3cae,
+                0x0317_3c25_41fa_6a85,
+                0x0a42_5ced_618c_a22d,
+            ])
+        );
+
+        // Byte strings which would share their elements with zero padding,
+        // or differ only past the first element, have distinct digests
+        let mut composer = StandardComposer::new();
+        let long = [7u8; BYTES_PER_ELEMENT + 1];
+        let inputs: [&[u8]; 6] = [
+            b"",
+            b"\0",
+            b"abc",
+            b"abc\0",
+            &long[..BYTES_PER_ELEMENT],
+            &long,
+        ];
+        let digests: Vec<BlsScalar> = inputs
+            .iter()
+            .map(|bytes| {
+                let digest = composer.hash_bytes(bytes);
+                composer.variables[&digest]
+            })
+            .collect();
+        digests.iter().enumerate().for_each(|(i, digest)| {
+            assert!(!digests[i + 1..].contains(digest));
+        });
+    }
+
+    #[test]
+    fn test_zelbet_sponge_padding() {
+        let res = gadget_plookup_tester(
+            |composer| {
+                let a = BlsScalar::from(42);
+
+                // Inputs which would share their block with zero padding
+                assert_ne!(
+                    sponge_hash(composer, &[]),
+                    sponge_hash(composer, &[BlsScalar::zero()])
+                );
+                assert_ne!(
+                    sponge_hash(composer, &[a]),
+                    sponge_hash(composer, &[a, BlsScalar::zero()])
+                );
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_zelbet_sponge_duplex() {
+        let res = gadget_plookup_tester(
+            |composer| {
+                let a = BlsScalar::from(42);
+                let x = composer.add_input(a);
+                let one = composer.add_input(BlsScalar::one());
+
+                // A single padded block gives the one-shot digest
+                let mut sponge = ZelbetSponge::new(composer);
+                sponge.absorb(composer, &[x, one]);
+                let digest = sponge.squeeze(composer, 1)[0];
+                assert_eq!(
+                    composer.variables[&digest],
+                    sponge_hash(composer, &[a])
+                );
+
+                // Squeezes can be split across the end of the rate
+                let mut split = sponge.clone();
+                let all = sponge.squeeze(composer, 3);
+                let mut parts = split.squeeze(composer, 1);
+                parts.extend(split.squeeze(composer, 2));
+                let values = |composer: &StandardComposer,
+                              vars: &[Variable]| {
+                    vars.iter()
+                        .map(|var| composer.variables[var])
+                        .collect::<Vec<_>>()
+                };
+                assert_eq!(values(composer, &all), values(composer, &parts));
+
+                // Absorbing after squeezing changes the next outputs
+                sponge.absorb(composer, &[x]);
+                let next = sponge.squeeze(composer, 1)[0];
+                assert_ne!(
+                    composer.variables[&next],
+                    composer.variables[&all[2]]
+                );
+            },
+            PlookupTable4Arity::create_hash_table(),
+        );
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn test_gadget_errors() {
+        let sbox = SboxTable::default();
+        let mut composer = StandardComposer::new();
+        let x = composer.add_input(BlsScalar::from(5));
+        let gates = composer.circuit_size();
+
+        assert!(matches!(
+            composer.try_zelbet_sponge_hash(&[x]),
+            Err(Error::TableMissing)
+        ));
+        assert!(matches!(
+            composer.try_s_box_and_constraints(x, 1, true, &sbox),
+            Err(Error::TableMissing)
+        ));
+        assert_eq!(composer.circuit_size(), gates);
+
+        composer.append_lookup_table(&PlookupTable4Arity::create_hash_table());
+        for counter in [0, 28].iter() {
+            assert!(matches!(
+                composer.try_s_box_and_constraints(x, *counter, true, &sbox),
+                Err(Error::CounterOutOfRange { counter: c }) if c == *counter
+            ));
+        }
+        assert_eq!(composer.circuit_size(), gates);
+
+        assert!(composer
+            .try_s_box_and_constraints(x, 1, true, &sbox)
+            .is_ok());
+        assert!(composer.try_zelbet_sponge_hash(&[x]).is_ok());
     }
 }
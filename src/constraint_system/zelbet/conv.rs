@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Conversions between [`BlsScalar`]s, `u256` integers and byte strings.
+//!
+//! A [`BlsScalar`] stores its limbs in Montgomery form, so its inner `.0`
+//! limbs are not the integer it represents. The integers and the bytes
+//! handled here are always canonical, that is the little-endian
+//! representation of the integer itself, which is the form of the table
+//! entries and of [`BlsScalar::from_raw`].
+
+use crate::error::Error;
+use bigint::U256 as u256;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+
+/// Converts the canonical integer `x` into a [`BlsScalar`], reducing it
+/// modulo the field order. See [`try_scalar_from_u256`] to reject the
+/// integers which are not below it instead.
+///
+/// [`try_scalar_from_u256`]: super::try_scalar_from_u256
+pub fn u256_to_scalar(x: u256) -> BlsScalar {
+    BlsScalar::from_raw(x.0)
+}
+
+/// Returns the canonical integer represented by `x`, which is always below
+/// the field order.
+pub fn scalar_to_u256(x: BlsScalar) -> u256 {
+    u256(x.reduce().0)
+}
+
+/// Reads the canonical integer of the little-endian `bytes` into a
+/// [`BlsScalar`], the missing most significant bytes being zero.
+///
+/// Returns [`Error::ScalarOverflow`] if there are more than
+/// [`BlsScalar::SIZE`] bytes, or if the integer isn't below the field order.
+pub fn bytes_to_scalar(bytes: &[u8]) -> Result<BlsScalar, Error> {
+    if bytes.len() > BlsScalar::SIZE {
+        return Err(Error::ScalarOverflow);
+    }
+
+    let mut canonical = [0u8; BlsScalar::SIZE];
+    canonical[..bytes.len()].copy_from_slice(bytes);
+    BlsScalar::from_bytes(&canonical).map_err(|_| Error::ScalarOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_conversions_round_trip() {
+        let scalars = [
+            BlsScalar::zero(),
+            BlsScalar::one(),
+            BlsScalar::from(700),
+            -BlsScalar::one(),
+            BlsScalar::random(&mut OsRng),
+        ];
+        scalars.iter().for_each(|scalar| {
+            let integer = scalar_to_u256(*scalar);
+            assert_eq!(u256_to_scalar(integer), *scalar);
+
+            let mut bytes = [0u8; BlsScalar::SIZE];
+            integer.to_little_endian(&mut bytes);
+            assert_eq!(bytes, scalar.to_bytes());
+            assert_eq!(bytes_to_scalar(&bytes).unwrap(), *scalar);
+        });
+    }
+
+    #[test]
+    fn test_conversions_are_canonical() {
+        // The integers are not the Montgomery limbs
+        let seven_hundred = BlsScalar::from(700);
+        assert_eq!(u256_to_scalar(u256::from(700)), seven_hundred);
+        assert_eq!(scalar_to_u256(seven_hundred), u256::from(700));
+        assert_ne!(u256(seven_hundred.0), u256::from(700));
+
+        // Short byte strings are zero extended
+        assert_eq!(bytes_to_scalar(&[0xbc, 0x02]).unwrap(), seven_hundred);
+        assert_eq!(bytes_to_scalar(&[]).unwrap(), BlsScalar::zero());
+    }
+
+    #[test]
+    fn test_conversions_overflow() {
+        let modulus = scalar_to_u256(-BlsScalar::one()) + u256::one();
+        assert_eq!(u256_to_scalar(modulus), BlsScalar::zero());
+        assert_eq!(u256_to_scalar(modulus + u256::from(5)), BlsScalar::from(5));
+
+        let mut bytes = [0u8; BlsScalar::SIZE];
+        modulus.to_little_endian(&mut bytes);
+        assert!(matches!(
+            bytes_to_scalar(&bytes),
+            Err(Error::ScalarOverflow)
+        ));
+        assert!(matches!(
+            bytes_to_scalar(&[0u8; BlsScalar::SIZE + 1]),
+            Err(Error::ScalarOverflow)
+        ));
+    }
+}
@@ -6,6 +6,7 @@
 
 //! A collection of all possible errors encountered in PLONK.
 
+use dusk_bls12_381::BlsScalar;
 use dusk_bytes::Error as DuskBytesError;
 
 /// Defines all possible errors that can be encountered in PLONK.
@@ -32,14 +33,89 @@ pub enum Error {
     UninitializedPIGenerator,
     /// PublicInput serialization error
     InvalidPublicInputBytes,
+    /// This error occurs when a named Public Input of the circuit isn't given
+    /// a value.
+    MissingPublicInput,
+    /// This error occurs when a value is given for a Public Input name that
+    /// the circuit doesn't declare, or when a gate without a Public Input is
+    /// hidden.
+    UnknownPublicInput,
+    /// This error occurs when a named Public Input is given more than one
+    /// value.
+    DuplicatePublicInput,
+    /// This error occurs when a Public Input is at a position past the size
+    /// of the circuit.
+    PublicInputOutOfRange {
+        /// Position of the Public Input
+        position: usize,
+        /// Size of the circuit
+        size: usize,
+    },
+    /// This error occurs when the same variable is bound to Public Inputs of
+    /// different values at two gates.
+    ConflictingPublicInputs {
+        /// Gate of the first Public Input
+        first: usize,
+        /// Gate of the Public Input conflicting with the first one
+        second: usize,
+    },
     /// This error occurs when the Prover structure already contains a
     /// preprocessed circuit inside, but you call preprocess again.
     CircuitAlreadyPreprocessed,
+    /// This error occurs when a proof generated for one circuit is verified
+    /// against the verifier key of a different circuit.
+    CircuitMismatch,
+
+    // Gadget errors
+    /// This error occurs when a gadget receives two slices of inputs which
+    /// should have the same length, but don't.
+    InputLengthMismatch {
+        /// Length of the first slice
+        left: usize,
+        /// Length of the second slice
+        right: usize,
+    },
+    /// This error occurs when a
+    /// [`Variable`](crate::constraint_system::Variable) constrained to a
+    /// constant holds a different value in the witness.
+    ConstantMismatch,
+    /// This error occurs when a base given to a decomposition gadget is zero
+    /// or doesn't match the inverse given along with it.
+    InvalidBase {
+        /// Index of the base
+        index: usize,
+    },
+    /// This error occurs when the counter of a nibble given to an s-box
+    /// gadget is not in `1..=27`.
+    CounterOutOfRange {
+        /// Counter given to the gadget
+        counter: u64,
+    },
+    /// This error occurs when a gate of the circuit isn't satisfied by the
+    /// witness.
+    Unsatisfiable {
+        /// Index of the first unsatisfied gate
+        gate: usize,
+        /// Values of the wires of the gate, ordered as
+        /// `(w_l, w_r, w_o, w_4)`
+        wires: [BlsScalar; 4],
+    },
 
     // Preprocessing errors
     /// This error occurs when an error triggers during the preprocessing
     /// stage.
     MismatchedPolyLen,
+    /// This error occurs when a loaded key was preprocessed for a circuit of
+    /// another size.
+    CircuitSizeMismatch {
+        /// Size the key is loaded for
+        expected: usize,
+        /// Size the key was preprocessed for
+        found: usize,
+    },
+    /// This error occurs when a loaded prover key wasn't preprocessed along
+    /// with the verifier key or out of the public parameters it's loaded for.
+    KeyMismatch,
 
     // KZG10 errors
     /// This error occurs when the user tries to create PublicParameters
@@ -60,6 +136,14 @@ pub enum Error {
     /// This error occurs when the pairing check fails at being equal to the
     /// Identity point.
     PairingCheckFailure,
+    /// This error occurs when loaded PublicParameters don't have a degree
+    /// large enough for the circuit they are loaded for.
+    SrsTooSmall {
+        /// Maximum degree of the PublicParameters
+        degree: usize,
+        /// Degree required by the circuit
+        required: usize,
+    },
 
     // Serialization errors
     /// Dusk-bytes serialization error
@@ -72,12 +156,40 @@ pub enum Error {
     /// This error occurs when a malformed BLS scalar is decoded from a byte
     /// array.
     BlsScalarMalformed,
+    /// This error occurs when an integer is converted into a BLS scalar but
+    /// isn't smaller than the field modulus.
+    ScalarOverflow,
+    /// This error occurs when a serialized proof was encoded with a version
+    /// of the format that isn't supported.
+    UnsupportedProofVersion {
+        /// Version found in the encoding
+        version: u8,
+    },
 
     // Plookup errors
+    /// This error occurs when a plookup gate is added to a composer which has
+    /// no lookup table.
+    TableMissing,
     /// Query element not found in lookup table
     ElementNotIndexed,
+    /// This error occurs when a circuit preprocessed in strict plookup mode
+    /// has more plookup gates than lookup table rows.
+    LookupTableTooSmall {
+        /// Number of plookup gates
+        gates: usize,
+        /// Number of lookup table rows
+        rows: usize,
+    },
     /// Cannot commit to table column polynomial
     TablePreProcessingError,
+    /// This error occurs when a lookup table is requested with more bits
+    /// than supported.
+    TableTooLarge {
+        /// Requested number of bits
+        bits: u32,
+        /// Maximum supported number of bits
+        max_bits: u32,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -105,12 +217,71 @@ impl std::fmt::Display for Error {
             Self::InvalidPublicInputBytes => {
                 write!(f, "invalid public input bytes")
             }
+            Self::MissingPublicInput => {
+                write!(f, "a named public input was not given a value")
+            }
+            Self::UnknownPublicInput => {
+                write!(f, "a value was given for an undeclared public input")
+            }
+            Self::DuplicatePublicInput => {
+                write!(f, "a named public input was given more than one value")
+            }
+            Self::PublicInputOutOfRange { position, size } => write!(
+                f,
+                "public input at position {} in a circuit of size {}",
+                position, size
+            ),
+            Self::ConflictingPublicInputs { first, second } => write!(
+                f,
+                "a variable is bound to different public inputs at gates {} \
+                 and {}",
+                first, second
+            ),
+            Self::InputLengthMismatch { left, right } => write!(
+                f,
+                "gadget inputs have different lengths: {} and {}",
+                left, right
+            ),
+            Self::ConstantMismatch => {
+                write!(f, "the witness doesn't match the constrained constant")
+            }
+            Self::InvalidBase { index } => write!(
+                f,
+                "the base {} of the decomposition is zero or doesn't match \
+                 its inverse",
+                index
+            ),
+            Self::CounterOutOfRange { counter } => write!(
+                f,
+                "the counter of a nibble must be in 1..=27, got {}",
+                counter
+            ),
+            Self::Unsatisfiable { gate, wires } => write!(
+                f,
+                "the gate {} is not satisfied by the witness (w_l: {:?}, \
+                 w_r: {:?}, w_o: {:?}, w_4: {:?})",
+                gate, wires[0], wires[1], wires[2], wires[3]
+            ),
             Self::MismatchedPolyLen => {
                 write!(f, "the length of the wires is not the same")
             }
+            Self::CircuitSizeMismatch { expected, found } => write!(
+                f,
+                "the key was preprocessed for a circuit of size {} instead \
+                 of {}",
+                found, expected
+            ),
+            Self::KeyMismatch => write!(
+                f,
+                "the prover key doesn't match the verifier key or the \
+                 public parameters"
+            ),
             Self::CircuitAlreadyPreprocessed => {
                 write!(f, "circuit has already been preprocessed")
             }
+            Self::CircuitMismatch => {
+                write!(f, "proof was generated for a different circuit")
+            }
             Self::DegreeIsZero => {
                 write!(f, "cannot create PublicParameters with max degree 0")
             }
@@ -129,18 +300,42 @@ impl std::fmt::Display for Error {
                 write!(f, "cannot commit to polynomial of zero degree")
             }
             Self::PairingCheckFailure => write!(f, "pairing check failed"),
+            Self::SrsTooSmall { degree, required } => write!(
+                f,
+                "public parameters of degree {} are too small for degree {}",
+                degree, required
+            ),
             Self::NotEnoughBytes => write!(f, "not enough bytes left to read"),
             Self::PointMalformed => write!(f, "BLS point bytes malformed"),
             Self::BlsScalarMalformed => write!(f, "BLS scalar bytes malformed"),
+            Self::ScalarOverflow => {
+                write!(f, "integer doesn't fit in the BLS scalar field")
+            }
+            Self::UnsupportedProofVersion { version } => {
+                write!(f, "unsupported proof encoding version {}", version)
+            }
             Self::BytesError(err) => write!(f, "{:?}", err),
+            Self::TableMissing => {
+                write!(f, "no lookup table was added to the composer")
+            }
             Self::ElementNotIndexed => write!(
                 f,
                 "the requested element was not indexed in the lookup table"
             ),
+            Self::LookupTableTooSmall { gates, rows } => write!(
+                f,
+                "{} plookup gates outnumber the {} lookup table rows",
+                gates, rows
+            ),
             Self::TablePreProcessingError => write!(
                 f,
                 "cannot commit to one of the table column polynomials"
             ),
+            Self::TableTooLarge { bits, max_bits } => write!(
+                f,
+                "lookup table of {} bits exceeds the maximum of {} bits",
+                bits, max_bits
+            ),
         }
     }
 }
@@ -18,8 +18,13 @@ use dusk_bytes::{DeserializableSlice, Serializable};
 /// Defines a domain over which finite field (I)FFTs can be performed. Works
 /// only for fields that have a large multiplicative subgroup of size that is
 /// a power-of-2.
+///
+/// The domain of a circuit is returned by [`StandardComposer::domain`].
+///
+/// [`StandardComposer::domain`]:
+/// crate::constraint_system::StandardComposer::domain
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub(crate) struct EvaluationDomain {
+pub struct EvaluationDomain {
     /// The size of the domain.
     pub(crate) size: u64,
     /// `log_2(self.size)`.
@@ -91,7 +96,7 @@ pub(crate) mod alloc {
     use ::alloc::vec::Vec;
     use core::ops::MulAssign;
     use dusk_bls12_381::{GENERATOR, ROOT_OF_UNITY, TWO_ADACITY};
-    #[cfg(feature = "std")]
+    #[cfg(feature = "parallel")]
     use rayon::prelude::*;
 
     impl EvaluationDomain {
@@ -131,10 +136,16 @@ pub(crate) mod alloc {
         }
 
         /// Return the size of `self`.
-        pub(crate) fn size(&self) -> usize {
+        pub fn size(&self) -> usize {
             self.size as usize
         }
 
+        /// Returns the generator of the subgroup of the roots of unity of
+        /// `self`, whose powers are the elements of the domain.
+        pub fn group_generator(&self) -> BlsScalar {
+            self.group_gen
+        }
+
         /// Compute a FFT.
         pub(crate) fn fft(&self, coeffs: &[BlsScalar]) -> Vec<BlsScalar> {
             let mut coeffs = coeffs.to_vec();
@@ -161,10 +172,10 @@ pub(crate) mod alloc {
             evals.resize(self.size(), BlsScalar::zero());
             best_fft(evals, self.group_gen_inv, self.log_size_of_group);
 
-            #[cfg(not(feature = "std"))]
+            #[cfg(not(feature = "parallel"))]
             evals.iter_mut().for_each(|val| *val *= &self.size_inv);
 
-            #[cfg(feature = "std")]
+            #[cfg(feature = "parallel")]
             evals.par_iter_mut().for_each(|val| *val *= &self.size_inv);
         }
 
@@ -227,7 +238,7 @@ pub(crate) mod alloc {
                 }
                 u
             } else {
-                use crate::util::batch_inversion;
+                use crate::util::batch_invert;
 
                 let mut l = (t_size - one) * self.size_inv;
                 let mut r = one;
@@ -240,14 +251,14 @@ pub(crate) mod alloc {
                     r *= &self.group_gen;
                 }
 
-                batch_inversion(u.as_mut_slice());
+                batch_invert(u.as_mut_slice());
 
-                #[cfg(not(feature = "std"))]
+                #[cfg(not(feature = "parallel"))]
                 u.iter_mut().zip(ls).for_each(|(tau_minus_r, l)| {
                     *tau_minus_r = l * *tau_minus_r;
                 });
 
-                #[cfg(feature = "std")]
+                #[cfg(feature = "parallel")]
                 u.par_iter_mut().zip(ls).for_each(|(tau_minus_r, l)| {
                     *tau_minus_r = l * *tau_minus_r;
                 });
@@ -290,8 +301,9 @@ pub(crate) mod alloc {
             Evaluations::from_vec_and_domain(v_h, *self)
         }
 
-        /// Return an iterator over the elements of the domain.
-        pub(crate) fn elements(&self) -> Elements {
+        /// Return an iterator over the elements of the domain, which are the
+        /// powers of [`EvaluationDomain::group_generator`] from `1` on.
+        pub fn elements(&self) -> Elements {
             Elements {
                 cur_elem: BlsScalar::one(),
                 cur_pow: 0,
@@ -358,7 +370,7 @@ pub(crate) mod alloc {
 
     /// An iterator over the elements of the domain.
     #[derive(Debug)]
-    pub(crate) struct Elements {
+    pub struct Elements {
         cur_elem: BlsScalar,
         cur_pow: u64,
         domain: EvaluationDomain,
@@ -58,6 +58,9 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[macro_use]
+mod logging;
+
 cfg_if::cfg_if!(
 if #[cfg(feature = "alloc")] {
     #[macro_use]
@@ -67,6 +70,8 @@ if #[cfg(feature = "alloc")] {
     pub mod circuit;
     mod util;
     mod permutation;
+
+    pub use util::batch_invert;
 });
 
 pub mod commitment_scheme;
@@ -75,7 +80,18 @@ mod fft;
 pub mod plookup;
 pub mod prelude;
 pub mod proof_system;
+#[cfg(feature = "serde")]
+mod serde_support;
 mod transcript;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use fft::EvaluationDomain;
+
+/// Recording of the transcript operations, to debug prover and verifier
+/// transcript mismatches.
+#[cfg(feature = "transcript-debug")]
+pub use transcript::debug as transcript_debug;
 
 #[doc = include_str!("../docs/notes-intro.md")]
 pub mod notes {
@@ -0,0 +1,144 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Logging hooks emitted at the milestones of proving and verifying.
+//!
+//! The hooks forward to the `log` crate under the `plonkup` target when the
+//! `logging` feature is enabled, and are discarded at compile time otherwise.
+
+/// Target under which every log record of the crate is emitted.
+#[cfg(feature = "logging")]
+pub(crate) const TARGET: &str = "plonkup";
+
+/// Emits a `debug` record under the `plonkup` target.
+#[cfg(feature = "logging")]
+macro_rules! log_debug {
+    ($($arg:tt)+) => {
+        log::debug!(target: $crate::logging::TARGET, $($arg)+)
+    };
+}
+
+/// Emits a `warn` record under the `plonkup` target.
+#[cfg(feature = "logging")]
+macro_rules! log_warn {
+    ($($arg:tt)+) => {
+        log::warn!(target: $crate::logging::TARGET, $($arg)+)
+    };
+}
+
+/// Emits a `trace` record under the `plonkup` target.
+#[cfg(feature = "logging")]
+macro_rules! log_trace {
+    ($($arg:tt)+) => {
+        log::trace!(target: $crate::logging::TARGET, $($arg)+)
+    };
+}
+
+// The arguments are still type-checked, but never formatted, when logging is
+// disabled, so that values only used for logging do not trigger warnings
+#[cfg(not(feature = "logging"))]
+macro_rules! log_debug {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! log_warn {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! log_trace {
+    ($($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+#[cfg(all(test, feature = "std", feature = "logging"))]
+mod tests {
+    use super::TARGET;
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::constraint_system::helper::dummy_gadget;
+    use crate::proof_system::{Prover, Verifier};
+    use log::{Log, Metadata, Record};
+    use rand_core::OsRng;
+    use std::cell::RefCell;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    std::thread_local! {
+        static RECORDS: RefCell<Vec<String>> = RefCell::new(Vec::new());
+    }
+
+    /// Logger capturing the records emitted by the current thread, so that
+    /// tests running in parallel do not interfere with each other.
+    struct CapturingLogger;
+
+    impl Log for CapturingLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.target() == TARGET
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                RECORDS.with(|records| {
+                    records.borrow_mut().push(record.args().to_string())
+                });
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger;
+
+    #[test]
+    fn test_proving_log_events() {
+        // Another test may have installed the logger already
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let proof = prover.prove(&ck).unwrap();
+
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+        assert!(verifier.verify(&proof, &vk, &public_inputs).is_ok());
+
+        let records = RECORDS.with(|records| records.borrow().clone());
+        assert_eq!(
+            records,
+            [
+                "prover preprocessing done",
+                "committed to wire polynomials",
+                "derived table compression challenge",
+                "committed to query polynomial",
+                "derived permutation challenges",
+                "committed to permutation polynomial",
+                "derived evaluation challenge",
+                "committed to sorted table polynomials",
+                "committed to lookup permutation polynomial",
+                "derived quotient challenges",
+                "committed to quotient polynomial",
+                "computed opening commitments",
+                "proof generated",
+                "verifier preprocessing done",
+                "proof verification succeeded",
+            ]
+        );
+    }
+}
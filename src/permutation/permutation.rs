@@ -7,18 +7,19 @@
 use super::constants::{K1, K2, K3};
 use crate::constraint_system::{Variable, WireData};
 use crate::fft::{EvaluationDomain, Polynomial};
+use crate::util::batch_invert;
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
 use hashbrown::HashMap;
 use itertools::izip;
 
-#[cfg(feature = "std")]
+#[cfg(feature = "parallel")]
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 /// Permutation provides the necessary state information and functions
 /// to create the permutation polynomial. In the literature, Z(X) is the
 /// "accumulator", this is what this codebase calls the permutation polynomial.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Permutation {
     // Maps a variable to the wires that it is associated to.
     pub(crate) variable_map: HashMap<Variable, Vec<WireData>>,
@@ -637,6 +638,31 @@ impl Permutation {
         beta: &BlsScalar,
         gamma: &BlsScalar,
         sigma_polys: (&Polynomial, &Polynomial, &Polynomial, &Polynomial),
+    ) -> Polynomial {
+        // Compute all roots
+        // Non-parallelizable?
+        let roots: Vec<BlsScalar> = domain.elements().collect();
+
+        self.compute_permutation_poly_with_roots(
+            domain,
+            &roots,
+            wires,
+            beta,
+            gamma,
+            sigma_polys,
+        )
+    }
+
+    /// Same as [`Permutation::compute_permutation_poly`], with the roots of
+    /// unity of the domain given by the caller.
+    pub(crate) fn compute_permutation_poly_with_roots(
+        &self,
+        domain: &EvaluationDomain,
+        roots: &[BlsScalar],
+        wires: (&[BlsScalar], &[BlsScalar], &[BlsScalar], &[BlsScalar]),
+        beta: &BlsScalar,
+        gamma: &BlsScalar,
+        sigma_polys: (&Polynomial, &Polynomial, &Polynomial, &Polynomial),
     ) -> Polynomial {
         let n = domain.size();
 
@@ -663,48 +689,48 @@ impl Permutation {
         )
         .map(|(s0, s1, s2, s3)| vec![s0, s1, s2, s3]);
 
-        // Compute all roots
-        // Non-parallelizable?
-        let roots: Vec<BlsScalar> = domain.elements().collect();
+        let (numerators, mut denominators): (Vec<_>, Vec<_>) =
+            izip!(roots.iter().copied(), gatewise_sigmas, gatewise_wires)
+                // Associate each wire value in a gate with the k defining its
+                // coset
+                .map(|(gate_root, gate_sigmas, gate_wires)| {
+                    (gate_root, izip!(gate_sigmas, gate_wires, &ks))
+                })
+                // Now the ith element represents gate i and will have the form:
+                //   (root_i, ((w0_i, s0_i, k0), (w1_i, s1_i, k1), ..., (wm_i,
+                // sm_i, km)))   for m different wires, which is
+                // all the information   needed for a single
+                // product coefficient for a single gate
+                // Multiply up the numerator and denominator
+                // irreducibles for each gate   and pair the
+                // results
+                .map(|(gate_root, wire_params)| {
+                    (
+                        // Numerator product
+                        wire_params
+                            .clone()
+                            .map(|(_sigma, wire, k)| {
+                                Permutation::numerator_irreducible(
+                                    &gate_root, wire, &k, beta, gamma,
+                                )
+                            })
+                            .product::<BlsScalar>(),
+                        // Denominator product
+                        wire_params
+                            .map(|(sigma, wire, _k)| {
+                                Permutation::denominator_irreducible(
+                                    &gate_root, wire, &sigma, beta, gamma,
+                                )
+                            })
+                            .product::<BlsScalar>(),
+                    )
+                })
+                .unzip();
 
-        let product_argument = izip!(roots, gatewise_sigmas, gatewise_wires)
-            // Associate each wire value in a gate with the k defining its coset
-            .map(|(gate_root, gate_sigmas, gate_wires)| {
-                (gate_root, izip!(gate_sigmas, gate_wires, &ks))
-            })
-            // Now the ith element represents gate i and will have the form:
-            //   (root_i, ((w0_i, s0_i, k0), (w1_i, s1_i, k1), ..., (wm_i, sm_i,
-            // km)))   for m different wires, which is all the
-            // information   needed for a single product coefficient
-            // for a single gate Multiply up the numerator and
-            // denominator irreducibles for each gate   and pair the
-            // results
-            .map(|(gate_root, wire_params)| {
-                (
-                    // Numerator product
-                    wire_params
-                        .clone()
-                        .map(|(_sigma, wire, k)| {
-                            Permutation::numerator_irreducible(
-                                &gate_root, wire, &k, beta, gamma,
-                            )
-                        })
-                        .product::<BlsScalar>(),
-                    // Denominator product
-                    wire_params
-                        .map(|(sigma, wire, _k)| {
-                            Permutation::denominator_irreducible(
-                                &gate_root, wire, &sigma, beta, gamma,
-                            )
-                        })
-                        .product::<BlsScalar>(),
-                )
-            })
-            // Divide each pair to get the single scalar representing each gate
-            .map(|(n, d)| n * d.invert().unwrap())
-            // Collect into vector intermediary since rayon does not support
-            // `scan`
-            .collect::<Vec<BlsScalar>>();
+        // Divide each pair to get the single scalar representing each gate
+        invert_denominators(&mut denominators);
+        let product_argument =
+            numerators.into_iter().zip(denominators).map(|(n, d)| n * d);
 
         let mut z = Vec::with_capacity(n);
 
@@ -743,67 +769,110 @@ impl Permutation {
         assert!(h_1.len() == domain.size());
         assert!(h_2.len() == domain.size());
 
-        let t_next: Vec<BlsScalar> = [&t[1..], &[t[0]]].concat();
-        let h_1_next: Vec<BlsScalar> = [&h_1[1..], &[h_1[0]]].concat();
+        // The terms of every row are independent, only the running product
+        // is sequential
+        #[cfg(feature = "parallel")]
+        let (numerators, denominators) =
+            lookup_product_terms_parallel(f, t, h_1, h_2, delta, epsilon);
+        #[cfg(not(feature = "parallel"))]
+        let (numerators, denominators) =
+            lookup_product_terms_serial(f, t, h_1, h_2, delta, epsilon);
 
-        #[cfg(feature = "std")]
-        let product_arguments: Vec<BlsScalar> =
-            (f, t, t_next, h_1, h_1_next, h_2)
-                .into_par_iter()
-                // Derive the numerator and denominator for each gate plookup
-                // gate and pair the results
-                .map(|(f, t, t_next, h_1, h_1_next, h_2)| {
-                    (
-                        plookup_numerator_irreducible(
-                            delta, epsilon, &f, &t, t_next,
-                        ),
-                        plookup_denominator_irreducible(
-                            delta, epsilon, &h_1, &h_1_next, &h_2,
-                        ),
-                    )
-                })
-                .map(|(num, den)| num * den.invert().unwrap())
-                .collect();
+        let p = lookup_accumulator(numerators, denominators);
+        assert_eq!(n, p.len());
 
-        #[cfg(not(feature = "std"))]
-        let product_arguments: Vec<BlsScalar> = f
-            .iter()
-            .zip(t)
-            .zip(t_next)
-            .zip(h_1)
-            .zip(h_1_next)
-            .zip(h_2)
-            // Derive the numerator and denominator for each gate plookup
-            // gate and pair the results
-            .map(|(((((f, t), t_next), h_1), h_1_next), h_2)| {
-                (
-                    plookup_numerator_irreducible(
-                        delta, epsilon, &f, &t, t_next,
-                    ),
-                    plookup_denominator_irreducible(
-                        delta, epsilon, &h_1, &h_1_next, &h_2,
-                    ),
-                )
-            })
-            .map(|(num, den)| num * den.invert().unwrap())
-            .collect();
+        Polynomial::from_coefficients_vec(domain.ifft(&p))
+    }
+}
 
-        let mut state = BlsScalar::one();
-        let mut p = Vec::with_capacity(n);
-        p.push(state);
+/// Computes the numerator and the denominator of the plookup product
+/// argument for every row, in a single thread.
+#[cfg(any(not(feature = "parallel"), test))]
+fn lookup_product_terms_serial(
+    f: &[BlsScalar],
+    t: &[BlsScalar],
+    h_1: &[BlsScalar],
+    h_2: &[BlsScalar],
+    delta: &BlsScalar,
+    epsilon: &BlsScalar,
+) -> (Vec<BlsScalar>, Vec<BlsScalar>) {
+    let t_next = t.iter().skip(1).chain(t.first());
+    let h_1_next = h_1.iter().skip(1).chain(h_1.first());
 
-        for s in product_arguments {
-            state *= s;
-            p.push(state);
-        }
+    izip!(f, t, t_next, h_1, h_1_next, h_2)
+        .map(|(f, t, t_next, h_1, h_1_next, h_2)| {
+            (
+                plookup_numerator_irreducible(delta, epsilon, f, t, *t_next),
+                plookup_denominator_irreducible(
+                    delta, epsilon, h_1, h_1_next, h_2,
+                ),
+            )
+        })
+        .unzip()
+}
 
-        // remove the last element
-        p.remove(n);
+/// Same as [`lookup_product_terms_serial`], with the rows split across the
+/// threads of rayon.
+#[cfg(feature = "parallel")]
+fn lookup_product_terms_parallel(
+    f: &[BlsScalar],
+    t: &[BlsScalar],
+    h_1: &[BlsScalar],
+    h_2: &[BlsScalar],
+    delta: &BlsScalar,
+    epsilon: &BlsScalar,
+) -> (Vec<BlsScalar>, Vec<BlsScalar>) {
+    let n = t.len();
 
-        assert_eq!(n, p.len());
+    (0..n)
+        .into_par_iter()
+        .map(|i| {
+            let next = (i + 1) % n;
+            (
+                plookup_numerator_irreducible(
+                    delta, epsilon, &f[i], &t[i], t[next],
+                ),
+                plookup_denominator_irreducible(
+                    delta, epsilon, &h_1[i], &h_1[next], &h_2[i],
+                ),
+            )
+        })
+        .unzip()
+}
 
-        Polynomial::from_coefficients_vec(domain.ifft(&p))
-    }
+/// Inverts the denominators of a permutation product in a single batch.
+///
+/// # Panics
+/// If one of the denominators is zero, which [`batch_invert`] would leave
+/// as zero and turn the product into a wrong one instead of failing.
+fn invert_denominators(denominators: &mut [BlsScalar]) {
+    assert!(
+        !denominators.contains(&BlsScalar::zero()),
+        "a denominator of the permutation product is zero"
+    );
+    batch_invert(denominators);
+}
+
+/// Computes the running product of the ratios of `numerators` and
+/// `denominators`, starting from one, which is the evaluation form of the
+/// plookup permutation polynomial.
+fn lookup_accumulator(
+    numerators: Vec<BlsScalar>,
+    mut denominators: Vec<BlsScalar>,
+) -> Vec<BlsScalar> {
+    invert_denominators(&mut denominators);
+
+    let mut state = BlsScalar::one();
+    let mut p = Vec::with_capacity(numerators.len());
+    numerators
+        .into_iter()
+        .zip(denominators)
+        .for_each(|(num, den)| {
+            p.push(state);
+            state *= num * den;
+        });
+
+    p
 }
 
 fn plookup_numerator_irreducible(
@@ -1248,6 +1317,39 @@ mod test {
         );
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_lookup_accumulator_parallel_matches_serial() {
+        let n = 64;
+        let random_vec = |n| -> Vec<BlsScalar> {
+            (0..n).map(|_| BlsScalar::random(&mut OsRng)).collect()
+        };
+        let (f, t, h_1, h_2) =
+            (random_vec(n), random_vec(n), random_vec(n), random_vec(n));
+        let delta = BlsScalar::random(&mut OsRng);
+        let epsilon = BlsScalar::random(&mut OsRng);
+
+        let serial =
+            lookup_product_terms_serial(&f, &t, &h_1, &h_2, &delta, &epsilon);
+        let parallel =
+            lookup_product_terms_parallel(&f, &t, &h_1, &h_2, &delta, &epsilon);
+        assert_eq!(serial, parallel);
+
+        let serial = lookup_accumulator(serial.0, serial.1);
+        let parallel = lookup_accumulator(parallel.0, parallel.1);
+        assert_eq!(serial, parallel);
+        assert_eq!(serial[0], BlsScalar::one());
+    }
+
+    #[test]
+    #[should_panic(expected = "a denominator of the permutation product")]
+    fn test_lookup_accumulator_zero_denominator() {
+        let numerators = vec![BlsScalar::one(); 3];
+        let denominators =
+            vec![BlsScalar::from(2), BlsScalar::zero(), BlsScalar::one()];
+        lookup_accumulator(numerators, denominators);
+    }
+
     // shifts the polynomials by one root of unity
     fn shift_poly_by_one(z_coefficients: Vec<BlsScalar>) -> Vec<BlsScalar> {
         let mut shifted_z_coefficients = z_coefficients;
@@ -0,0 +1,74 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use crate::constraint_system::Variable;
+
+/// The wires of a single lookup query.
+///
+/// A lookup gate is satisfied when the tuple `(query, aux, output, tag)`
+/// matches, as a whole, one of the rows `(a, b, c, d)` of the 4-arity
+/// lookup table. Every column takes part in the match, so a table row is
+/// only found if all four values agree at the same time:
+///
+/// - `query` is the value being looked up, and goes in the first column.
+/// - `aux` is an auxiliary input that selects among rows sharing the same
+///   query, eg. the position counter of the Zelbet s-box tables.
+/// - `output` is the result read from the table, and is the [`Variable`]
+///   returned by [`StandardComposer::plookup_gate`].
+/// - `tag` carries a per-row constraint tag, such as the `c_i` values of the
+///   Zelbet s-box. A `tag` of `None` is the no-lookup sentinel: the zero
+///   variable is used in its place, so it matches table rows whose fourth
+///   column is zero.
+///
+/// [`StandardComposer::plookup_gate`]:
+/// crate::constraint_system::StandardComposer::plookup_gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LookupRow {
+    /// Value looked up in the first column
+    pub query: Variable,
+    /// Auxiliary input matched against the second column
+    pub aux: Variable,
+    /// Output matched against the third column
+    pub output: Variable,
+    /// Optional tag matched against the fourth column
+    pub tag: Option<Variable>,
+}
+
+impl LookupRow {
+    /// Creates a [`LookupRow`] from its four columns.
+    pub fn new(
+        query: Variable,
+        aux: Variable,
+        output: Variable,
+        tag: Option<Variable>,
+    ) -> Self {
+        Self {
+            query,
+            aux,
+            output,
+            tag,
+        }
+    }
+}
+
+impl From<(Variable, Variable, Variable, Option<Variable>)> for LookupRow {
+    fn from(
+        (query, aux, output, tag): (
+            Variable,
+            Variable,
+            Variable,
+            Option<Variable>,
+        ),
+    ) -> Self {
+        Self::new(query, aux, output, tag)
+    }
+}
+
+impl From<(Variable, Variable, Variable)> for LookupRow {
+    fn from((query, aux, output): (Variable, Variable, Variable)) -> Self {
+        Self::new(query, aux, output, None)
+    }
+}
@@ -18,7 +18,10 @@ pub mod multiset;
 /// hello
 pub mod table;
 // pub mod plookup;
+/// Named columns of a lookup query
+pub mod lookup_row;
 
+pub use lookup_row::LookupRow;
 pub use multiset::MultiSet;
 pub use table::{
     lookup_table::{PlookupTable3Arity, PlookupTable4Arity},
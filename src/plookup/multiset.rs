@@ -10,6 +10,10 @@ use crate::fft::{EvaluationDomain, Polynomial};
 use alloc::vec::Vec;
 use core::ops::{Add, Mul};
 use dusk_bytes::{DeserializableSlice, Serializable};
+use hashbrown::HashMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// MultiSet is struct containing vectors of scalars, which
 /// individually represents either a wire value or an index
@@ -98,15 +102,37 @@ impl MultiSet {
     /// Then we combine the multisets together and sort
     /// their elements together. The final MultiSet will
     /// look as follows, s: {1,1,2,2,3,3,4,4}
+    ///
+    /// Every element of f is inserted next to the first occurrence of its
+    /// value in t, whose positions are indexed once beforehand.
     pub fn sorted_concat(&self, f: &MultiSet) -> Result<MultiSet, Error> {
-        let mut s = self.clone();
-        s.0.reserve(f.0.len());
-        for element in f.0.iter() {
-            let index = s.position(element).ok_or(Error::ElementNotIndexed)?;
-            s.0.insert(index, *element);
-        }
-
-        Ok(s)
+        let mut first_positions = HashMap::with_capacity(self.0.len());
+        self.0.iter().enumerate().rev().for_each(|(i, element)| {
+            first_positions.insert(element.to_bytes(), i);
+        });
+        let position = |element: &BlsScalar| {
+            first_positions
+                .get(&element.to_bytes())
+                .copied()
+                .ok_or(Error::ElementNotIndexed)
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let positions: Result<Vec<usize>, Error> =
+            f.0.iter().map(position).collect();
+        #[cfg(feature = "parallel")]
+        let positions: Result<Vec<usize>, Error> =
+            f.0.par_iter().map(position).collect();
+
+        let mut copies = vec![0usize; self.0.len()];
+        positions?.into_iter().for_each(|i| copies[i] += 1);
+
+        let mut s = Vec::with_capacity(self.0.len() + f.0.len());
+        self.0.iter().zip(copies).for_each(|(element, copies)| {
+            s.extend(core::iter::repeat(*element).take(copies + 1));
+        });
+
+        Ok(MultiSet(s))
     }
 
     /// Checks whether one mutltiset is a subset of another.
@@ -191,23 +217,53 @@ impl MultiSet {
         multisets: [&MultiSet; 4],
         alpha: BlsScalar,
     ) -> MultiSet {
-        MultiSet(
-            multisets[0]
-                .0
-                .iter()
-                .zip(multisets[1].0.iter())
-                .zip(multisets[2].0.iter())
-                .zip(multisets[3].0.iter())
-                .map(|(((a, b), c), d)| {
-                    a + b * alpha
-                        + c * alpha.square()
-                        + d * alpha.pow(&[3u64, 0u64, 0u64, 0u64])
-                })
-                .collect::<Vec<BlsScalar>>(),
-        )
+        #[cfg(feature = "parallel")]
+        let compress = compress_four_arity_parallel;
+        #[cfg(not(feature = "parallel"))]
+        let compress = compress_four_arity_serial;
+
+        MultiSet(compress(multisets, alpha))
     }
 }
 
+/// Computes the elements of [`MultiSet::compress_four_arity`], in a single
+/// thread.
+#[cfg(any(not(feature = "parallel"), test))]
+fn compress_four_arity_serial(
+    multisets: [&MultiSet; 4],
+    alpha: BlsScalar,
+) -> Vec<BlsScalar> {
+    let alpha_2 = alpha.square();
+    let alpha_3 = alpha.pow(&[3u64, 0u64, 0u64, 0u64]);
+    multisets[0]
+        .0
+        .iter()
+        .zip(multisets[1].0.iter())
+        .zip(multisets[2].0.iter())
+        .zip(multisets[3].0.iter())
+        .map(|(((a, b), c), d)| a + b * alpha + c * alpha_2 + d * alpha_3)
+        .collect()
+}
+
+/// Same as [`compress_four_arity_serial`], with the elements split across
+/// the threads of rayon.
+#[cfg(feature = "parallel")]
+fn compress_four_arity_parallel(
+    multisets: [&MultiSet; 4],
+    alpha: BlsScalar,
+) -> Vec<BlsScalar> {
+    let alpha_2 = alpha.square();
+    let alpha_3 = alpha.pow(&[3u64, 0u64, 0u64, 0u64]);
+    multisets[0]
+        .0
+        .par_iter()
+        .zip(multisets[1].0.par_iter())
+        .zip(multisets[2].0.par_iter())
+        .zip(multisets[3].0.par_iter())
+        .map(|(((a, b), c), d)| a + b * alpha + c * alpha_2 + d * alpha_3)
+        .collect()
+}
+
 impl Add for MultiSet {
     type Output = MultiSet;
 
@@ -243,6 +299,8 @@ mod test {
     use super::*;
     use crate::fft::EvaluationDomain;
     use crate::plookup::WitnessTable3Arity;
+    #[cfg(feature = "parallel")]
+    use rand_core::OsRng;
 
     #[test]
     fn test_halve() {
@@ -411,4 +469,56 @@ mod test {
 
         assert_eq!(actual_set, compressed_element);
     }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_compress_four_arity_parallel_matches_serial() {
+        let random_set = || {
+            MultiSet((0..64).map(|_| BlsScalar::random(&mut OsRng)).collect())
+        };
+        let multisets =
+            [random_set(), random_set(), random_set(), random_set()];
+        let multisets =
+            [&multisets[0], &multisets[1], &multisets[2], &multisets[3]];
+        let alpha = BlsScalar::random(&mut OsRng);
+
+        assert_eq!(
+            compress_four_arity_serial(multisets, alpha),
+            compress_four_arity_parallel(multisets, alpha)
+        );
+    }
+
+    #[test]
+    fn test_sorted_concat_matches_insertion() {
+        // Table with repeated values, as the padding of a real one
+        let t = MultiSet(
+            [5u64, 1, 3, 3, 7, 1, 0, 0]
+                .iter()
+                .map(|x| BlsScalar::from(*x))
+                .collect(),
+        );
+        let f = MultiSet(
+            [3u64, 0, 7, 3, 1, 5, 5, 0, 3]
+                .iter()
+                .map(|x| BlsScalar::from(*x))
+                .collect(),
+        );
+
+        // Inserts every element of f at the first occurrence of its value
+        let mut expected = t.clone();
+        f.0.iter().for_each(|element| {
+            let index = expected.position(element).unwrap();
+            expected.0.insert(index, *element);
+        });
+
+        let s = t.sorted_concat(&f).unwrap();
+        assert_eq!(s, expected);
+        assert_eq!(s.len(), t.len() + f.len());
+
+        let missing = MultiSet(vec![BlsScalar::from(3), BlsScalar::from(2)]);
+        assert!(matches!(
+            t.sorted_concat(&missing),
+            Err(Error::ElementNotIndexed)
+        ));
+    }
 }
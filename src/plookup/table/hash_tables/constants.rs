@@ -252,8 +252,12 @@ pub const BLS_RECIP: [u64; 26] = [
     8423050992405072644,
 ];
 
+/// Number of nibbles mapped through the S-box, the ones from `0` to
+/// `SBOX_DOMAIN_SIZE - 1`. Larger nibbles are mapped to themselves.
+pub const SBOX_DOMAIN_SIZE: usize = 659;
+
 /// Predefined S-box
-pub const SBOX_U256: [u256; 659] = [
+pub const SBOX_U256: [u256; SBOX_DOMAIN_SIZE] = [
     u256([15, 0, 0, 0]),
     u256([187, 0, 0, 0]),
     u256([186, 0, 0, 0]),
@@ -915,8 +919,21 @@ pub const SBOX_U256: [u256; 659] = [
     u256([346, 0, 0, 0]),
 ];
 
+/// Returns the nibble `v_i` of the decomposition of `q - 1` at position
+/// `counter`, from 1 for the most significant nibble to [`N`] for the least
+/// significant one, or `None` if `counter` is out of `1..=N`.
+pub fn bls_scalar_real_nibble(counter: u64) -> Option<u64> {
+    match (1..=N).contains(&counter) {
+        true => Some(BLS_SCALAR_REAL[(N - counter) as usize].as_u64()),
+        false => None,
+    }
+}
+
 /// Decomposition of -1 = [v_n, v_{n-1} ..., v_1], i.e. the representation of
-/// q-1
+/// q-1, as computed by [`field_modulus_nibble_bounds`]
+///
+/// [`field_modulus_nibble_bounds`]:
+/// crate::constraint_system::zelbet::field_modulus_nibble_bounds
 pub const BLS_SCALAR_REAL: [u256; 27] = [
     u256([660, 0, 0, 0]),
     u256([660, 0, 0, 0]),
@@ -978,6 +995,186 @@ pub const DECOMPOSITION_S_I: [BlsScalar; 27] = [
     BlsScalar([679, 0, 0, 0]),
 ];
 
+/// Checks that every element of [`INVERSES_S_I`] is the inverse in the field
+/// of the `s_i` at the same index of [`DECOMPOSITION_S_I`], which the
+/// decomposition gadgets rely on. It should hold for any regenerated pair of
+/// tables.
+pub fn verify_s_i_constants() -> bool {
+    DECOMPOSITION_S_I
+        .iter()
+        .zip(INVERSES_S_I.iter())
+        .all(|(s, inverse)| {
+            BlsScalar::from(s.0[0]) * inverse == BlsScalar::one()
+        })
+}
+
+/// Inverses of the s_i in Montgomery form, in the same order as
+/// [`DECOMPOSITION_S_I`]
+pub const INVERSES_S_I: [BlsScalar; 27] = [
+    BlsScalar([
+        10221572469640980478,
+        9996602938199176322,
+        12629687422955549043,
+        8006457294428823986,
+    ]),
+    BlsScalar([
+        2544378491691269391,
+        4278332345953639965,
+        11874303680749211767,
+        2426939821791091123,
+    ]),
+    BlsScalar([
+        744249036390430597,
+        12703574479313007857,
+        12941063472551889101,
+        7898626469976166322,
+    ]),
+    BlsScalar([
+        12813307260754568983,
+        13401737678607148669,
+        16259656273855951252,
+        77635945376597827,
+    ]),
+    BlsScalar([
+        5868183680725469139,
+        13963385237577580741,
+        2771541177262918091,
+        2057109021922450213,
+    ]),
+    BlsScalar([
+        12076645397819930386,
+        3513102646780106621,
+        5511421539288173570,
+        5771838565320455168,
+    ]),
+    BlsScalar([
+        6887496337358022873,
+        10595820730184048433,
+        5009316404333812521,
+        5382134171948568183,
+    ]),
+    BlsScalar([
+        10221572469640980478,
+        9996602938199176322,
+        12629687422955549043,
+        8006457294428823986,
+    ]),
+    BlsScalar([
+        4216398644020393879,
+        8184599923631624209,
+        8370160424198379966,
+        2747212268616577148,
+    ]),
+    BlsScalar([
+        9008875010444565957,
+        5372543878254038396,
+        4977331236853060682,
+        4494972308657887955,
+    ]),
+    BlsScalar([
+        4216398644020393879,
+        8184599923631624209,
+        8370160424198379966,
+        2747212268616577148,
+    ]),
+    BlsScalar([
+        744249036390430597,
+        12703574479313007857,
+        12941063472551889101,
+        7898626469976166322,
+    ]),
+    BlsScalar([
+        14604768842820473715,
+        14849596265064785286,
+        7748405401081812358,
+        5829685384640365743,
+    ]),
+    BlsScalar([
+        744249036390430597,
+        12703574479313007857,
+        12941063472551889101,
+        7898626469976166322,
+    ]),
+    BlsScalar([
+        158341149692136564,
+        11915096076612138202,
+        992774181608407826,
+        2273115756299035808,
+    ]),
+    BlsScalar([
+        14604768842820473715,
+        14849596265064785286,
+        7748405401081812358,
+        5829685384640365743,
+    ]),
+    BlsScalar([
+        14604768842820473715,
+        14849596265064785286,
+        7748405401081812358,
+        5829685384640365743,
+    ]),
+    BlsScalar([
+        14604768842820473715,
+        14849596265064785286,
+        7748405401081812358,
+        5829685384640365743,
+    ]),
+    BlsScalar([
+        12076645397819930386,
+        3513102646780106621,
+        5511421539288173570,
+        5771838565320455168,
+    ]),
+    BlsScalar([
+        14693968055083782227,
+        11284659261154016671,
+        1375836735433574310,
+        7087423626300479469,
+    ]),
+    BlsScalar([
+        8098570567349679003,
+        8435273170816927395,
+        2562595664368193229,
+        3130570460078912387,
+    ]),
+    BlsScalar([
+        9026571779888472938,
+        4361001158901360622,
+        8564805700575463153,
+        287658769533324946,
+    ]),
+    BlsScalar([
+        15293454485951598931,
+        15506724579931865072,
+        2628833535283587331,
+        4905112046230959809,
+    ]),
+    BlsScalar([
+        6887496337358022873,
+        10595820730184048433,
+        5009316404333812521,
+        5382134171948568183,
+    ]),
+    BlsScalar([
+        9008875010444565957,
+        5372543878254038396,
+        4977331236853060682,
+        4494972308657887955,
+    ]),
+    BlsScalar([
+        9026571779888472938,
+        4361001158901360622,
+        8564805700575463153,
+        287658769533324946,
+    ]),
+    BlsScalar([
+        5868183680725469139,
+        13963385237577580741,
+        2771541177262918091,
+        2057109021922450213,
+    ]),
+];
+
 /// Remainers in Montgomery form
 pub const REMAINDER_MONT: [BlsScalar; 703] = [
     BlsScalar([0, 0, 0, 0]),
@@ -5194,3 +5391,21 @@ pub const REMAINDER_MONT: [BlsScalar; 703] = [
         1663207574208738521,
     ]),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_s_i_constants() {
+        (0..27).for_each(|k| {
+            assert_eq!(
+                BlsScalar::from(DECOMPOSITION_S_I[k].0[0]) * INVERSES_S_I[k],
+                BlsScalar::one(),
+                "wrong inverse of s_{}",
+                k
+            );
+        });
+        assert!(verify_s_i_constants());
+    }
+}
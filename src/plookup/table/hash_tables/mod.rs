@@ -9,7 +9,11 @@
 
 /// Module containing constants required in reinforced concrete
 pub mod constants;
+/// Module containing the pluggable s-box of reinforced concrete
+pub mod sbox;
 
 pub use constants::{
-    BLS_SCALAR_REAL, DECOMPOSITION_S_I, N, S, SBOX_U256, T_S, V,
+    bls_scalar_real_nibble, verify_s_i_constants, BLS_SCALAR_REAL,
+    DECOMPOSITION_S_I, INVERSES_S_I, N, S, SBOX_DOMAIN_SIZE, SBOX_U256, T_S, V,
 };
+pub use sbox::SboxTable;
@@ -0,0 +1,152 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Pluggable s-box of the bar function of Reinforced Concrete
+
+use super::constants::{
+    BLS_SCALAR_REAL, DECOMPOSITION_S_I, N, SBOX_DOMAIN_SIZE, SBOX_U256,
+};
+use bigint::U256 as u256;
+use dusk_bls12_381::BlsScalar;
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+/// S-box applied to the nibbles below [`SBOX_DOMAIN_SIZE`], along with the
+/// decomposition `[v_n, ..., v_1]` of `p - 1` bounding the nibbles, laid out
+/// as [`SBOX_U256`] and [`BLS_SCALAR_REAL`].
+///
+/// The nibbles from [`SBOX_DOMAIN_SIZE`] upwards are always mapped to
+/// themselves, so only the first part of the hash table depends on the
+/// s-box. The default table is [`SboxTable::REINFORCED_CONCRETE`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SboxTable {
+    sbox: [u256; SBOX_DOMAIN_SIZE],
+    bounds: [u256; 27],
+}
+
+impl SboxTable {
+    /// S-box and bounds of Reinforced Concrete over the BLS12-381 scalar
+    /// field
+    pub const REINFORCED_CONCRETE: SboxTable = SboxTable {
+        sbox: SBOX_U256,
+        bounds: BLS_SCALAR_REAL,
+    };
+
+    /// Creates a table mapping the nibble `k` to `sbox[k]`, with the nibbles
+    /// of `p - 1` given from the least significant one to the most
+    /// significant one.
+    ///
+    /// # Panics
+    /// If a bound doesn't fall between [`SBOX_DOMAIN_SIZE`] and its `s_i`
+    /// from [`DECOMPOSITION_S_I`], as the larger part of the hash table
+    /// couldn't be built around it.
+    pub fn new(sbox: [u256; SBOX_DOMAIN_SIZE], bounds: [u256; 27]) -> Self {
+        bounds
+            .iter()
+            .zip(DECOMPOSITION_S_I.iter())
+            .for_each(|(v, s)| {
+                let v = v.as_u64();
+                assert!(
+                    v >= SBOX_DOMAIN_SIZE as u64 && v < s.0[0],
+                    "the bound {} is out of {}..{}",
+                    v,
+                    SBOX_DOMAIN_SIZE,
+                    s.0[0]
+                );
+            });
+
+        SboxTable { sbox, bounds }
+    }
+
+    /// Returns the image of `nibble` through the s-box, or `None` if it
+    /// isn't below [`SBOX_DOMAIN_SIZE`].
+    pub fn output(&self, nibble: u64) -> Option<BlsScalar> {
+        match nibble < SBOX_DOMAIN_SIZE as u64 {
+            true => Some(BlsScalar::from_raw(self.sbox[nibble as usize].0)),
+            false => None,
+        }
+    }
+
+    /// Same as [`SboxTable::output`], returning zero for the nibbles from
+    /// [`SBOX_DOMAIN_SIZE`] upwards, but in constant time: the whole s-box is
+    /// scanned, and the image selected, without branching on `nibble` nor
+    /// indexing by it.
+    pub fn ct_output(&self, nibble: u64) -> BlsScalar {
+        let mut limbs = [0u64; 4];
+        self.sbox.iter().enumerate().for_each(|(k, y)| {
+            let found = (k as u64).ct_eq(&nibble);
+            limbs
+                .iter_mut()
+                .zip(y.0.iter())
+                .for_each(|(limb, y)| limb.conditional_assign(y, found));
+        });
+
+        BlsScalar::from_raw(limbs)
+    }
+
+    /// Returns the nibble `v_i` of `p - 1` at position `counter`, from 1
+    /// for the most significant nibble to [`N`] for the least significant
+    /// one, or `None` if `counter` is out of `1..=N`, as
+    /// [`bls_scalar_real_nibble`](super::bls_scalar_real_nibble) does.
+    pub fn bound(&self, counter: u64) -> Option<u64> {
+        match (1..=N).contains(&counter) {
+            true => Some(self.bounds[(N - counter) as usize].as_u64()),
+            false => None,
+        }
+    }
+
+    /// Returns the nibbles of `p - 1`, from the least significant one to the
+    /// most significant one.
+    pub fn bounds(&self) -> &[u256; 27] {
+        &self.bounds
+    }
+}
+
+impl Default for SboxTable {
+    fn default() -> Self {
+        SboxTable::REINFORCED_CONCRETE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::bls_scalar_real_nibble;
+    use super::*;
+
+    #[test]
+    fn test_default_table() {
+        let table = SboxTable::default();
+        (0..SBOX_DOMAIN_SIZE as u64).for_each(|k| {
+            assert_eq!(
+                table.output(k),
+                Some(BlsScalar::from_raw(SBOX_U256[k as usize].0))
+            );
+        });
+        assert_eq!(table.output(SBOX_DOMAIN_SIZE as u64), None);
+        (0..=N + 1).for_each(|counter| {
+            assert_eq!(table.bound(counter), bls_scalar_real_nibble(counter));
+        });
+    }
+
+    #[test]
+    fn test_ct_output() {
+        let table = SboxTable::default();
+        (0..SBOX_DOMAIN_SIZE as u64 + 2).for_each(|k| {
+            assert_eq!(
+                table.ct_output(k),
+                table.output(k).unwrap_or_else(BlsScalar::zero)
+            );
+        });
+        assert_eq!(table.ct_output(u64::MAX), BlsScalar::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "the bound 658 is out of 659..")]
+    fn test_bound_in_sbox_domain() {
+        let mut bounds = BLS_SCALAR_REAL;
+        bounds[3] = u256::from(SBOX_DOMAIN_SIZE as u64 - 1);
+        SboxTable::new(SBOX_U256, bounds);
+    }
+}
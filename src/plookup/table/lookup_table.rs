@@ -8,12 +8,25 @@
 //! Denoted as 't' in Plookup paper.
 
 use super::hash_tables::constants::{
-    BLS_SCALAR_REAL, DECOMPOSITION_S_I, SBOX_U256,
+    DECOMPOSITION_S_I, SBOX_DOMAIN_SIZE, SBOX_U256,
 };
+use super::hash_tables::SboxTable;
 use crate::error::Error;
 use crate::plookup::MultiSet;
 use crate::prelude::BlsScalar;
 use alloc::vec::Vec;
+use dusk_bytes::{DeserializableSlice, Serializable};
+use hashbrown::HashSet;
+
+/// Maximum number of bits of a table built with
+/// [`PlookupTable4Arity::create_range_table`]
+pub const MAX_RANGE_TABLE_BITS: u32 = 20;
+
+/// Maximum number of bits of a table built with
+/// [`PlookupTable4Arity::create_xor_table`] or
+/// [`PlookupTable4Arity::create_and_table`], which have as many rows as a
+/// range table of twice as many bits
+pub const MAX_BITWISE_TABLE_BITS: u32 = MAX_RANGE_TABLE_BITS / 2;
 
 /// For the implemenation of look up tables in PLONK, aptly named PLOOKup
 /// tables, there will be different fucntions depending on the type of table
@@ -165,8 +178,8 @@ impl PlookupTable3Arity {
 
     /// Function that generates the S-box used in reinforced concrete
     pub fn s_box_table() -> Self {
-        let mut s_box = Vec::with_capacity(659);
-        (0..659).for_each(|k| {
+        let mut s_box = Vec::with_capacity(SBOX_DOMAIN_SIZE);
+        (0..SBOX_DOMAIN_SIZE as u64).for_each(|k| {
             s_box.push([
                 BlsScalar([k, 0, 0, 0]),
                 BlsScalar([k, 0, 0, 0]),
@@ -224,6 +237,24 @@ impl PlookupTable3Arity {
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct PlookupTable4Arity(pub Vec<[BlsScalar; 4]>);
 
+/// Byte encoding of a row, used to hash it
+type RowBytes = [[u8; BlsScalar::SIZE]; 4];
+
+fn row_bytes(row: &[BlsScalar; 4]) -> RowBytes {
+    [
+        row[0].to_bytes(),
+        row[1].to_bytes(),
+        row[2].to_bytes(),
+        row[3].to_bytes(),
+    ]
+}
+
+impl From<Vec<[BlsScalar; 4]>> for PlookupTable4Arity {
+    fn from(rows: Vec<[BlsScalar; 4]>) -> Self {
+        PlookupTable4Arity(rows)
+    }
+}
+
 impl Default for PlookupTable4Arity {
     fn default() -> Self {
         PlookupTable4Arity::new()
@@ -233,7 +264,28 @@ impl Default for PlookupTable4Arity {
 impl PlookupTable4Arity {
     /// Create a new, empty Plookup table, with arity 4.
     pub fn new() -> Self {
-        PlookupTable4Arity(vec![])
+        PlookupTable4Arity::from(vec![])
+    }
+
+    /// Creates the table of the unary function `f` over `domain`, with one
+    /// row `(x, 0, f(x), 0)` per element `x`.
+    ///
+    /// The output of `x` can then be looked up in a circuit with
+    /// [`StandardComposer::plookup_query`], given `x` and a zero
+    /// [`Variable`].
+    ///
+    /// [`StandardComposer::plookup_query`]:
+    /// crate::constraint_system::StandardComposer::plookup_query
+    /// [`Variable`]: crate::constraint_system::Variable
+    pub fn from_fn(
+        domain: impl Iterator<Item = BlsScalar>,
+        f: impl Fn(BlsScalar) -> BlsScalar,
+    ) -> Self {
+        PlookupTable4Arity::from(
+            domain
+                .map(|x| [x, BlsScalar::zero(), f(x), BlsScalar::zero()])
+                .collect::<Vec<_>>(),
+        )
     }
 
     /// Insert a new row for an addition operation.
@@ -420,10 +472,255 @@ impl PlookupTable4Arity {
         Ok(self.0[pos][2])
     }
 
+    /// Checks whether `row` is in the table, without adding any gate to a
+    /// circuit. This can be used to validate the witness of a lookup before
+    /// proving, see [`PlookupTable4Arity::contains_rows`] to check many of
+    /// them.
+    pub fn contains(&self, row: [BlsScalar; 4]) -> bool {
+        self.0.contains(&row)
+    }
+
+    /// Checks whether every row of `rows` is in the table, returning one
+    /// boolean per row.
+    ///
+    /// The rows of the table are hashed once per call rather than kept
+    /// along with the table, as they can be changed at any time through
+    /// the inner vector.
+    pub fn contains_rows(&self, rows: &[[BlsScalar; 4]]) -> Vec<bool> {
+        let table: HashSet<RowBytes> = self.0.iter().map(row_bytes).collect();
+        rows.iter()
+            .map(|row| table.contains(&row_bytes(row)))
+            .collect()
+    }
+
+    /// Checks whether two tables hold the same rows, with the same
+    /// multiplicities, regardless of the order in which they were inserted.
+    pub fn eq_ignoring_order(&self, other: &Self) -> bool {
+        if self.0.len() != other.0.len() {
+            return false;
+        }
+
+        let sorted_rows = |table: &Self| {
+            let mut rows: Vec<RowBytes> =
+                table.0.iter().map(row_bytes).collect();
+            rows.sort_unstable();
+            rows
+        };
+
+        sorted_rows(self) == sorted_rows(other)
+    }
+
+    /// Creates a table for range checks of `bits` bits, holding the rows
+    /// `(v, 0, v, bits)` for every `v` in `0..2^bits`. See
+    /// [`StandardComposer::range_check_lookup`].
+    ///
+    /// Returns an error if `bits` is larger than [`MAX_RANGE_TABLE_BITS`].
+    ///
+    /// [`StandardComposer::range_check_lookup`]:
+    /// crate::constraint_system::StandardComposer::range_check_lookup
+    pub fn create_range_table(bits: u32) -> Result<Self, Error> {
+        if bits > MAX_RANGE_TABLE_BITS {
+            return Err(Error::TableTooLarge {
+                bits,
+                max_bits: MAX_RANGE_TABLE_BITS,
+            });
+        }
+
+        let tag = BlsScalar::from(bits as u64);
+        let table = (0..1u64 << bits)
+            .map(|v| {
+                let v = BlsScalar::from(v);
+                [v, BlsScalar::zero(), v, tag]
+            })
+            .collect();
+
+        Ok(PlookupTable4Arity::from(table))
+    }
+
+    /// Creates a table holding the rows `(a, b, a ^ b, 0)` for every `a` and
+    /// `b` in `0..2^bits`, that is `4^bits` rows. See
+    /// [`StandardComposer::xor_lookup`].
+    ///
+    /// As the rows of [`PlookupTable4Arity::create_and_table`] also have a
+    /// zero fourth column, both tables should only be used together after
+    /// merging them with [`PlookupTable4Arity::merge_tagged`].
+    ///
+    /// Returns an error if `bits` is larger than [`MAX_BITWISE_TABLE_BITS`].
+    ///
+    /// [`StandardComposer::xor_lookup`]:
+    /// crate::constraint_system::StandardComposer::xor_lookup
+    pub fn create_xor_table(bits: u32) -> Result<Self, Error> {
+        Self::create_bitwise_table(bits, |a, b| a ^ b)
+    }
+
+    /// Creates a table holding the rows `(a, b, a & b, 0)` for every `a` and
+    /// `b` in `0..2^bits`, that is `4^bits` rows. See
+    /// [`StandardComposer::and_lookup`].
+    ///
+    /// As the rows of [`PlookupTable4Arity::create_xor_table`] also have a
+    /// zero fourth column, both tables should only be used together after
+    /// merging them with [`PlookupTable4Arity::merge_tagged`].
+    ///
+    /// Returns an error if `bits` is larger than [`MAX_BITWISE_TABLE_BITS`].
+    ///
+    /// [`StandardComposer::and_lookup`]:
+    /// crate::constraint_system::StandardComposer::and_lookup
+    pub fn create_and_table(bits: u32) -> Result<Self, Error> {
+        Self::create_bitwise_table(bits, |a, b| a & b)
+    }
+
+    fn create_bitwise_table<F: Fn(u64, u64) -> u64>(
+        bits: u32,
+        op: F,
+    ) -> Result<Self, Error> {
+        if bits > MAX_BITWISE_TABLE_BITS {
+            return Err(Error::TableTooLarge {
+                bits,
+                max_bits: MAX_BITWISE_TABLE_BITS,
+            });
+        }
+
+        let upper_bound = 1u64 << bits;
+        let mut table =
+            Vec::with_capacity((upper_bound * upper_bound) as usize);
+        (0..upper_bound).for_each(|a| {
+            (0..upper_bound).for_each(|b| {
+                table.push([
+                    BlsScalar::from(a),
+                    BlsScalar::from(b),
+                    BlsScalar::from(op(a, b)),
+                    BlsScalar::zero(),
+                ])
+            })
+        });
+
+        Ok(PlookupTable4Arity::from(table))
+    }
+
+    /// Returns the value added to the fourth column of the rows of the table
+    /// `table_id` by [`PlookupTable4Arity::merge_tagged`], which is
+    /// `table_id * 2^128`.
+    pub fn table_tag(table_id: u64) -> BlsScalar {
+        BlsScalar::from(table_id) * BlsScalar::from(2).pow(&[128, 0, 0, 0])
+    }
+
+    /// Merges `tables` into a single table, adding
+    /// [`PlookupTable4Arity::table_tag`] of the position of each table in
+    /// `tables` to the fourth column of its rows, so that a row of one
+    /// table can't be matched by a lookup targeting another one. See
+    /// [`StandardComposer::tagged_plookup_gate`].
+    ///
+    /// Rows are disambiguated as long as the fourth column of every table
+    /// holds small values, between `-2^127` and `2^127`, as is the case for
+    /// the tables built in this module.
+    ///
+    /// [`StandardComposer::tagged_plookup_gate`]:
+    /// crate::constraint_system::StandardComposer::tagged_plookup_gate
+    pub fn merge_tagged(tables: &[&PlookupTable4Arity]) -> Self {
+        let rows = tables
+            .iter()
+            .enumerate()
+            .flat_map(|(table_id, table)| {
+                let tag = Self::table_tag(table_id as u64);
+                table.0.iter().map(move |row| {
+                    let mut row = *row;
+                    row[3] += tag;
+                    row
+                })
+            })
+            .collect();
+
+        PlookupTable4Arity::from(rows)
+    }
+
+    /// Removes the rows that are exact duplicates of a previous one, keeping
+    /// the order of the first occurrences. As a row can be matched by a
+    /// lookup regardless of how many times it appears, this doesn't change
+    /// which lookups succeed.
+    ///
+    /// Returns the number of rows before and after the deduplication.
+    pub fn dedup(&mut self) -> (usize, usize) {
+        let len_before = self.0.len();
+
+        let mut seen = HashSet::with_capacity(len_before);
+        self.0.retain(|row| seen.insert(row_bytes(row)));
+
+        (len_before, self.0.len())
+    }
+
+    /// Serialises the table into a byte vector: the number of rows as a
+    /// little-endian `u64`, followed by the four [`BlsScalar`]s of every
+    /// row, in order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(8 + self.0.len() * 4 * BlsScalar::SIZE);
+        bytes.extend_from_slice(&(self.0.len() as u64).to_le_bytes());
+        self.0.iter().for_each(|row| {
+            row.iter()
+                .for_each(|scalar| bytes.extend_from_slice(&scalar.to_bytes()))
+        });
+
+        bytes
+    }
+
+    /// Deserialises a table serialised with [`PlookupTable4Arity::to_bytes`],
+    /// keeping the order of the rows.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        const ROW_SIZE: usize = 4 * BlsScalar::SIZE;
+
+        if bytes.len() < 8 {
+            return Err(Error::NotEnoughBytes);
+        }
+        let mut len = [0u8; 8];
+        len.copy_from_slice(&bytes[..8]);
+        let rows = &bytes[8..];
+
+        let expected = (u64::from_le_bytes(len) as usize)
+            .checked_mul(ROW_SIZE)
+            .ok_or(Error::NotEnoughBytes)?;
+        if rows.len() < expected {
+            return Err(Error::NotEnoughBytes);
+        }
+        if rows.len() > expected {
+            return Err(dusk_bytes::Error::BadLength {
+                found: bytes.len(),
+                expected: 8 + expected,
+            }
+            .into());
+        }
+
+        let table = rows
+            .chunks(ROW_SIZE)
+            .map(|row| {
+                let mut scalars = [BlsScalar::zero(); 4];
+                for (scalar, chunk) in
+                    scalars.iter_mut().zip(row.chunks(BlsScalar::SIZE))
+                {
+                    *scalar = BlsScalar::from_slice(chunk)?;
+                }
+                Ok(scalars)
+            })
+            .collect::<Result<Vec<[BlsScalar; 4]>, dusk_bytes::Error>>()?;
+
+        Ok(PlookupTable4Arity::from(table))
+    }
+
     /// Function that creates the table needed for reinforced concrete.
     /// Creates one table that is the concatenation T_2 || T_3 || T_1
     /// from the paper
+    ///
+    /// The rows are generated by nested loops over ordered ranges, without
+    /// any hashing involved, so the table and its
+    /// [`PlookupTable4Arity::to_bytes`] encoding are identical on every
+    /// call and every platform.
     pub fn create_hash_table() -> Self {
+        Self::create_hash_table_with_sbox(&SboxTable::REINFORCED_CONCRETE)
+    }
+
+    /// Same as [`PlookupTable4Arity::create_hash_table`], but with the
+    /// permutation part of T_1 and the nibbles of `p - 1` taken from `sbox`,
+    /// for the gadgets given the same [`SboxTable`].
+    pub fn create_hash_table_with_sbox(sbox: &SboxTable) -> Self {
         let mut table = Vec::new();
         let two = BlsScalar::from(2);
 
@@ -521,9 +818,9 @@ impl PlookupTable4Arity {
 
         // Construct the T_1 part
         // Build the permutation part of the table (the top section)
-        for k in 0..659 {
+        for k in 0..SBOX_DOMAIN_SIZE as u64 {
             let first = BlsScalar::from(k);
-            let third = BlsScalar::from_raw(SBOX_U256[k as usize].0);
+            let third = sbox.output(k).expect("k is in the s-box domain");
             table.push([first, BlsScalar::zero(), third, BlsScalar::one()]);
         }
         // Build the remaining 27 sections that range from p' to s_i (except
@@ -532,11 +829,11 @@ impl PlookupTable4Arity {
             // The rev denotes that it is inverted, so s_rev_26 will actually be
             // s_1 (i.e. i = 27-k)
             let s_rev_k = DECOMPOSITION_S_I[k].0[0];
-            let v_rev_k = BLS_SCALAR_REAL[k].as_u64();
+            let v_rev_k = sbox.bounds()[k].as_u64();
             // If i=1, then we go to v_1 and not s_1
             if k == 26 {
                 // v_1 = 678
-                for j in 659..(v_rev_k + 1) {
+                for j in SBOX_DOMAIN_SIZE as u64..(v_rev_k + 1) {
                     // Fourth column is 1, unless j=v_i, in which case it is 0
                     if j == v_rev_k {
                         let first = BlsScalar::from(j);
@@ -559,7 +856,7 @@ impl PlookupTable4Arity {
             } else {
                 // When j is between p' and v_i the fourth column is always 1
                 let second = BlsScalar::from((27 - k) as u64);
-                for j in 659..v_rev_k {
+                for j in SBOX_DOMAIN_SIZE as u64..v_rev_k {
                     let first = BlsScalar::from(j);
                     table.push([first, second, first, BlsScalar::one()]);
                 }
@@ -577,7 +874,7 @@ impl PlookupTable4Arity {
             }
         }
 
-        PlookupTable4Arity(table)
+        PlookupTable4Arity::from(table)
     }
 }
 
@@ -693,4 +990,215 @@ mod test {
         let xor = table.0[36][0] ^ table.0[36][1];
         assert_eq!(xor, BlsScalar::from(5u64));
     }
+
+    #[test]
+    fn test_eq_ignoring_order() {
+        let mut table = PlookupTable4Arity::new();
+        table.insert_multi_xor(0, 3);
+        table.insert_multi_add(2, 3);
+
+        let mut reordered = table.clone();
+        reordered.0.reverse();
+        reordered.0.swap(0, 5);
+        assert_ne!(table, reordered);
+        assert!(table.eq_ignoring_order(&reordered));
+
+        let mut changed = reordered.clone();
+        changed.0[3][2] += BlsScalar::one();
+        assert!(!table.eq_ignoring_order(&changed));
+
+        let mut shorter = table.clone();
+        shorter.0.pop();
+        assert!(!table.eq_ignoring_order(&shorter));
+    }
+
+    #[test]
+    fn test_table_bytes() {
+        let table = PlookupTable4Arity::create_hash_table();
+        let bytes = table.to_bytes();
+        let decoded = PlookupTable4Arity::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded, table);
+        assert_eq!(decoded.vec_to_multiset(), table.vec_to_multiset());
+
+        assert!(
+            PlookupTable4Arity::from_bytes(&bytes[..bytes.len() - 1]).is_err()
+        );
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(PlookupTable4Arity::from_bytes(&extended).is_err());
+
+        let empty = PlookupTable4Arity::new();
+        assert_eq!(
+            PlookupTable4Arity::from_bytes(&empty.to_bytes()).unwrap(),
+            empty
+        );
+    }
+
+    /// 64-bit FNV-1a hash of `bytes`
+    fn fnv1a(bytes: &[u8]) -> u64 {
+        bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+
+    #[test]
+    fn test_hash_table_digest() {
+        let bytes = PlookupTable4Arity::create_hash_table().to_bytes();
+
+        // Any change in the rows, or in their order, changes the digest
+        assert_eq!(bytes.len(), 8 + 1671 * 4 * BlsScalar::SIZE);
+        assert_eq!(fnv1a(&bytes), 0x95f1_e1bc_e7f8_fd11);
+        assert_eq!(PlookupTable4Arity::create_hash_table().to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_range_table() {
+        let table = PlookupTable4Arity::create_range_table(4).unwrap();
+        assert_eq!(table.0.len(), 16);
+        assert_eq!(
+            table.0[15],
+            [
+                BlsScalar::from(15),
+                BlsScalar::zero(),
+                BlsScalar::from(15),
+                BlsScalar::from(4)
+            ]
+        );
+
+        assert!(PlookupTable4Arity::create_range_table(
+            MAX_RANGE_TABLE_BITS + 1
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_bitwise_table_bits() {
+        let table = PlookupTable4Arity::create_xor_table(2).unwrap();
+        assert_eq!(table.0.len(), 16);
+
+        assert!(matches!(
+            PlookupTable4Arity::create_xor_table(MAX_BITWISE_TABLE_BITS + 1),
+            Err(Error::TableTooLarge { bits, max_bits })
+                if bits == max_bits + 1 && max_bits == MAX_BITWISE_TABLE_BITS
+        ));
+        assert!(matches!(
+            PlookupTable4Arity::create_and_table(64),
+            Err(Error::TableTooLarge { bits: 64, .. })
+        ));
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut table = PlookupTable4Arity::new();
+        table.insert_multi_xor(0, 2);
+        table.insert_multi_mul(0, 2);
+        table.insert_multi_xor(0, 2);
+        table.insert_mul_row(3, 3, 4);
+        let original = table.clone();
+
+        assert_eq!(table.dedup(), (49, 32));
+        assert_eq!(&table.0[..], &original.0[..32]);
+        assert_eq!(table.dedup(), (32, 32));
+
+        // Every lookup behaves the same after the deduplication
+        (0..5u64).for_each(|a| {
+            (0..5u64).for_each(|b| {
+                [-BlsScalar::one(), BlsScalar::one(), BlsScalar::zero()]
+                    .iter()
+                    .for_each(|d| {
+                        let a = BlsScalar::from(a);
+                        let b = BlsScalar::from(b);
+                        assert_eq!(
+                            table.lookup(a, b, *d).ok(),
+                            original.lookup(a, b, *d).ok()
+                        );
+                    })
+            })
+        });
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut table = PlookupTable4Arity::create_hash_table();
+        let present = table.0[table.0.len() / 2];
+        let absent = [
+            present[0],
+            present[1],
+            present[2] + BlsScalar::one(),
+            present[3],
+        ];
+
+        assert!(table.contains(present));
+        assert!(table.contains(table.0[0]));
+        assert!(!table.contains(absent));
+
+        assert_eq!(
+            table.contains_rows(&[present, absent, table.0[0]]),
+            vec![true, false, true]
+        );
+
+        // Rows changed after a query are taken into account
+        table.0.push(absent);
+        assert!(table.contains(absent));
+        let last = table.0.len() - 1;
+        table.0[last] = present;
+        assert_eq!(table.contains_rows(&[absent]), vec![false]);
+        table.0.clear();
+        table.0.push(absent);
+        assert!(!table.contains(present));
+        assert_eq!(table.contains_rows(&[absent, present]), vec![true, false]);
+        assert!(!PlookupTable4Arity::new().contains(present));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_fn() {
+        use crate::constraint_system::helper::gadget_plookup_tester;
+
+        fn squares() -> PlookupTable4Arity {
+            PlookupTable4Arity::from_fn((0..16u64).map(BlsScalar::from), |x| {
+                x.square()
+            })
+        }
+
+        let table = squares();
+        assert_eq!(table.0.len(), 16);
+        assert_eq!(
+            table.0[5],
+            [
+                BlsScalar::from(5),
+                BlsScalar::zero(),
+                BlsScalar::from(25),
+                BlsScalar::zero()
+            ]
+        );
+
+        // Should pass as 7 is in the domain
+        let res = gadget_plookup_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(7));
+                let zero = composer.zero_var;
+                let square = composer.plookup_query(x, zero, &squares());
+                composer.constrain_to_constant(
+                    square,
+                    BlsScalar::from(49),
+                    None,
+                );
+            },
+            squares(),
+        );
+        assert!(res.is_ok());
+
+        // Should fail as 16 is out of the domain
+        let res = gadget_plookup_tester(
+            |composer| {
+                let x = composer.add_input(BlsScalar::from(16));
+                let zero = composer.zero_var;
+                composer.plookup_query(x, zero, &squares());
+            },
+            squares(),
+        );
+        assert!(res.is_err());
+    }
 }
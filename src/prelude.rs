@@ -11,13 +11,16 @@
 
 #[cfg(feature = "alloc")]
 pub use crate::{
-    circuit::{self, Circuit, PublicInputValue, VerifierData},
+    circuit::{self, Circuit, PublicInputValue, PublicInputs, VerifierData},
     commitment_scheme::kzg10::{
         key::{CommitKey, OpeningKey},
         PublicParameters,
     },
     constraint_system::{Point, StandardComposer, Variable},
-    proof_system::{Proof, Prover, ProverKey, Verifier, VerifierKey},
+    proof_system::{
+        Proof, ProofSize, Prover, ProverKey, Verifier, VerifierKey,
+        WireCommitments,
+    },
 };
 
 /// Re-exported [`dusk-bls12_381::BlsScalar`].
@@ -0,0 +1,232 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Selective disclosure of the Public Inputs of a proof.
+//!
+//! Only part of the Public Inputs are revealed to the verifier, the others
+//! being replaced by a commitment which is opened at the evaluation
+//! challenge of the proof.
+//!
+//! The polynomial `h` interpolating the hidden inputs vanishes at every
+//! other position of the domain, so it's `Z_R * q` where `Z_R` is the
+//! vanishing polynomial of these positions. The prover commits to `q`, and
+//! the verifier evaluates `Z_R` itself: `h` can't be nonzero at a revealed
+//! position, so the revealed inputs are bound by the proof whatever the
+//! commitment.
+
+use crate::commitment_scheme::kzg10::proof::Proof as OpeningProof;
+use crate::commitment_scheme::kzg10::Commitment;
+use crate::error::Error;
+use crate::fft::{EvaluationDomain, Polynomial};
+use alloc::vec::Vec;
+use dusk_bls12_381::{BlsScalar, G1Affine};
+
+/// Public Input at a position of the circuit, as seen by the verifier of a
+/// proof generated with [`Prover::prove_with_committed_pi`].
+///
+/// [`Prover::prove_with_committed_pi`]:
+/// crate::proof_system::Prover::prove_with_committed_pi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicInputSpec {
+    /// Public Input given in cleartext, with the value of the dense vector
+    /// of [`StandardComposer::construct_dense_pi_vec`].
+    ///
+    /// [`StandardComposer::construct_dense_pi_vec`]:
+    /// crate::constraint_system::StandardComposer::construct_dense_pi_vec
+    Revealed(BlsScalar),
+    /// Public Input hidden in the [`CommittedPublicInputs`] of the proof.
+    Committed,
+}
+
+impl PublicInputSpec {
+    /// Returns the cleartext value of the Public Input, which is zero for
+    /// a committed one as it's accounted for by the commitment instead.
+    pub(crate) fn revealed_value(&self) -> BlsScalar {
+        match self {
+            Self::Revealed(value) => *value,
+            Self::Committed => BlsScalar::zero(),
+        }
+    }
+}
+
+/// Public Inputs a [`Proof`] is verified against, see [`Verifier::verify`].
+///
+/// A dense vector of Public Inputs converts into
+/// [`ProofPublicInputs::Dense`].
+///
+/// [`Proof`]: crate::proof_system::Proof
+/// [`Verifier::verify`]: crate::proof_system::Verifier::verify
+#[derive(Debug, Clone, Copy)]
+pub enum ProofPublicInputs<'a> {
+    /// Dense vector of the Public Inputs, with one entry per gate.
+    Dense(&'a [BlsScalar]),
+    /// Public Inputs of a proof generated with
+    /// [`Prover::prove_with_committed_pi`], with one [`PublicInputSpec`] per
+    /// gate, the hidden ones being accounted for by `committed`.
+    ///
+    /// [`Prover::prove_with_committed_pi`]:
+    /// crate::proof_system::Prover::prove_with_committed_pi
+    Disclosed {
+        /// Revealed or committed Public Input of every gate
+        specs: &'a [PublicInputSpec],
+        /// Commitment to the hidden Public Inputs and its opening
+        committed: &'a CommittedPublicInputs,
+    },
+}
+
+impl<'a> From<&'a [BlsScalar]> for ProofPublicInputs<'a> {
+    fn from(public_inputs: &'a [BlsScalar]) -> Self {
+        Self::Dense(public_inputs)
+    }
+}
+
+impl<'a> From<&'a Vec<BlsScalar>> for ProofPublicInputs<'a> {
+    fn from(public_inputs: &'a Vec<BlsScalar>) -> Self {
+        Self::Dense(public_inputs)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [BlsScalar; N]> for ProofPublicInputs<'a> {
+    fn from(public_inputs: &'a [BlsScalar; N]) -> Self {
+        Self::Dense(public_inputs)
+    }
+}
+
+/// Commitment to the hidden Public Inputs of a proof, along with its
+/// opening at the evaluation challenge of the proof.
+///
+/// The revealed Public Inputs are bound by the proof, but the hidden values
+/// only through the commitment: it must be checked against a commitment the
+/// verifier trusts, see [`CommittedPublicInputs::commitment`], as any
+/// hidden values satisfying the circuit would otherwise be accepted. The
+/// commitment isn't blinded either, so hidden values which can be guessed
+/// can be recovered from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommittedPublicInputs {
+    /// Commitment to the quotient of the polynomial interpolating the
+    /// hidden inputs by the vanishing polynomial of the other positions.
+    pub(crate) commitment: Commitment,
+    /// Evaluation of the quotient at the evaluation challenge.
+    pub(crate) evaluation: BlsScalar,
+    /// Commitment to the witness of the opening at the challenge.
+    pub(crate) witness: Commitment,
+}
+
+impl CommittedPublicInputs {
+    /// Returns the commitment to the hidden Public Inputs, which only
+    /// depends on their values and positions and on the size of the
+    /// circuit.
+    pub fn commitment(&self) -> G1Affine {
+        self.commitment.0
+    }
+
+    /// Returns the opening of the commitment at the evaluation challenge of
+    /// the proof.
+    pub(crate) fn opening(&self) -> OpeningProof {
+        OpeningProof {
+            commitment_to_witness: self.witness,
+            evaluated_point: self.evaluation,
+            commitment_to_polynomial: self.commitment,
+        }
+    }
+}
+
+/// Returns the quotient `q` of the polynomial interpolating the values of
+/// `public_inputs` at the `hidden` positions, which must be distinct, by the
+/// vanishing polynomial of the other positions of `domain`.
+///
+/// `h * Z_H` is `q * (X^n - 1)`, where `Z_H` vanishes at the hidden
+/// positions, and `q` has less than `hidden.len()` coefficients, so they are
+/// the ones of `h * Z_H` past the `n` first.
+pub(crate) fn hidden_quotient(
+    domain: &EvaluationDomain,
+    public_inputs: &[BlsScalar],
+    hidden: &[usize],
+) -> Polynomial {
+    let mut hidden_inputs = vec![BlsScalar::zero(); domain.size()];
+    hidden
+        .iter()
+        .for_each(|pos| hidden_inputs[*pos] = public_inputs[*pos]);
+
+    let mut product = domain.ifft(&hidden_inputs);
+    hidden.iter().for_each(|pos| {
+        // Multiplies by X - w^pos
+        let root = domain.group_gen.pow(&[*pos as u64, 0, 0, 0]);
+        product.push(BlsScalar::zero());
+        (1..product.len())
+            .rev()
+            .for_each(|k| product[k] = product[k - 1] - root * product[k]);
+        product[0] = -root * product[0];
+    });
+
+    Polynomial::from_coefficients_vec(product.split_off(domain.size()))
+}
+
+/// Evaluates at `z` the vanishing polynomial of the positions of `domain`
+/// which are not `hidden`, that is `(z^n - 1) / Z_H(z)`.
+///
+/// Returns [`Error::PublicInputOutOfRange`] if a hidden position is not in
+/// the domain.
+pub(crate) fn revealed_vanishing_eval(
+    domain: &EvaluationDomain,
+    hidden: &[usize],
+    z: &BlsScalar,
+) -> Result<BlsScalar, Error> {
+    let mut z_hidden = BlsScalar::one();
+    for pos in hidden {
+        if *pos >= domain.size() {
+            return Err(Error::PublicInputOutOfRange {
+                position: *pos,
+                size: domain.size(),
+            });
+        }
+        z_hidden *= z - domain.group_gen.pow(&[*pos as u64, 0, 0, 0]);
+    }
+
+    // The challenge is a root of unity of the domain with negligible
+    // probability
+    Ok(domain.evaluate_vanishing_polynomial(z)
+        * z_hidden.invert().unwrap_or(BlsScalar::zero()))
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_hidden_quotient() {
+        let domain = EvaluationDomain::new(16).unwrap();
+        let public_inputs: Vec<BlsScalar> =
+            (0..16u64).map(|i| BlsScalar::from(i * i + 1)).collect();
+        let hidden = [2, 5, 13];
+
+        let mut hidden_inputs = vec![BlsScalar::zero(); 16];
+        hidden
+            .iter()
+            .for_each(|pos| hidden_inputs[*pos] = public_inputs[*pos]);
+        let h = Polynomial::from_coefficients_vec(domain.ifft(&hidden_inputs));
+
+        // h = Z_R * q everywhere
+        let q = hidden_quotient(&domain, &public_inputs, &hidden);
+        assert!(q.coeffs.len() <= hidden.len());
+        let z = BlsScalar::random(&mut OsRng);
+        assert_eq!(
+            h.evaluate(&z),
+            revealed_vanishing_eval(&domain, &hidden, &z).unwrap()
+                * q.evaluate(&z)
+        );
+
+        assert!(matches!(
+            revealed_vanishing_eval(&domain, &[3, 16], &z),
+            Err(Error::PublicInputOutOfRange {
+                position: 16,
+                size: 16
+            })
+        ));
+    }
+}
@@ -8,6 +8,10 @@
 
 cfg_if::cfg_if!(
     if #[cfg(feature = "alloc")] {
+        pub(crate) mod disclosure;
+        pub use disclosure::{
+            CommittedPublicInputs, ProofPublicInputs, PublicInputSpec,
+        };
         mod preprocess;
         /// Represents a PLONK Prover
         pub mod prover;
@@ -25,5 +29,5 @@ cfg_if::cfg_if!(
 
 /// Represents PLONK Proof
 pub mod proof;
-pub use proof::Proof;
+pub use proof::{Proof, ProofSize, WireCommitments};
 pub(crate) mod linearisation_poly;
@@ -108,7 +108,7 @@ impl StandardComposer {
         commit_key: &CommitKey,
         transcript: &mut Transcript,
     ) -> Result<ProverKey, Error> {
-        let (_, selectors, preprocessed_table, domain) =
+        let (verifier_key, selectors, preprocessed_table, domain) =
             self.preprocess_shared(commit_key, transcript)?;
 
         let domain_4n = EvaluationDomain::new(4 * domain.size())?;
@@ -289,6 +289,7 @@ impl StandardComposer {
             // Compute 4n evaluations for X^n -1
             v_h_coset_4n: domain_4n
                 .compute_vanishing_poly_over_coset(domain.size() as u64),
+            circuit_digest: verifier_key.circuit_digest(),
         };
 
         Ok(prover_key)
@@ -324,10 +325,13 @@ impl StandardComposer {
         ),
         Error,
     > {
-        let domain = EvaluationDomain::new(self.total_size())?;
+        let domain = self.domain()?;
 
         // Check that the length of the wires is consistent.
         self.check_poly_same_len()?;
+        self.check_lookup_table_size()?;
+
+        let report = self.circuit_report();
 
         // 1. Pad circuit to a power of two
         self.pad(domain.size as usize - self.n);
@@ -395,7 +399,7 @@ impl StandardComposer {
 
         // Preprocess the lookup table
         let preprocessed_table = PreprocessedTable4Arity::preprocess(
-            &self.lookup_table,
+            &self.full_lookup_table(),
             &commit_key,
             domain.size() as u32,
         )?;
@@ -451,6 +455,12 @@ impl StandardComposer {
 
         let verifier_key = widget::VerifierKey {
             n: self.circuit_size(),
+            plookup_gates: report
+                .plookup_gates
+                .saturating_sub(StandardComposer::DUMMY_LOOKUP_GATES),
+            lookup_table_rows: report
+                .lookup_table_rows
+                .saturating_sub(StandardComposer::DUMMY_LOOKUP_ROWS),
             arithmetic: arithmetic_verifier_key,
             logic: logic_verifier_key,
             range: range_verifier_key,
@@ -519,4 +529,88 @@ mod test {
         assert!(composer.w_r.len() == size);
         assert!(composer.w_o.len() == size);
     }
+
+    #[test]
+    fn test_verifier_key_uses_plookup() {
+        use crate::commitment_scheme::kzg10::PublicParameters;
+        use crate::plookup::PlookupTable4Arity;
+        use dusk_bytes::Serializable;
+        use rand_core::OsRng;
+
+        let public_parameters =
+            PublicParameters::setup(1 << 8, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(1 << 8).unwrap();
+
+        let mut arithmetic = StandardComposer::new();
+        dummy_gadget(10, &mut arithmetic);
+        let arithmetic_key = arithmetic
+            .preprocess_verifier(&ck, &mut Transcript::new(b"test"))
+            .unwrap();
+
+        let mut plookup = StandardComposer::new();
+        let mut table = PlookupTable4Arity::new();
+        table.insert_multi_xor(0, 2);
+        plookup.append_lookup_table(&table);
+        let one = plookup.add_input(BlsScalar::one());
+        let two = plookup.add_input(BlsScalar::from(2));
+        let three = plookup.add_input(BlsScalar::from(3));
+        let tag = Some(plookup.add_input(-BlsScalar::one()));
+        plookup.plookup_gate((one, two, three, tag), BlsScalar::zero());
+        plookup.plookup_gate((two, one, three, tag), BlsScalar::zero());
+        dummy_gadget(10, &mut plookup);
+        let plookup_key = plookup
+            .preprocess_verifier(&ck, &mut Transcript::new(b"test"))
+            .unwrap();
+
+        assert!(!arithmetic_key.uses_plookup());
+        assert_eq!(arithmetic_key.lookup_table_rows(), 0);
+        assert!(plookup_key.uses_plookup());
+        assert_eq!(plookup_key.lookup_table_rows(), table.0.len());
+
+        // The metadata survives the serialization of the key
+        let bytes = plookup_key.to_bytes();
+        let got = widget::VerifierKey::from_bytes(&bytes).unwrap();
+        assert!(got.uses_plookup());
+        assert_eq!(got.lookup_table_rows(), table.0.len());
+    }
+
+    #[test]
+    fn test_strict_plookup() {
+        use crate::commitment_scheme::kzg10::PublicParameters;
+        use crate::error::Error;
+        use crate::plookup::PlookupTable4Arity;
+        use rand_core::OsRng;
+
+        let public_parameters =
+            PublicParameters::setup(1 << 8, &mut OsRng).unwrap();
+        let (ck, _) = public_parameters.trim(1 << 8).unwrap();
+
+        // Three lookups into a table of two rows
+        let composer = |strict: bool| {
+            let mut composer = StandardComposer::new();
+            let table = PlookupTable4Arity::from_fn(
+                (0..2u64).map(BlsScalar::from),
+                |x| x.double(),
+            );
+            composer.append_lookup_table(&table);
+            composer.set_strict_plookup(strict);
+            let zero = composer.zero_var;
+            (0..3).for_each(|_| {
+                composer.plookup_query(zero, zero, &table);
+            });
+            dummy_gadget(10, &mut composer);
+            composer
+        };
+
+        let res = composer(false)
+            .preprocess_verifier(&ck, &mut Transcript::new(b"test"));
+        assert!(res.is_ok());
+
+        let res = composer(true)
+            .preprocess_verifier(&ck, &mut Transcript::new(b"test"));
+        assert!(matches!(
+            res,
+            Err(Error::LookupTableTooSmall { gates: 3, rows: 2 })
+        ));
+    }
 }
@@ -13,6 +13,7 @@
 use super::linearisation_poly::ProofEvaluations;
 use crate::commitment_scheme::kzg10::Commitment;
 use crate::error::Error;
+use dusk_bls12_381::{BlsScalar, G1Affine};
 use dusk_bytes::{DeserializableSlice, Serializable};
 
 /// A Proof is a composition of `Commitment`s to the Witness, Permutation,
@@ -66,9 +67,17 @@ pub struct Proof {
     pub(crate) w_zw_comm: Commitment,
     /// Subset of all of the evaluations added to the proof.
     pub(crate) evaluations: ProofEvaluations,
+
+    /// Digest of the circuit the proof was generated for.
+    pub(crate) circuit_digest: [u8; 32],
+}
+
+impl Proof {
+    /// Version of the encoding produced by [`Proof::to_versioned_bytes`].
+    pub const VERSION: u8 = 1;
 }
 
-impl Serializable<{ 15 * Commitment::SIZE + ProofEvaluations::SIZE }>
+impl Serializable<{ 15 * Commitment::SIZE + ProofEvaluations::SIZE + 32 }>
     for Proof
 {
     type Error = dusk_bytes::Error;
@@ -95,6 +104,7 @@ impl Serializable<{ 15 * Commitment::SIZE + ProofEvaluations::SIZE }>
         writer.write(&self.w_z_comm.to_bytes());
         writer.write(&self.w_zw_comm.to_bytes());
         writer.write(&self.evaluations.to_bytes());
+        writer.write(&self.circuit_digest);
 
         buf
     }
@@ -118,6 +128,8 @@ impl Serializable<{ 15 * Commitment::SIZE + ProofEvaluations::SIZE }>
         let w_z_comm = Commitment::from_reader(&mut buffer)?;
         let w_zw_comm = Commitment::from_reader(&mut buffer)?;
         let evaluations = ProofEvaluations::from_reader(&mut buffer)?;
+        let mut circuit_digest = [0u8; 32];
+        circuit_digest.copy_from_slice(buffer);
 
         Ok(Proof {
             a_comm,
@@ -136,29 +148,162 @@ impl Serializable<{ 15 * Commitment::SIZE + ProofEvaluations::SIZE }>
             w_z_comm,
             w_zw_comm,
             evaluations,
+            circuit_digest,
         })
     }
 }
 
+/// Commitments to the left, right, output and fourth wire polynomials of a
+/// [`Proof`].
+///
+/// # Note
+/// The wire polynomials are not blinded, so these commitments are not
+/// hiding: anyone can check them against a guess of the witness, which
+/// reveals a witness of low entropy. They should only be published when the
+/// witness is public or unpredictable, for example to anchor it for data
+/// availability purposes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WireCommitments {
+    /// Commitment to the witness polynomial for the left wires.
+    pub left: G1Affine,
+    /// Commitment to the witness polynomial for the right wires.
+    pub right: G1Affine,
+    /// Commitment to the witness polynomial for the output wires.
+    pub output: G1Affine,
+    /// Commitment to the witness polynomial for the fourth wires.
+    pub fourth: G1Affine,
+}
+
+impl Proof {
+    /// Returns the commitments to the wire polynomials embedded in the
+    /// [`Proof`].
+    pub fn wire_commitments(&self) -> WireCommitments {
+        WireCommitments {
+            left: self.a_comm.0,
+            right: self.b_comm.0,
+            output: self.c_comm.0,
+            fourth: self.d_comm.0,
+        }
+    }
+}
+
+/// Sizes of the elements of a [`Proof`], to compare the overhead of circuit
+/// variants.
+///
+/// Every proof carries the same elements whatever its circuit, including
+/// the plookup commitments and evaluations of circuits without lookups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofSize {
+    /// Number of G1 commitments in the proof.
+    pub commitments: usize,
+    /// Number of scalar evaluations in the proof.
+    pub evaluations: usize,
+    /// Number of bytes of the encoding of the proof, including the digest of
+    /// its circuit.
+    pub bytes: usize,
+}
+
+impl Proof {
+    /// Returns the number of commitments and evaluations of the [`Proof`],
+    /// along with the size of its encoding by [`Proof::to_bytes`].
+    pub fn size_breakdown(&self) -> ProofSize {
+        let commitments = [
+            &self.a_comm,
+            &self.b_comm,
+            &self.c_comm,
+            &self.d_comm,
+            &self.f_comm,
+            &self.h_1_comm,
+            &self.h_2_comm,
+            &self.z_comm,
+            &self.p_comm,
+            &self.t_1_comm,
+            &self.t_2_comm,
+            &self.t_3_comm,
+            &self.t_4_comm,
+            &self.w_z_comm,
+            &self.w_zw_comm,
+        ];
+        let evaluations = ProofEvaluations::SIZE / BlsScalar::SIZE;
+
+        ProofSize {
+            commitments: commitments.len(),
+            evaluations,
+            bytes: commitments.len() * Commitment::SIZE
+                + evaluations * BlsScalar::SIZE
+                + self.circuit_digest.len(),
+        }
+    }
+}
+
 #[cfg(feature = "alloc")]
 pub(crate) mod alloc {
     use super::*;
     use crate::{
         commitment_scheme::kzg10::{AggregateProof, OpeningKey},
         fft::EvaluationDomain,
+        proof_system::disclosure::{
+            revealed_vanishing_eval, CommittedPublicInputs,
+        },
+        proof_system::verifier::VerifierCache,
         proof_system::widget::VerifierKey,
         transcript::TranscriptProtocol,
-        util::batch_inversion,
+        util::batch_invert,
     };
     use ::alloc::vec::Vec;
     use dusk_bls12_381::{
-        multiscalar_mul::msm_variable_base, BlsScalar, G1Affine,
+        multiscalar_mul::msm_variable_base, BlsScalar, G1Affine, G1Projective,
     };
     use merlin::Transcript;
-    #[cfg(feature = "std")]
+    #[cfg(feature = "parallel")]
     use rayon::prelude::*;
 
+    /// Challenges of a [`Proof`] derived before its evaluations.
+    struct Challenges {
+        zeta: BlsScalar,
+        beta: BlsScalar,
+        gamma: BlsScalar,
+        delta: BlsScalar,
+        epsilon: BlsScalar,
+        z_challenge: BlsScalar,
+    }
+
     impl Proof {
+        /// Serializes the [`Proof`] prefixed by a byte with the version of
+        /// the encoding, [`Proof::VERSION`].
+        pub fn to_versioned_bytes(&self) -> Vec<u8> {
+            let mut bytes = Vec::with_capacity(1 + Self::SIZE);
+            bytes.push(Self::VERSION);
+            bytes.extend_from_slice(&self.to_bytes());
+            bytes
+        }
+
+        /// Deserializes a [`Proof`] encoded with
+        /// [`Proof::to_versioned_bytes`].
+        ///
+        /// Returns [`Error::UnsupportedProofVersion`] if the version byte
+        /// doesn't match [`Proof::VERSION`], and [`Error::NotEnoughBytes`] or
+        /// a length error if the size of `bytes` is wrong.
+        pub fn from_versioned_bytes(bytes: &[u8]) -> Result<Self, Error> {
+            let (&version, bytes) =
+                bytes.split_first().ok_or(Error::NotEnoughBytes)?;
+            if version != Self::VERSION {
+                return Err(Error::UnsupportedProofVersion { version });
+            }
+            if bytes.len() < Self::SIZE {
+                return Err(Error::NotEnoughBytes);
+            }
+            if bytes.len() > Self::SIZE {
+                return Err(dusk_bytes::Error::BadLength {
+                    found: bytes.len(),
+                    expected: Self::SIZE,
+                }
+                .into());
+            }
+
+            Ok(Self::from_slice(bytes)?)
+        }
+
         /// Performs the verification of a `Proof` returning a boolean result.
         pub(crate) fn verify(
             &self,
@@ -166,11 +311,29 @@ pub(crate) mod alloc {
             transcript: &mut Transcript,
             opening_key: &OpeningKey,
             pub_inputs: &[BlsScalar],
+            committed: Option<(&CommittedPublicInputs, &[usize])>,
+            cache: Option<&VerifierCache>,
         ) -> Result<(), Error> {
-            let domain = EvaluationDomain::new(verifier_key.n)?;
+            let (total_c, total_w) = self.accumulate(
+                verifier_key,
+                transcript,
+                opening_key,
+                pub_inputs,
+                committed,
+                cache,
+            )?;
 
-            // Subgroup checks are done when the proof is deserialised.
+            if opening_key.check_accumulated(total_c, total_w).is_err() {
+                return Err(Error::ProofVerificationError);
+            }
+
+            Ok(())
+        }
 
+        /// Derives the challenges of the proof up to the evaluation
+        /// challenge, appending the commitments they depend on to
+        /// `transcript` as the prover did.
+        fn challenges(&self, transcript: &mut Transcript) -> Challenges {
             // In order for the Verifier and Prover to have the same view in the
             // non-interactive setting Both parties must commit the
             // same elements into the transcript Below the verifier
@@ -205,6 +368,61 @@ pub(crate) mod alloc {
             // Compute evaluation challenge
             let z_challenge = transcript.challenge_scalar(b"z_challenge");
 
+            Challenges {
+                zeta,
+                beta,
+                gamma,
+                delta,
+                epsilon,
+                z_challenge,
+            }
+        }
+
+        /// Returns the evaluation challenge of the proof, starting from the
+        /// `transcript` it was generated with.
+        pub(crate) fn evaluation_challenge(
+            &self,
+            mut transcript: Transcript,
+        ) -> BlsScalar {
+            self.challenges(&mut transcript).z_challenge
+        }
+
+        /// Performs every check of the verification of a `Proof` except for
+        /// the final pairing, returning the accumulator that the pairing
+        /// must be checked on, see [`OpeningKey::accumulate`].
+        ///
+        /// The Public Inputs hidden in `committed` at the given positions are
+        /// added to the ones of `pub_inputs`, and the opening of their
+        /// commitment is accumulated along with the ones of the proof.
+        pub(crate) fn accumulate(
+            &self,
+            verifier_key: &VerifierKey,
+            transcript: &mut Transcript,
+            opening_key: &OpeningKey,
+            pub_inputs: &[BlsScalar],
+            committed: Option<(&CommittedPublicInputs, &[usize])>,
+            cache: Option<&VerifierCache>,
+        ) -> Result<(G1Projective, G1Projective), Error> {
+            if self.circuit_digest != verifier_key.circuit_digest() {
+                return Err(Error::CircuitMismatch);
+            }
+
+            let domain = match cache {
+                Some(cache) => cache.domain,
+                None => EvaluationDomain::new(verifier_key.n)?,
+            };
+
+            // Subgroup checks are done when the proof is deserialised.
+
+            let Challenges {
+                zeta,
+                beta,
+                gamma,
+                delta,
+                epsilon,
+                z_challenge,
+            } = self.challenges(transcript);
+
             // Add h polynomials to transcript
             transcript.append_commitment(b"h1", &self.h_1_comm);
             transcript.append_commitment(b"h2", &self.h_2_comm);
@@ -248,16 +466,32 @@ pub(crate) mod alloc {
                     + verifier_key.lookup.table_4.0 * zeta * zeta * zeta,
             ));
 
+            // Compute the public input polynomial evaluated at `z_challenge`,
+            // the hidden inputs being zero in `pub_inputs`. Their polynomial
+            // is the committed quotient times the vanishing polynomial of the
+            // revealed positions, so it can't offset the revealed inputs
+            let hidden_eval = match committed {
+                Some((committed, hidden)) => {
+                    revealed_vanishing_eval(&domain, hidden, &z_challenge)?
+                        * committed.evaluation
+                }
+                None => BlsScalar::zero(),
+            };
+            let pi_eval = compute_barycentric_eval(
+                pub_inputs,
+                &z_challenge,
+                &domain,
+                cache.map(|cache| cache.group_gen_inv_powers.as_slice()),
+            ) + hidden_eval;
+
             // Compute quotient polynomial evaluated at `z_challenge`
             let t_eval = self.compute_quotient_evaluation(
-                &domain,
-                pub_inputs,
+                &pi_eval,
                 &alpha,
                 &beta,
                 &gamma,
                 &delta,
                 &epsilon,
-                &z_challenge,
                 &z_h_eval,
                 &l1_eval,
                 &self.evaluations.perm_eval,
@@ -400,40 +634,35 @@ pub(crate) mod alloc {
             transcript.append_commitment(b"w_z", &self.w_z_comm);
             transcript.append_commitment(b"w_z_w", &self.w_zw_comm);
             // Batch check
-            if opening_key
-                .batch_check(
-                    &[z_challenge, (z_challenge * domain.group_gen)],
-                    &[flattened_proof_a, flattened_proof_b],
-                    transcript,
-                )
-                .is_err()
-            {
-                return Err(Error::ProofVerificationError.into());
+            let mut points =
+                vec![z_challenge, (z_challenge * domain.group_gen)];
+            let mut openings = vec![flattened_proof_a, flattened_proof_b];
+            if let Some((committed, _)) = committed {
+                // Add the opening of the hidden inputs to transcript
+                transcript
+                    .append_scalar(b"committed_pi_eval", &committed.evaluation);
+                transcript
+                    .append_commitment(b"committed_pi_w", &committed.witness);
+                points.push(z_challenge);
+                openings.push(committed.opening());
             }
-
-            Ok(())
+            Ok(opening_key.accumulate(&points, &openings, transcript))
         }
 
         #[allow(clippy::too_many_arguments)]
         fn compute_quotient_evaluation(
             &self,
-            domain: &EvaluationDomain,
-            pub_inputs: &[BlsScalar],
+            pi_eval: &BlsScalar,
             alpha: &BlsScalar,
             beta: &BlsScalar,
             gamma: &BlsScalar,
             delta: &BlsScalar,
             epsilon: &BlsScalar,
-            z_challenge: &BlsScalar,
             z_h_eval: &BlsScalar,
             l1_eval: &BlsScalar,
             z_hat_eval: &BlsScalar,
             lookup_sep_challenge: &BlsScalar,
         ) -> BlsScalar {
-            // Compute the public input polynomial evaluated at `z_challenge`
-            let pi_eval =
-                compute_barycentric_eval(pub_inputs, z_challenge, domain);
-
             // Compute powers of alpha_0
             let alpha_sq = alpha.square();
 
@@ -608,16 +837,17 @@ pub(crate) mod alloc {
         evaluations: &[BlsScalar],
         point: &BlsScalar,
         domain: &EvaluationDomain,
+        group_gen_inv_powers: Option<&[BlsScalar]>,
     ) -> BlsScalar {
         let numerator = (point.pow(&[domain.size() as u64, 0, 0, 0])
             - BlsScalar::one())
             * domain.size_inv;
 
         // Indices with non-zero evaluations
-        #[cfg(not(feature = "std"))]
+        #[cfg(not(feature = "parallel"))]
         let range = (0..evaluations.len()).into_iter();
 
-        #[cfg(feature = "std")]
+        #[cfg(feature = "parallel")]
         let range = (0..evaluations.len()).into_par_iter();
 
         let non_zero_evaluations: Vec<usize> = range
@@ -628,10 +858,10 @@ pub(crate) mod alloc {
             .collect();
 
         // Only compute the denominators with non-zero evaluations
-        #[cfg(not(feature = "std"))]
+        #[cfg(not(feature = "parallel"))]
         let range = (0..non_zero_evaluations.len()).into_iter();
 
-        #[cfg(feature = "std")]
+        #[cfg(feature = "parallel")]
         let range = (0..non_zero_evaluations.len()).into_par_iter();
 
         let mut denominators: Vec<BlsScalar> = range
@@ -640,11 +870,15 @@ pub(crate) mod alloc {
                 // index of non-zero evaluation
                 let index = non_zero_evaluations[i];
 
-                (domain.group_gen_inv.pow(&[index as u64, 0, 0, 0]) * point)
-                    - BlsScalar::one()
+                let group_gen_inv_pow = match group_gen_inv_powers {
+                    Some(powers) => powers[index],
+                    None => domain.group_gen_inv.pow(&[index as u64, 0, 0, 0]),
+                };
+
+                (group_gen_inv_pow * point) - BlsScalar::one()
             })
             .collect();
-        batch_inversion(&mut denominators);
+        batch_invert(&mut denominators);
 
         let result: BlsScalar = range
             .map(|i| {
@@ -662,7 +896,6 @@ pub(crate) mod alloc {
 #[cfg(test)]
 mod proof_tests {
     use super::*;
-    use dusk_bls12_381::BlsScalar;
     use rand_core::OsRng;
 
     #[test]
@@ -709,10 +942,58 @@ mod proof_tests {
                 table_eval: BlsScalar::random(&mut OsRng),
                 table_next_eval: BlsScalar::random(&mut OsRng),
             },
+            circuit_digest: [0xcd; 32],
         };
 
         let proof_bytes = proof.to_bytes();
         let got_proof = Proof::from_bytes(&proof_bytes).unwrap();
         assert_eq!(got_proof, proof);
     }
+
+    #[test]
+    fn test_versioned_bytes() {
+        let proof = Proof {
+            circuit_digest: [0xcd; 32],
+            ..Proof::default()
+        };
+
+        let bytes = proof.to_versioned_bytes();
+        assert_eq!(bytes.len(), 1 + Proof::SIZE);
+        assert_eq!(bytes[0], Proof::VERSION);
+        assert_eq!(Proof::from_versioned_bytes(&bytes).unwrap(), proof);
+
+        assert!(matches!(
+            Proof::from_versioned_bytes(&bytes[..bytes.len() - 1]),
+            Err(Error::NotEnoughBytes)
+        ));
+        assert!(matches!(
+            Proof::from_versioned_bytes(&[]),
+            Err(Error::NotEnoughBytes)
+        ));
+
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert!(matches!(
+            Proof::from_versioned_bytes(&longer),
+            Err(Error::BytesError(dusk_bytes::Error::BadLength { .. }))
+        ));
+
+        let mut other_version = bytes;
+        other_version[0] = Proof::VERSION + 1;
+        assert!(matches!(
+            Proof::from_versioned_bytes(&other_version),
+            Err(Error::UnsupportedProofVersion { version })
+                if version == Proof::VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn test_size_breakdown() {
+        let proof = Proof::default();
+        let size = proof.size_breakdown();
+
+        assert_eq!(size.commitments, 15);
+        assert_eq!(size.evaluations, 24);
+        assert_eq!(size.bytes, proof.to_bytes().len());
+    }
 }
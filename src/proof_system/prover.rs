@@ -5,25 +5,62 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use crate::{
-    commitment_scheme::kzg10::CommitKey,
+    commitment_scheme::kzg10::{CommitKey, Commitment},
     constraint_system::{StandardComposer, Variable},
     error::Error,
     fft::{EvaluationDomain, Polynomial},
     plookup::MultiSet,
     proof_system::{
-        linearisation_poly, proof::Proof, quotient_poly, ProverKey,
+        disclosure::hidden_quotient, linearisation_poly, proof::Proof,
+        quotient_poly, CommittedPublicInputs, ProverKey, WireCommitments,
     },
     transcript::TranscriptProtocol,
 };
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
+use hashbrown::HashMap;
 use merlin::Transcript;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// FFT precomputations that only depend on the size of the circuit and would
+/// otherwise be recomputed on every call to [`Prover::prove`].
+#[derive(Debug, Clone)]
+pub(crate) struct ProverCache {
+    /// Evaluation domain of the circuit.
+    pub(crate) domain: EvaluationDomain,
+    /// Evaluation domain of four times the size, used for the quotient.
+    pub(crate) domain_4n: EvaluationDomain,
+    /// Roots of unity of `domain`, from `0` to `n - 1`.
+    pub(crate) roots: Vec<BlsScalar>,
+}
+
+impl ProverCache {
+    /// Precomputes the cached quantities for a domain that fits `size`
+    /// elements.
+    pub(crate) fn new(size: usize) -> Result<Self, Error> {
+        let domain = EvaluationDomain::new(size)?;
+        let domain_4n = EvaluationDomain::new(4 * domain.size())?;
+        let roots = domain.elements().collect();
+
+        Ok(Self {
+            domain,
+            domain_4n,
+            roots,
+        })
+    }
+}
+
 /// Abstraction structure designed to construct a circuit and generate
 /// [`Proof`]s for it.
 #[allow(missing_debug_implementations)]
 
 /// Prover composes a circuit and builds a proof
+///
+/// # Note
+/// The wire polynomials are committed to without random blinding, so proofs
+/// are deterministic and there is no blinding to toggle off.
 #[allow(missing_debug_implementations)]
 pub struct Prover {
     /// ProverKey which is used to create proofs about a specific plookup
@@ -34,6 +71,14 @@ pub struct Prover {
     /// Store the messages exchanged during the preprocessing stage
     /// This is copied each time, we make a proof
     pub preprocessed_transcript: Transcript,
+
+    /// FFT precomputations reused by every call to [`Prover::prove`] for a
+    /// circuit of the same size
+    pub(crate) cache: Option<ProverCache>,
+
+    /// Whether the witness is checked against every gate before proving,
+    /// see [`Prover::set_debug`]
+    pub(crate) debug: bool,
 }
 
 impl Prover {
@@ -56,9 +101,39 @@ impl Prover {
             prover_key: None,
             cs: StandardComposer::new(),
             preprocessed_transcript: Transcript::new(label),
+            cache: None,
+            debug: false,
         }
     }
 
+    /// Creates a new `Prover` instance whose Fiat-Shamir transcript is
+    /// domain-separated by `label`.
+    ///
+    /// Proofs are deterministic, so the same circuit, witness and label
+    /// always give the same [`Proof`]. A [`Verifier`] must be created with
+    /// the same label to verify it.
+    ///
+    /// [`Verifier`]: crate::proof_system::Verifier
+    pub fn with_transcript_label(label: &'static [u8]) -> Prover {
+        Prover::new(label)
+    }
+
+    /// Creates a new `Prover` instance whose transcript is domain-separated
+    /// by `label` and then keyed with the public `seed`, see
+    /// [`Prover::with_transcript_label`].
+    ///
+    /// The [`Verifier`] is seeded the same way with
+    /// [`Verifier::with_transcript_seed`].
+    ///
+    /// [`Verifier`]: crate::proof_system::Verifier
+    /// [`Verifier::with_transcript_seed`]:
+    /// crate::proof_system::Verifier::with_transcript_seed
+    pub fn with_transcript_seed(label: &'static [u8], seed: &[u8]) -> Prover {
+        let mut prover = Prover::new(label);
+        prover.key_transcript(b"seed", seed);
+        prover
+    }
+
     /// Preprocesses the underlying constraint system
     pub fn preprocess(&mut self, commit_key: &CommitKey) -> Result<(), Error> {
         if self.prover_key.is_some() {
@@ -68,6 +143,7 @@ impl Prover {
             .cs
             .preprocess_prover(commit_key, &mut self.preprocessed_transcript)?;
         self.prover_key = Some(pk);
+        log_debug!("prover preprocessing done");
         Ok(())
     }
 
@@ -77,6 +153,43 @@ impl Prover {
             prover_key: None,
             cs: StandardComposer::with_expected_size(size),
             preprocessed_transcript: Transcript::new(label),
+            cache: None,
+            debug: false,
+        }
+    }
+
+    /// Turns the debug proving mode on or off.
+    ///
+    /// In debug mode, the witness is checked against every gate, lookups
+    /// included, before proving. Proving then fails with
+    /// [`Error::Unsatisfiable`], holding the index and the wire values of the
+    /// first gate that isn't satisfied, instead of returning a proof that
+    /// doesn't verify. The check evaluates the whole circuit, so it's meant
+    /// for debugging only.
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+    }
+
+    /// Returns the first gate, arithmetic or lookup, that the witness
+    /// doesn't satisfy, see [`Prover::set_debug`].
+    fn check_witness(&self) -> Result<(), Error> {
+        let lookup = self
+            .cs
+            .precheck_lookups()
+            .err()
+            .and_then(|failures| failures.into_iter().next());
+
+        match (self.cs.try_check_circuit_satisfied(), lookup) {
+            (Err(Error::Unsatisfiable { gate, .. }), Some((first, wires)))
+                if first < gate =>
+            {
+                Err(Error::Unsatisfiable { gate: first, wires })
+            }
+            (Err(error), _) => Err(error),
+            (Ok(()), Some((gate, wires))) => {
+                Err(Error::Unsatisfiable { gate, wires })
+            }
+            (Ok(()), None) => Ok(()),
         }
     }
 
@@ -124,14 +237,19 @@ impl Prover {
 
     /// Convert variables to their actual witness values.
     pub(crate) fn to_scalars(&self, vars: &[Variable]) -> Vec<BlsScalar> {
-        vars.iter().map(|var| self.cs.variables[var]).collect()
+        #[cfg(feature = "parallel")]
+        let witness_values = witness_values_parallel;
+        #[cfg(not(feature = "parallel"))]
+        let witness_values = witness_values_serial;
+
+        witness_values(&self.cs.variables, vars)
     }
 
     /// Resets the witnesses in the prover object.
     /// This function is used when the user wants to make multiple proofs with
     /// the same circuit.
     pub fn clear_witness(&mut self) {
-        self.cs = StandardComposer::new();
+        self.cs.clear();
     }
 
     /// Clears all data in the `Prover` instance.
@@ -141,6 +259,7 @@ impl Prover {
         self.clear_witness();
         self.prover_key = None;
         self.preprocessed_transcript = Transcript::new(b"plonk");
+        self.cache = None;
     }
 
     /// Keys the [`Transcript`] with additional seed information
@@ -149,6 +268,49 @@ impl Prover {
         self.preprocessed_transcript.append_message(label, message);
     }
 
+    /// Computes the commitments to the wire polynomials of the current
+    /// witness, which are the same as the ones embedded in the [`Proof`]
+    /// generated for it, see [`Proof::wire_commitments`].
+    ///
+    /// # Note
+    /// Since [`Prover::prove`] clears the witness, this method should be
+    /// called before proving. The commitments are not hiding, see
+    /// [`WireCommitments`].
+    pub fn wire_commitments(
+        &self,
+        commit_key: &CommitKey,
+    ) -> Result<WireCommitments, Error> {
+        let domain = EvaluationDomain::new(self.domain_size())?;
+        let commit = |wire: &[Variable]| {
+            self.commit_wire(commit_key, &domain, wire)
+                .map(|(_, _, commitment)| commitment.0)
+        };
+
+        Ok(WireCommitments {
+            left: commit(&self.cs.w_l)?,
+            right: commit(&self.cs.w_r)?,
+            output: commit(&self.cs.w_o)?,
+            fourth: commit(&self.cs.w_4)?,
+        })
+    }
+
+    /// Computes the witness values of `wire`, padded to the size of
+    /// `domain`, along with the wire polynomial interpolating them and its
+    /// commitment.
+    fn commit_wire(
+        &self,
+        commit_key: &CommitKey,
+        domain: &EvaluationDomain,
+        wire: &[Variable],
+    ) -> Result<(Vec<BlsScalar>, Polynomial, Commitment), Error> {
+        let pad = vec![BlsScalar::zero(); domain.size() - wire.len()];
+        let scalars = [&self.to_scalars(wire)[..], &pad].concat();
+        let poly = Polynomial::from_coefficients_vec(domain.ifft(&scalars));
+        let commitment = commit_key.commit(&poly)?;
+
+        Ok((scalars, poly, commitment))
+    }
+
     /// Creates a [`Proof]` that demonstrates that a circuit is satisfied.
     /// # Note
     /// If you intend to construct multiple [`Proof`]s with different witnesses,
@@ -160,12 +322,31 @@ impl Prover {
         commit_key: &CommitKey,
         prover_key: &ProverKey,
     ) -> Result<Proof, Error> {
-        // make sure the domain is big enough to handle the circuit as well as
-        // the lookup table
-        let domain = EvaluationDomain::new(core::cmp::max(
-            self.cs.circuit_size(),
-            self.cs.lookup_table.0.len(),
-        ))?;
+        let cache = ProverCache::new(self.domain_size())?;
+        self.prove_with_cache(commit_key, prover_key, &cache)
+    }
+
+    /// Size the evaluation domain must fit, which is big enough to handle
+    /// the circuit as well as the lookup table.
+    fn domain_size(&self) -> usize {
+        core::cmp::max(self.cs.circuit_size(), self.cs.lookup_table_size())
+    }
+
+    /// Creates a [`Proof`] using the FFT precomputations of `cache`, which
+    /// must be built for [`Prover::domain_size`].
+    fn prove_with_cache(
+        &self,
+        commit_key: &CommitKey,
+        prover_key: &ProverKey,
+        cache: &ProverCache,
+    ) -> Result<Proof, Error> {
+        let domain = cache.domain;
+
+        // Catch the wiring bugs which would make the proof fail to verify
+        self.cs.check_public_inputs()?;
+        if self.debug {
+            self.check_witness()?;
+        }
 
         // Since the caller is passing a pre-processed circuit
         // We assume that the Transcript has been seeded with the preprocessed
@@ -175,41 +356,31 @@ impl Prover {
         // 1. Compute witness Polynomials
         //
         // Convert Variables to BlsScalars padding them to the
-        // correct domain size.
-        let pad = vec![BlsScalar::zero(); domain.size() - self.cs.w_l.len()];
-        let w_l_scalar = &[&self.to_scalars(&self.cs.w_l)[..], &pad].concat();
-        let w_r_scalar = &[&self.to_scalars(&self.cs.w_r)[..], &pad].concat();
-        let w_o_scalar = &[&self.to_scalars(&self.cs.w_o)[..], &pad].concat();
-        let w_4_scalar = &[&self.to_scalars(&self.cs.w_4)[..], &pad].concat();
+        // correct domain size. They are then in evaluation form, convert them
+        // to coefficients so that we may commit to them
+        let (w_l_scalar, w_l_poly, w_l_poly_commit) =
+            self.commit_wire(commit_key, &domain, &self.cs.w_l)?;
+        let (w_r_scalar, w_r_poly, w_r_poly_commit) =
+            self.commit_wire(commit_key, &domain, &self.cs.w_r)?;
+        let (w_o_scalar, w_o_poly, w_o_poly_commit) =
+            self.commit_wire(commit_key, &domain, &self.cs.w_o)?;
+        let (w_4_scalar, w_4_poly, w_4_poly_commit) =
+            self.commit_wire(commit_key, &domain, &self.cs.w_4)?;
 
         // make sure q_lookup is also the right size for constructing f
+        let pad = vec![BlsScalar::zero(); domain.size() - self.cs.w_l.len()];
         let padded_q_lookup = [&self.cs.q_lookup[..], &pad].concat();
 
-        // Witnesses are now in evaluation form, convert them to coefficients
-        // So that we may commit to them
-        let w_l_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(w_l_scalar));
-        let w_r_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(w_r_scalar));
-        let w_o_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(w_o_scalar));
-        let w_4_poly =
-            Polynomial::from_coefficients_vec(domain.ifft(w_4_scalar));
-
-        // Commit to witness polynomials
-        let w_l_poly_commit = commit_key.commit(&w_l_poly)?;
-        let w_r_poly_commit = commit_key.commit(&w_r_poly)?;
-        let w_o_poly_commit = commit_key.commit(&w_o_poly)?;
-        let w_4_poly_commit = commit_key.commit(&w_4_poly)?;
-
         // Add witness polynomial commitments to transcript
         transcript.append_commitment(b"w_l", &w_l_poly_commit);
         transcript.append_commitment(b"w_r", &w_r_poly_commit);
         transcript.append_commitment(b"w_o", &w_o_poly_commit);
         transcript.append_commitment(b"w_4", &w_4_poly_commit);
+        log_trace!("committed to wire polynomials");
 
         // Generate table compression factor
         let zeta = transcript.challenge_scalar(b"zeta");
+        log_trace!("derived table compression challenge");
 
         // Compress table into vector of single elements
         let compressed_t_multiset = MultiSet::compress_four_arity(
@@ -231,28 +402,21 @@ impl Prover {
         // When q_lookup[i] is zero the wire value is replaced with a dummy
         // value Currently set as the first row of the public table
         // If q_lookup is one the wire values are preserved
-        let f_1_scalar = w_l_scalar
-            .iter()
-            .zip(&padded_q_lookup)
-            .map(|(w, s)| {
-                w * s + (BlsScalar::one() - s) * compressed_t_multiset.0[0]
-            })
-            .collect::<Vec<BlsScalar>>();
-        let f_2_scalar = w_r_scalar
-            .iter()
-            .zip(&padded_q_lookup)
-            .map(|(w, s)| w * s)
-            .collect::<Vec<BlsScalar>>();
-        let f_3_scalar = w_o_scalar
-            .iter()
-            .zip(&padded_q_lookup)
-            .map(|(w, s)| w * s)
-            .collect::<Vec<BlsScalar>>();
-        let f_4_scalar = w_4_scalar
-            .iter()
-            .zip(&padded_q_lookup)
-            .map(|(w, s)| w * s)
-            .collect::<Vec<BlsScalar>>();
+        #[cfg(feature = "parallel")]
+        let query_column = query_column_parallel;
+        #[cfg(not(feature = "parallel"))]
+        let query_column = query_column_serial;
+        let f_1_scalar = query_column(
+            &w_l_scalar,
+            &padded_q_lookup,
+            compressed_t_multiset.0[0],
+        );
+        let f_2_scalar =
+            query_column(&w_r_scalar, &padded_q_lookup, BlsScalar::zero());
+        let f_3_scalar =
+            query_column(&w_o_scalar, &padded_q_lookup, BlsScalar::zero());
+        let f_4_scalar =
+            query_column(&w_4_scalar, &padded_q_lookup, BlsScalar::zero());
 
         // Compress all wires into a single vector
         let compressed_f_multiset = MultiSet::compress_four_arity(
@@ -275,6 +439,7 @@ impl Prover {
 
         // Add f_poly commitment to transcript
         transcript.append_commitment(b"f", &f_poly_commit);
+        log_trace!("committed to query polynomial");
 
         // 2. Compute permutation polynomial
         //
@@ -286,10 +451,12 @@ impl Prover {
         let gamma = transcript.challenge_scalar(b"gamma");
         let delta = transcript.challenge_scalar(b"delta");
         let epsilon = transcript.challenge_scalar(b"epsilon");
+        log_trace!("derived permutation challenges");
 
         let z_poly = Polynomial::from_coefficients_slice(
-            &self.cs.perm.compute_permutation_poly(
+            &self.cs.perm.compute_permutation_poly_with_roots(
                 &domain,
+                &cache.roots,
                 (&w_l_scalar, &w_r_scalar, &w_o_scalar, &w_4_scalar),
                 &beta,
                 &gamma,
@@ -308,6 +475,7 @@ impl Prover {
 
         // Add commitment to permutation polynomial to transcript
         transcript.append_commitment(b"z", &z_poly_commit);
+        log_trace!("committed to permutation polynomial");
 
         // 3. Compute public inputs polynomial
         let pi_poly = Polynomial::from_coefficients_vec(
@@ -316,11 +484,10 @@ impl Prover {
 
         // Compute evaluation challenge; `z`
         let z_challenge = transcript.challenge_scalar(b"z_challenge");
+        log_trace!("derived evaluation challenge");
 
         // Compute s, as the sorted and concatenated version of f and t
-        let s = compressed_t_multiset
-            .sorted_concat(&compressed_f_multiset)
-            .unwrap();
+        let s = compressed_t_multiset.sorted_concat(&compressed_f_multiset)?;
 
         // Compute first and second halves of s, as h_1 and h_2
         let (h_1, h_2) = s.halve_alternating();
@@ -338,6 +505,7 @@ impl Prover {
         // Add h polynomials to transcript
         transcript.append_commitment(b"h1", &h_1_poly_commit);
         transcript.append_commitment(b"h2", &h_2_poly_commit);
+        log_trace!("committed to sorted table polynomials");
 
         // Compute lookup permutation poly
         let p_poly = Polynomial::from_coefficients_slice(
@@ -358,6 +526,7 @@ impl Prover {
 
         // Add permutation polynomial commitment to transcript
         transcript.append_commitment(b"p", &p_poly_commit);
+        log_trace!("committed to lookup permutation polynomial");
 
         // 4. Compute quotient polynomial
         //
@@ -373,9 +542,11 @@ impl Prover {
             transcript.challenge_scalar(b"variable base separation challenge");
         let lookup_sep_challenge =
             transcript.challenge_scalar(b"lookup challenge");
+        log_trace!("derived quotient challenges");
 
         let t_poly = quotient_poly::compute(
             &domain,
+            &cache.domain_4n,
             &prover_key,
             &z_poly,
             &p_poly,
@@ -415,6 +586,7 @@ impl Prover {
         transcript.append_commitment(b"t_2", &t_2_commit);
         transcript.append_commitment(b"t_3", &t_3_commit);
         transcript.append_commitment(b"t_4", &t_4_commit);
+        log_trace!("committed to quotient polynomial");
 
         // 4. Compute linearisation polynomial
         //
@@ -536,6 +708,7 @@ impl Prover {
             &mut transcript,
         );
         let w_zx_comm = commit_key.commit(&shifted_aggregate_witness)?;
+        log_trace!("computed opening commitments");
 
         // Create Proof
         Ok(Proof {
@@ -561,6 +734,8 @@ impl Prover {
             w_zw_comm: w_zx_comm,
 
             evaluations: evaluations.proof,
+
+            circuit_digest: prover_key.circuit_digest,
         })
     }
 
@@ -582,13 +757,167 @@ impl Prover {
 
         prover_key = self.prover_key.as_ref().unwrap();
 
-        let proof = self.prove_with_preprocessed(commit_key, prover_key)?;
+        // The cache only depends on the domain size, so it is recomputed if
+        // the circuit grew or shrank since the last proof
+        let size = self.domain_size().next_power_of_two();
+        if self.cache.as_ref().map(|cache| cache.domain.size()) != Some(size) {
+            self.cache = Some(ProverCache::new(size)?);
+        }
+        let cache = self.cache.as_ref().unwrap();
+
+        let proof = self.prove_with_cache(commit_key, prover_key, cache)?;
+        log_debug!("proof generated");
 
         // Clear witness and reset composer variables
         self.clear_witness();
 
         Ok(proof)
     }
+
+    /// Same as [`Prover::prove`], but binds the transcript to the hash of
+    /// the Public Inputs into a single scalar, so that the proof must be
+    /// checked with [`Verifier::verify_with_hashed_pi`].
+    ///
+    /// The circuit is preprocessed first if needed, and the hash is only
+    /// appended to the transcript of this proof.
+    ///
+    /// [`Verifier::verify_with_hashed_pi`]:
+    /// crate::proof_system::Verifier::verify_with_hashed_pi
+    pub fn prove_with_hashed_pi(
+        &mut self,
+        commit_key: &CommitKey,
+    ) -> Result<Proof, Error> {
+        if self.prover_key.is_none() {
+            self.preprocess(commit_key)?;
+        }
+
+        let preprocessed_transcript = self.preprocessed_transcript.clone();
+        self.preprocessed_transcript
+            .append_public_inputs_hash(&self.cs.construct_dense_pi_vec());
+        let proof = self.prove(commit_key);
+        self.preprocessed_transcript = preprocessed_transcript;
+
+        proof
+    }
+
+    /// Same as [`Prover::prove`], but only discloses the Public Inputs
+    /// which are not at the gates of `hidden`, the hidden ones being
+    /// committed to in the returned [`CommittedPublicInputs`]. The proof must
+    /// then be checked against [`ProofPublicInputs::Disclosed`] with
+    /// [`Verifier::verify`].
+    ///
+    /// The commitment is bound to the transcript of this proof and opened
+    /// at its evaluation challenge. Returns [`Error::UnknownPublicInput`] if
+    /// there is no Public Input at one of the gates of `hidden`.
+    ///
+    /// [`ProofPublicInputs::Disclosed`]:
+    /// crate::proof_system::ProofPublicInputs::Disclosed
+    /// [`Verifier::verify`]: crate::proof_system::Verifier::verify
+    pub fn prove_with_committed_pi(
+        &mut self,
+        commit_key: &CommitKey,
+        hidden: &[usize],
+    ) -> Result<(Proof, CommittedPublicInputs), Error> {
+        if self.prover_key.is_none() {
+            self.preprocess(commit_key)?;
+        }
+
+        let mut hidden = hidden.to_vec();
+        hidden.sort_unstable();
+        hidden.dedup();
+        if hidden
+            .iter()
+            .any(|pos| !self.cs.public_inputs_sparse_store.contains_key(pos))
+        {
+            return Err(Error::UnknownPublicInput);
+        }
+
+        let domain =
+            EvaluationDomain::new(self.prover_key.as_ref().unwrap().n)?;
+        let quotient = hidden_quotient(
+            &domain,
+            &self.cs.construct_dense_pi_vec(),
+            &hidden,
+        );
+
+        self.prove_with_committed_poly(commit_key, &quotient)
+    }
+
+    /// Proves the circuit with the commitment to `committed_poly` bound to
+    /// the transcript, and opens it at the evaluation challenge of the
+    /// proof.
+    pub(crate) fn prove_with_committed_poly(
+        &mut self,
+        commit_key: &CommitKey,
+        committed_poly: &Polynomial,
+    ) -> Result<(Proof, CommittedPublicInputs), Error> {
+        let commitment = commit_key.commit(committed_poly)?;
+
+        let preprocessed_transcript = self.preprocessed_transcript.clone();
+        self.preprocessed_transcript
+            .append_commitment(b"committed_pi", &commitment);
+        let transcript = self.preprocessed_transcript.clone();
+        let proof = self.prove(commit_key);
+        self.preprocessed_transcript = preprocessed_transcript;
+        let proof = proof?;
+
+        let z_challenge = proof.evaluation_challenge(transcript);
+        let committed = CommittedPublicInputs {
+            commitment,
+            evaluation: committed_poly.evaluate(&z_challenge),
+            witness: commit_key.commit(&committed_poly.ruffini(z_challenge))?,
+        };
+
+        Ok((proof, committed))
+    }
+}
+
+/// Reads the witness value of every variable of `vars`, in a single thread.
+#[cfg(any(not(feature = "parallel"), test))]
+fn witness_values_serial(
+    variables: &HashMap<Variable, BlsScalar>,
+    vars: &[Variable],
+) -> Vec<BlsScalar> {
+    vars.iter().map(|var| variables[var]).collect()
+}
+
+/// Same as [`witness_values_serial`], with the variables split across the
+/// threads of rayon.
+#[cfg(feature = "parallel")]
+fn witness_values_parallel(
+    variables: &HashMap<Variable, BlsScalar>,
+    vars: &[Variable],
+) -> Vec<BlsScalar> {
+    vars.par_iter().map(|var| variables[var]).collect()
+}
+
+/// Computes a column of the query table `f`, keeping the wire value where
+/// `q_lookup` is one and replacing it with `dummy` where it is zero, in a
+/// single thread.
+#[cfg(any(not(feature = "parallel"), test))]
+fn query_column_serial(
+    wire: &[BlsScalar],
+    q_lookup: &[BlsScalar],
+    dummy: BlsScalar,
+) -> Vec<BlsScalar> {
+    wire.iter()
+        .zip(q_lookup)
+        .map(|(w, s)| w * s + (BlsScalar::one() - s) * dummy)
+        .collect()
+}
+
+/// Same as [`query_column_serial`], with the rows split across the threads
+/// of rayon.
+#[cfg(feature = "parallel")]
+fn query_column_parallel(
+    wire: &[BlsScalar],
+    q_lookup: &[BlsScalar],
+    dummy: BlsScalar,
+) -> Vec<BlsScalar> {
+    wire.par_iter()
+        .zip(q_lookup)
+        .map(|(w, s)| w * s + (BlsScalar::one() - s) * dummy)
+        .collect()
 }
 
 /// Computes the quotient opening polynomial.
@@ -625,3 +954,43 @@ pub(crate) fn split_tx_poly(
         Polynomial::from_coefficients_vec(t_x[3 * n..].to_vec()),
     )
 }
+
+#[cfg(feature = "parallel")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_parallel_query_columns() {
+        // Every wire and query column of a circuit is the same whether it's
+        // computed in parallel or not
+        let mut composer = StandardComposer::new();
+        composer.lookup_table.insert_multi_mul(0, 3);
+        let two = composer.add_input(BlsScalar::from(2));
+        let three = composer.add_input(BlsScalar::from(3));
+        let six = composer.add_input(BlsScalar::from(6));
+        let one = composer.add_input(BlsScalar::one());
+        composer.plookup_gate((two, three, six, Some(one)), BlsScalar::zero());
+        (0..64).for_each(|_| {
+            let a = composer.add_input(BlsScalar::random(&mut OsRng));
+            composer.mul(BlsScalar::one(), a, a, BlsScalar::zero(), None);
+        });
+        let dummy = BlsScalar::random(&mut OsRng);
+
+        let wires =
+            [&composer.w_l, &composer.w_r, &composer.w_o, &composer.w_4];
+        wires.iter().enumerate().for_each(|(i, wire)| {
+            let serial = witness_values_serial(&composer.variables, wire);
+            let parallel = witness_values_parallel(&composer.variables, wire);
+            assert_eq!(serial, parallel);
+
+            // Only the first column replaces the wire with a dummy value
+            let dummy = if i == 0 { dummy } else { BlsScalar::zero() };
+            assert_eq!(
+                query_column_serial(&serial, &composer.q_lookup, dummy),
+                query_column_parallel(&parallel, &composer.q_lookup, dummy)
+            );
+        });
+    }
+}
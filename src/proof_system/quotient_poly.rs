@@ -11,13 +11,14 @@ use crate::{
 };
 use alloc::vec::Vec;
 use dusk_bls12_381::BlsScalar;
-#[cfg(feature = "std")]
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 /// Computes the Quotient [`Polynomial`] given the [`EvaluationDomain`], a
 /// [`ProverKey`] and some other info.
 pub(crate) fn compute(
     domain: &EvaluationDomain,
+    domain_4n: &EvaluationDomain,
     prover_key: &ProverKey,
     z_poly: &Polynomial,
     p_poly: &Polynomial,
@@ -59,7 +60,6 @@ pub(crate) fn compute(
     ),
 ) -> Result<Polynomial, Error> {
     // Compute 4n eval of z(X)
-    let domain_4n = EvaluationDomain::new(4 * domain.size())?;
     let mut z_eval_4n = domain_4n.coset_fft(&z_poly);
     z_eval_4n.push(z_eval_4n[0]);
     z_eval_4n.push(z_eval_4n[1]);
@@ -118,6 +118,7 @@ pub(crate) fn compute(
 
     let t_1 = compute_circuit_satisfiability_equation(
         &domain,
+        domain_4n,
         (
             range_challenge,
             logic_challenge,
@@ -139,16 +140,17 @@ pub(crate) fn compute(
 
     let t_2 = compute_permutation_checks(
         domain,
+        domain_4n,
         prover_key,
         (&wl_eval_4n, &wr_eval_4n, &wo_eval_4n, &w4_eval_4n),
         &z_eval_4n,
         (alpha, beta, gamma),
     );
 
-    #[cfg(not(feature = "std"))]
+    #[cfg(not(feature = "parallel"))]
     let range = (0..domain_4n.size()).into_iter();
 
-    #[cfg(feature = "std")]
+    #[cfg(feature = "parallel")]
     let range = (0..domain_4n.size()).into_par_iter();
 
     let quotient: Vec<_> = range
@@ -167,6 +169,7 @@ pub(crate) fn compute(
 // Ensures that the circuit is satisfied
 fn compute_circuit_satisfiability_equation(
     domain: &EvaluationDomain,
+    domain_4n: &EvaluationDomain,
     (
         range_challenge,
         logic_challenge,
@@ -190,7 +193,6 @@ fn compute_circuit_satisfiability_equation(
     h_1_eval_4n: &[BlsScalar],
     h_2_eval_4n: &[BlsScalar],
 ) -> Vec<BlsScalar> {
-    let domain_4n = EvaluationDomain::new(4 * domain.size()).unwrap();
     let public_eval_4n = domain_4n.coset_fft(pi_poly);
 
     let l1_eval_4n = domain_4n.coset_fft(&compute_first_lagrange_poly_scaled(
@@ -198,10 +200,10 @@ fn compute_circuit_satisfiability_equation(
         BlsScalar::one(),
     ));
 
-    #[cfg(not(feature = "std"))]
+    #[cfg(not(feature = "parallel"))]
     let range = (0..domain_4n.size()).into_iter();
 
-    #[cfg(feature = "std")]
+    #[cfg(feature = "parallel")]
     let range = (0..domain_4n.size()).into_par_iter();
 
     let t: Vec<_> = range
@@ -300,6 +302,7 @@ fn compute_circuit_satisfiability_equation(
 
 fn compute_permutation_checks(
     domain: &EvaluationDomain,
+    domain_4n: &EvaluationDomain,
     prover_key: &ProverKey,
     (wl_eval_4n, wr_eval_4n, wo_eval_4n, w4_eval_4n): (
         &[BlsScalar],
@@ -310,15 +313,14 @@ fn compute_permutation_checks(
     z_eval_4n: &[BlsScalar],
     (alpha, beta, gamma): (&BlsScalar, &BlsScalar, &BlsScalar),
 ) -> Vec<BlsScalar> {
-    let domain_4n = EvaluationDomain::new(4 * domain.size()).unwrap();
     let l1_poly_alpha =
         compute_first_lagrange_poly_scaled(domain, alpha.square());
     let l1_alpha_sq_evals = domain_4n.coset_fft(&l1_poly_alpha.coeffs);
 
-    #[cfg(not(feature = "std"))]
+    #[cfg(not(feature = "parallel"))]
     let range = (0..domain_4n.size()).into_iter();
 
-    #[cfg(feature = "std")]
+    #[cfg(feature = "parallel")]
     let range = (0..domain_4n.size()).into_par_iter();
 
     let t: Vec<_> = range
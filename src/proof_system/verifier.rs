@@ -4,14 +4,46 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use crate::commitment_scheme::kzg10::{CommitKey, OpeningKey};
+use crate::commitment_scheme::kzg10::{CommitKey, Commitment, OpeningKey};
 use crate::constraint_system::StandardComposer;
 use crate::error::Error;
+use crate::fft::EvaluationDomain;
+use crate::proof_system::disclosure::{
+    CommittedPublicInputs, ProofPublicInputs, PublicInputSpec,
+};
 use crate::proof_system::widget::VerifierKey;
 use crate::proof_system::Proof;
-use dusk_bls12_381::BlsScalar;
+use crate::transcript::TranscriptProtocol;
+use crate::util::powers_of;
+use alloc::vec::Vec;
+use dusk_bls12_381::{BlsScalar, G1Affine, G1Projective};
 use merlin::Transcript;
 
+/// Quantities that only depend on the size of the circuit and would
+/// otherwise be recomputed on every call to [`Verifier::verify`].
+#[derive(Debug, Clone)]
+pub(crate) struct VerifierCache {
+    /// Evaluation domain of the circuit.
+    pub(crate) domain: EvaluationDomain,
+    /// Powers of the inverse of the domain generator, from `0` to `n - 1`.
+    pub(crate) group_gen_inv_powers: Vec<BlsScalar>,
+}
+
+impl VerifierCache {
+    /// Precomputes the cached quantities for the circuit of the given
+    /// [`VerifierKey`].
+    pub(crate) fn new(verifier_key: &VerifierKey) -> Result<Self, Error> {
+        let domain = EvaluationDomain::new(verifier_key.n)?;
+        let group_gen_inv_powers =
+            powers_of(&domain.group_gen_inv, domain.size() - 1);
+
+        Ok(Self {
+            domain,
+            group_gen_inv_powers,
+        })
+    }
+}
+
 /// Abstraction structure designed verify [`Proof`]s.
 #[allow(missing_debug_implementations)]
 pub struct Verifier {
@@ -25,6 +57,12 @@ pub struct Verifier {
     /// is not copied, then the verification procedure will modify
     /// the transcript, making it unusable for future proofs.
     pub preprocessed_transcript: Transcript,
+
+    /// Whether the key-dependent precomputations should be cached
+    caching: bool,
+    /// Precomputations shared by every verification, see
+    /// [`Verifier::with_cache`]
+    cache: Option<VerifierCache>,
 }
 
 impl Default for Verifier {
@@ -40,6 +78,33 @@ impl Verifier {
             verifier_key: None,
             cs: StandardComposer::new(),
             preprocessed_transcript: Transcript::new(label),
+            caching: false,
+            cache: None,
+        }
+    }
+
+    /// Creates a new `Verifier` instance whose transcript is
+    /// domain-separated by `label` and then keyed with the public `seed`,
+    /// matching [`Prover::with_transcript_seed`].
+    ///
+    /// [`Prover::with_transcript_seed`]:
+    /// crate::proof_system::Prover::with_transcript_seed
+    pub fn with_transcript_seed(label: &'static [u8], seed: &[u8]) -> Verifier {
+        let mut verifier = Verifier::new(label);
+        verifier.key_transcript(b"seed", seed);
+        verifier
+    }
+
+    /// Creates a new `Verifier` instance that caches the quantities which
+    /// only depend on the circuit, such as the evaluation domain and the
+    /// powers of its generator, across calls to [`Verifier::verify`].
+    ///
+    /// The cache is computed in [`Verifier::preprocess`], so verifying many
+    /// proofs for the same circuit amortizes its cost.
+    pub fn with_cache(label: &'static [u8]) -> Verifier {
+        Verifier {
+            caching: true,
+            ..Verifier::new(label)
         }
     }
 
@@ -49,6 +114,8 @@ impl Verifier {
             verifier_key: None,
             cs: StandardComposer::with_expected_size(size),
             preprocessed_transcript: Transcript::new(label),
+            caching: false,
+            cache: None,
         }
     }
 
@@ -71,7 +138,11 @@ impl Verifier {
             &mut self.preprocessed_transcript,
         )?;
 
+        if self.caching {
+            self.cache = Some(VerifierCache::new(&vk)?);
+        }
         self.verifier_key = Some(vk);
+        log_debug!("verifier preprocessing done");
         Ok(())
     }
 
@@ -81,21 +152,222 @@ impl Verifier {
         self.preprocessed_transcript.append_message(label, message);
     }
 
-    /// Verifies a [`Proof`].
-    pub fn verify(
+    /// Verifies a [`Proof`] against its Public Inputs, given either as a
+    /// dense vector or, for a proof generated with
+    /// [`Prover::prove_with_committed_pi`], as a [`PublicInputSpec`] per
+    /// gate along with the [`CommittedPublicInputs`] of the hidden ones, see
+    /// [`ProofPublicInputs`].
+    ///
+    /// Only the revealed Public Inputs are checked by the proof, so the
+    /// commitment of the hidden ones should be checked against a trusted
+    /// one.
+    ///
+    /// [`Prover::prove_with_committed_pi`]:
+    /// crate::proof_system::Prover::prove_with_committed_pi
+    pub fn verify<'a, P>(
+        &self,
+        proof: &Proof,
+        opening_key: &OpeningKey,
+        public_inputs: P,
+    ) -> Result<(), Error>
+    where
+        P: Into<ProofPublicInputs<'a>>,
+    {
+        match public_inputs.into() {
+            ProofPublicInputs::Dense(public_inputs) => self
+                .verify_with_transcript(
+                    proof,
+                    opening_key,
+                    public_inputs,
+                    None,
+                    self.preprocessed_transcript.clone(),
+                ),
+            ProofPublicInputs::Disclosed { specs, committed } => {
+                self.verify_disclosed(proof, opening_key, specs, committed)
+            }
+        }
+    }
+
+    /// Verifies a [`Proof`] generated with
+    /// [`Prover::prove_with_hashed_pi`], whose transcript is bound to the
+    /// hash of `public_inputs` into a single scalar.
+    ///
+    /// The challenges are then derived from the Public Inputs
+    /// themselves, so a proof only verifies with this method and
+    /// [`Verifier::verify`] accepts it only if it was generated with
+    /// [`Prover::prove`]. The Public Inputs are still needed to evaluate
+    /// their polynomial.
+    ///
+    /// [`Prover::prove_with_hashed_pi`]:
+    /// crate::proof_system::Prover::prove_with_hashed_pi
+    /// [`Prover::prove`]: crate::proof_system::Prover::prove
+    pub fn verify_with_hashed_pi(
         &self,
         proof: &Proof,
         opening_key: &OpeningKey,
         public_inputs: &[BlsScalar],
     ) -> Result<(), Error> {
-        let mut cloned_transcript = self.preprocessed_transcript.clone();
+        let mut transcript = self.preprocessed_transcript.clone();
+        transcript.append_public_inputs_hash(public_inputs);
+        self.verify_with_transcript(
+            proof,
+            opening_key,
+            public_inputs,
+            None,
+            transcript,
+        )
+    }
+
+    /// Verifies a [`Proof`] where only the Public Inputs marked
+    /// [`PublicInputSpec::Revealed`] are given in cleartext, the others
+    /// being accounted for by `committed`.
+    fn verify_disclosed(
+        &self,
+        proof: &Proof,
+        opening_key: &OpeningKey,
+        specs: &[PublicInputSpec],
+        committed: &CommittedPublicInputs,
+    ) -> Result<(), Error> {
+        let revealed: Vec<BlsScalar> =
+            specs.iter().map(PublicInputSpec::revealed_value).collect();
+        let hidden: Vec<usize> = specs
+            .iter()
+            .enumerate()
+            .filter(|(_, spec)| **spec == PublicInputSpec::Committed)
+            .map(|(pos, _)| pos)
+            .collect();
+
+        let mut transcript = self.preprocessed_transcript.clone();
+        transcript.append_commitment(b"committed_pi", &committed.commitment);
+        self.verify_with_transcript(
+            proof,
+            opening_key,
+            &revealed,
+            Some((committed, &hidden)),
+            transcript,
+        )
+    }
+
+    /// Verifies a [`Proof`] starting from `transcript`, which must hold the
+    /// preprocessed circuit, along with the Public Inputs hidden at the
+    /// given positions by `committed` if any.
+    fn verify_with_transcript(
+        &self,
+        proof: &Proof,
+        opening_key: &OpeningKey,
+        public_inputs: &[BlsScalar],
+        committed: Option<(&CommittedPublicInputs, &[usize])>,
+        mut cloned_transcript: Transcript,
+    ) -> Result<(), Error> {
         let verifier_key = self.verifier_key.as_ref().unwrap();
+        // The cache only depends on the circuit size, so it is discarded if
+        // the key was replaced by the one of a circuit of a different size
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|cache| cache.domain.size() == verifier_key.n);
 
-        proof.verify(
+        let result = proof.verify(
             verifier_key,
             &mut cloned_transcript,
             opening_key,
             public_inputs,
-        )
+            committed,
+            cache,
+        );
+        match &result {
+            Ok(()) => log_debug!("proof verification succeeded"),
+            Err(e) => log_debug!("proof verification failed: {:?}", e),
+        }
+
+        result
+    }
+
+    /// Verifies a [`Proof`] whose Public Inputs are given by name, in any
+    /// order, see [`StandardComposer::add_public_input_named`].
+    pub fn verify_named<'a, I>(
+        &self,
+        proof: &Proof,
+        opening_key: &OpeningKey,
+        public_inputs: I,
+    ) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = (&'a str, BlsScalar)>,
+    {
+        let public_inputs =
+            self.cs.construct_dense_pi_vec_named(public_inputs)?;
+        self.verify(proof, opening_key, &public_inputs)
+    }
+
+    /// Verifies a batch of [`Proof`]s of the circuit, where the proof
+    /// `proofs[i]` is checked against the public inputs `public_inputs[i]`.
+    ///
+    /// Every check is performed on each proof except for the final pairing:
+    /// the pairing inputs of the proofs are combined with random weights,
+    /// derived from all of them, so that only one pairing is computed for
+    /// the whole batch. The batch fails if any of its proofs is invalid.
+    pub fn verify_batch(
+        &self,
+        proofs: &[Proof],
+        opening_key: &OpeningKey,
+        public_inputs: &[Vec<BlsScalar>],
+    ) -> Result<(), Error> {
+        if proofs.len() != public_inputs.len() {
+            return Err(Error::InputLengthMismatch {
+                left: proofs.len(),
+                right: public_inputs.len(),
+            });
+        }
+        if proofs.is_empty() {
+            return Ok(());
+        }
+
+        let verifier_key = self.verifier_key.as_ref().unwrap();
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|cache| cache.domain.size() == verifier_key.n);
+
+        let mut batch_transcript = Transcript::new(b"verify_batch");
+        let accumulators = proofs
+            .iter()
+            .zip(public_inputs)
+            .map(|(proof, public_inputs)| {
+                let (c, w) = proof.accumulate(
+                    verifier_key,
+                    &mut self.preprocessed_transcript.clone(),
+                    opening_key,
+                    public_inputs,
+                    None,
+                    cache,
+                )?;
+                batch_transcript
+                    .append_commitment(b"c", &Commitment(G1Affine::from(c)));
+                batch_transcript
+                    .append_commitment(b"w", &Commitment(G1Affine::from(w)));
+                Ok((c, w))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // The weights are only known once every accumulator is fixed, so a
+        // failing pairing equation can't cancel out another one
+        let challenge = batch_transcript.challenge_scalar(b"batch weight");
+        let weights = powers_of(&challenge, proofs.len() - 1);
+        let (total_c, total_w) = accumulators.iter().zip(weights).fold(
+            (G1Projective::identity(), G1Projective::identity()),
+            |(total_c, total_w), ((c, w), weight)| {
+                (total_c + c * weight, total_w + w * weight)
+            },
+        );
+
+        let result = opening_key
+            .check_accumulated(total_c, total_w)
+            .map_err(|_| Error::ProofVerificationError);
+        match &result {
+            Ok(()) => log_debug!("batch verification succeeded"),
+            Err(e) => log_debug!("batch verification failed: {:?}", e),
+        }
+
+        result
     }
 }
@@ -11,8 +11,10 @@ pub mod lookup;
 pub mod permutation;
 pub mod range;
 use crate::commitment_scheme::kzg10::Commitment;
+use crate::error::Error;
 use crate::plookup::MultiSet;
 use dusk_bytes::{DeserializableSlice, Serializable};
+use merlin::Transcript;
 
 /// PLONK circuit Verification Key.
 ///
@@ -22,6 +24,12 @@ use dusk_bytes::{DeserializableSlice, Serializable};
 pub struct VerifierKey {
     /// Circuit size (not padded to a power of two).
     pub(crate) n: usize,
+    /// Number of plookup gates of the circuit, besides the dummy ones every
+    /// circuit holds.
+    pub(crate) plookup_gates: usize,
+    /// Number of rows of the lookup table the circuit expects, besides the
+    /// dummy ones every table holds.
+    pub(crate) lookup_table_rows: usize,
     /// VerifierKey for arithmetic gates
     pub(crate) arithmetic: arithmetic::VerifierKey,
     /// VerifierKey for logic gates
@@ -38,7 +46,7 @@ pub struct VerifierKey {
     pub(crate) permutation: permutation::VerifierKey,
 }
 
-impl Serializable<{ 20 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
+impl Serializable<{ 20 * Commitment::SIZE + 3 * u64::SIZE }> for VerifierKey {
     type Error = dusk_bytes::Error;
 
     #[allow(unused_must_use)]
@@ -48,6 +56,8 @@ impl Serializable<{ 20 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
         let mut writer = &mut buff[..];
 
         writer.write(&(self.n as u64).to_bytes());
+        writer.write(&(self.plookup_gates as u64).to_bytes());
+        writer.write(&(self.lookup_table_rows as u64).to_bytes());
         writer.write(&self.arithmetic.q_m.to_bytes());
         writer.write(&self.arithmetic.q_l.to_bytes());
         writer.write(&self.arithmetic.q_r.to_bytes());
@@ -76,6 +86,8 @@ impl Serializable<{ 20 * Commitment::SIZE + u64::SIZE }> for VerifierKey {
         let mut buffer = &buf[..];
 
         Ok(Self::from_polynomial_commitments(
+            u64::from_reader(&mut buffer)? as usize,
+            u64::from_reader(&mut buffer)? as usize,
             u64::from_reader(&mut buffer)? as usize,
             Commitment::from_reader(&mut buffer)?,
             Commitment::from_reader(&mut buffer)?,
@@ -107,11 +119,69 @@ impl VerifierKey {
         self.n.next_power_of_two()
     }
 
+    /// Loads a [`VerifierKey`] exported with [`Serializable::to_bytes`],
+    /// checking that it was preprocessed for a circuit of
+    /// `padded_circuit_size`, as returned by
+    /// [`Circuit::padded_circuit_size`].
+    ///
+    /// Returns [`Error::CircuitSizeMismatch`] if it wasn't. The commitments
+    /// of the key can't be checked against the public parameters without the
+    /// polynomials they commit to, which
+    /// [`ProverKey::from_slice_for_circuit`] does on the prover side.
+    ///
+    /// [`Circuit::padded_circuit_size`]:
+    /// crate::circuit::Circuit::padded_circuit_size
+    /// [`ProverKey::from_slice_for_circuit`]:
+    /// alloc::ProverKey::from_slice_for_circuit
+    pub fn from_slice_for_circuit(
+        bytes: &[u8],
+        padded_circuit_size: usize,
+    ) -> Result<VerifierKey, Error> {
+        let verifier_key = VerifierKey::from_slice(bytes)?;
+        if verifier_key.padded_circuit_size() != padded_circuit_size {
+            return Err(Error::CircuitSizeMismatch {
+                expected: padded_circuit_size,
+                found: verifier_key.padded_circuit_size(),
+            });
+        }
+
+        Ok(verifier_key)
+    }
+
+    /// Returns `true` if the circuit this key was preprocessed from contains
+    /// plookup gates, not counting the dummy ones added by the composer.
+    pub const fn uses_plookup(&self) -> bool {
+        self.plookup_gates > 0
+    }
+
+    /// Returns the number of rows of the lookup table the circuit expects,
+    /// before padding and not counting the dummy ones added by the composer.
+    pub const fn lookup_table_rows(&self) -> usize {
+        self.lookup_table_rows
+    }
+
+    /// Returns a digest that uniquely identifies the circuit this key was
+    /// preprocessed from.
+    ///
+    /// Every [`Proof`](super::Proof) carries the digest of the circuit it was
+    /// generated for, so it can be checked against the verifier key before
+    /// running the verification itself.
+    pub fn circuit_digest(&self) -> [u8; 32] {
+        let mut transcript = Transcript::new(b"circuit digest");
+        transcript.append_message(b"verifier key", &self.to_bytes());
+
+        let mut digest = [0u8; 32];
+        transcript.challenge_bytes(b"digest", &mut digest);
+        digest
+    }
+
     /// Constructs a [`VerifierKey`] from the widget VerifierKey's that are
     /// constructed based on the selector polynomial commitments and the
     /// sigma polynomial commitments.
     pub(crate) fn from_polynomial_commitments(
         n: usize,
+        plookup_gates: usize,
+        lookup_table_rows: usize,
         q_m: Commitment,
         q_l: Commitment,
         q_r: Commitment,
@@ -171,6 +241,8 @@ impl VerifierKey {
 
         VerifierKey {
             n,
+            plookup_gates,
+            lookup_table_rows,
             arithmetic,
             logic,
             range,
@@ -186,7 +258,7 @@ impl VerifierKey {
 pub(crate) mod alloc {
     use super::*;
     use crate::{
-        error::Error,
+        commitment_scheme::kzg10::CommitKey,
         fft::{EvaluationDomain, Evaluations, Polynomial},
         transcript::TranscriptProtocol,
     };
@@ -261,6 +333,8 @@ pub(crate) mod alloc {
         // in their evaluation phase and divide by the quotient
         // polynomial without having to perform IFFT
         pub(crate) v_h_coset_4n: Evaluations,
+        /// Digest of the circuit, see [`VerifierKey::circuit_digest`]
+        pub(crate) circuit_digest: [u8; 32],
     }
 
     #[cfg(feature = "alloc")]
@@ -303,7 +377,8 @@ pub(crate) mod alloc {
                 (Self::num_polys() * poly_size
                     + evals_size * Self::num_evals()
                     + multiset_size * Self::num_multiset()
-                    + 17 * u64::SIZE) as usize
+                    + 17 * u64::SIZE
+                    + 32) as usize
             ];
 
             let mut writer = &mut bytes[..];
@@ -426,6 +501,8 @@ pub(crate) mod alloc {
 
             writer.write(&self.v_h_coset_4n.to_var_bytes());
 
+            writer.write(&self.circuit_digest);
+
             bytes
         }
 
@@ -571,6 +648,12 @@ pub(crate) mod alloc {
 
             let v_h_coset_4n = evals_from_reader(&mut buffer)?;
 
+            if buffer.len() < 32 {
+                return Err(Error::NotEnoughBytes);
+            }
+            let mut circuit_digest = [0u8; 32];
+            circuit_digest.copy_from_slice(&buffer[..32]);
+
             let arithmetic = arithmetic::ProverKey {
                 q_m,
                 q_l: q_l.clone(),
@@ -625,11 +708,55 @@ pub(crate) mod alloc {
                 lookup,
                 permutation,
                 v_h_coset_4n,
+                circuit_digest,
             };
 
             Ok(prover_key)
         }
 
+        /// Loads a [`ProverKey`] exported with [`ProverKey::to_var_bytes`],
+        /// checking that it can prove with `commit_key` the circuit of
+        /// `verifier_key`, so that a prover can skip the preprocessing.
+        ///
+        /// Returns [`Error::SrsTooSmall`] if `commit_key` can't commit to the
+        /// polynomials of the circuit, [`Error::CircuitSizeMismatch`] if the
+        /// key was preprocessed for another circuit size, and
+        /// [`Error::KeyMismatch`] if it wasn't preprocessed along with
+        /// `verifier_key` out of the same public parameters as `commit_key`.
+        /// The last check commits to one of the polynomials of the key again.
+        pub fn from_slice_for_circuit(
+            bytes: &[u8],
+            commit_key: &CommitKey,
+            verifier_key: &VerifierKey,
+        ) -> Result<ProverKey, Error> {
+            let prover_key = ProverKey::from_slice(bytes)?;
+
+            let n = prover_key.n;
+            if commit_key.max_degree() < n {
+                return Err(Error::SrsTooSmall {
+                    degree: commit_key.max_degree(),
+                    required: n,
+                });
+            }
+            if n != verifier_key.n
+                || prover_key.v_h_coset_4n.evals.len() != 4 * n
+            {
+                return Err(Error::CircuitSizeMismatch {
+                    expected: verifier_key.n,
+                    found: n,
+                });
+            }
+
+            if prover_key.circuit_digest != verifier_key.circuit_digest()
+                || commit_key.commit(&prover_key.permutation.left_sigma.0)?
+                    != verifier_key.permutation.left_sigma
+            {
+                return Err(Error::KeyMismatch);
+            }
+
+            Ok(prover_key)
+        }
+
         pub(crate) fn v_h_coset_4n(&self) -> &Evaluations {
             &self.v_h_coset_4n
         }
@@ -757,6 +884,7 @@ mod test {
             lookup,
             permutation,
             v_h_coset_4n,
+            circuit_digest: [0xab; 32],
         };
 
         let prover_key_bytes = prover_key.to_var_bytes();
@@ -840,6 +968,8 @@ mod test {
 
         let verifier_key = VerifierKey {
             n,
+            plookup_gates: 3,
+            lookup_table_rows: 10,
             arithmetic,
             logic,
             range,
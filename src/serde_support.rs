@@ -0,0 +1,266 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! `serde` implementations of the types which don't derive them.
+//!
+//! Byte encodings, such as the ones of the scalars, are written as hex
+//! strings in human-readable formats and as raw bytes in binary ones.
+
+use crate::proof_system::Proof;
+use core::fmt;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::Serializable;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// Displays a byte slice as lowercase hex.
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+/// Serializes `bytes` as hex if the format is human-readable, and as raw
+/// bytes otherwise.
+fn serialize_bytes<S: Serializer>(
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Hex(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserializes `N` bytes encoded by [`serialize_bytes`].
+fn deserialize_bytes<'de, D: Deserializer<'de>, const N: usize>(
+    deserializer: D,
+) -> Result<[u8; N], D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor::<N>)
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor::<N>)
+    }
+}
+
+struct BytesVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bytes, or a hex string encoding them", N)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.len() != 2 * N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        let mut bytes = [0u8; N];
+        for (byte, pair) in bytes.iter_mut().zip(v.as_bytes().chunks(2)) {
+            let pair = core::str::from_utf8(pair)
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))?;
+            *byte = u8::from_str_radix(pair, 16)
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))?;
+        }
+
+        Ok(bytes)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() != N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(v);
+        Ok(bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0u8; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// A [`BlsScalar`] encoded with [`serialize_bytes`].
+pub(crate) struct Scalar(pub(crate) BlsScalar);
+
+impl Serialize for Scalar {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scalar {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes::<D, { BlsScalar::SIZE }>(deserializer)?;
+        BlsScalar::from_bytes(&bytes)
+            .map(Scalar)
+            .map_err(|_| de::Error::custom("BLS scalar bytes malformed"))
+    }
+}
+
+impl Serialize for Proof {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Proof {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let bytes = deserialize_bytes::<D, { Proof::SIZE }>(deserializer)?;
+        Proof::from_bytes(&bytes)
+            .map_err(|_| de::Error::custom("proof bytes malformed"))
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc {
+    use super::*;
+    use crate::plookup::PlookupTable4Arity;
+    use ::alloc::vec::Vec;
+    use serde::ser::SerializeSeq;
+
+    impl Serialize for PlookupTable4Arity {
+        fn serialize<S: Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+            for row in &self.0 {
+                let [a, b, c, d] = *row;
+                seq.serialize_element(&[
+                    Scalar(a),
+                    Scalar(b),
+                    Scalar(c),
+                    Scalar(d),
+                ])?;
+            }
+            seq.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PlookupTable4Arity {
+        fn deserialize<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            deserializer.deserialize_seq(TableVisitor)
+        }
+    }
+
+    struct TableVisitor;
+
+    impl<'de> Visitor<'de> for TableVisitor {
+        type Value = PlookupTable4Arity;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a sequence of rows of four BLS scalars")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(
+            self,
+            mut seq: A,
+        ) -> Result<Self::Value, A::Error> {
+            let mut rows = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some([a, b, c, d]) = seq.next_element::<[Scalar; 4]>()? {
+                rows.push([a.0, b.0, c.0, d.0]);
+            }
+
+            Ok(PlookupTable4Arity::from(rows))
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraint_system::Variable;
+    use crate::plookup::PlookupTable4Arity;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_serde_variable() {
+        let var = Variable(42);
+
+        let json = serde_json::to_string(&var).unwrap();
+        assert_eq!(serde_json::from_str::<Variable>(&json).unwrap(), var);
+
+        let bytes = bincode::serialize(&var).unwrap();
+        assert_eq!(bincode::deserialize::<Variable>(&bytes).unwrap(), var);
+    }
+
+    #[test]
+    fn test_serde_proof() {
+        let proof = Proof {
+            circuit_digest: [0xcd; 32],
+            ..Proof::default()
+        };
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(json.len(), 2 * Proof::SIZE + 2);
+        assert_eq!(serde_json::from_str::<Proof>(&json).unwrap(), proof);
+
+        let bytes = bincode::serialize(&proof).unwrap();
+        assert_eq!(bincode::deserialize::<Proof>(&bytes).unwrap(), proof);
+    }
+
+    #[test]
+    fn test_serde_table() {
+        let mut table = PlookupTable4Arity::new();
+        table.insert_multi_mul(0, 3);
+        table.0.push([
+            BlsScalar::random(&mut OsRng),
+            BlsScalar::random(&mut OsRng),
+            BlsScalar::random(&mut OsRng),
+            -BlsScalar::one(),
+        ]);
+
+        let json = serde_json::to_string(&table).unwrap();
+        let got: PlookupTable4Arity = serde_json::from_str(&json).unwrap();
+        assert_eq!(got, table);
+
+        let bytes = bincode::serialize(&table).unwrap();
+        let got: PlookupTable4Arity = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(got, table);
+
+        // Scalars out of the field are rejected
+        let zero = "00".repeat(32);
+        let json = format!(
+            "[[\"{}\",\"{}\",\"{}\",\"{}\"]]",
+            "ff".repeat(32),
+            zero,
+            zero,
+            zero
+        );
+        assert!(serde_json::from_str::<PlookupTable4Arity>(&json).is_err());
+    }
+}
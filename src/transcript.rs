@@ -25,14 +25,36 @@ pub(crate) trait TranscriptProtocol {
 
     /// Append domain separator for the circuit size.
     fn circuit_domain_sep(&mut self, n: u64);
+
+    /// Append the hash of `public_inputs`, see [`hash_public_inputs`].
+    fn append_public_inputs_hash(&mut self, public_inputs: &[BlsScalar]);
+}
+
+/// Hashes `public_inputs` into a single scalar with a dedicated
+/// [`Transcript`], so that they can be bound to the transcript of a proof
+/// with a single append.
+pub(crate) fn hash_public_inputs(public_inputs: &[BlsScalar]) -> BlsScalar {
+    let mut transcript = Transcript::new(b"public inputs");
+    transcript.append_u64(b"len", public_inputs.len() as u64);
+    public_inputs
+        .iter()
+        .for_each(|pi| transcript.append_message(b"pi", &pi.to_bytes()));
+
+    let mut buf = [0u8; 64];
+    transcript.challenge_bytes(b"hash", &mut buf);
+    BlsScalar::from_bytes_wide(&buf)
 }
 
 impl TranscriptProtocol for Transcript {
     fn append_commitment(&mut self, label: &'static [u8], comm: &Commitment) {
+        #[cfg(feature = "transcript-debug")]
+        debug::record_append(label, &comm.0.to_bytes());
         self.append_message(label, &comm.0.to_bytes());
     }
 
     fn append_scalar(&mut self, label: &'static [u8], s: &BlsScalar) {
+        #[cfg(feature = "transcript-debug")]
+        debug::record_append(label, &s.to_bytes());
         self.append_message(label, &s.to_bytes())
     }
 
@@ -40,11 +62,161 @@ impl TranscriptProtocol for Transcript {
         let mut buf = [0u8; 64];
         self.challenge_bytes(label, &mut buf);
 
-        BlsScalar::from_bytes_wide(&buf)
+        let challenge = BlsScalar::from_bytes_wide(&buf);
+        #[cfg(feature = "transcript-debug")]
+        debug::record_challenge(label, challenge);
+        challenge
     }
 
     fn circuit_domain_sep(&mut self, n: u64) {
+        #[cfg(feature = "transcript-debug")]
+        {
+            debug::record_append(b"dom-sep", b"circuit_size");
+            debug::record_append(b"n", &n.to_le_bytes());
+        }
         self.append_message(b"dom-sep", b"circuit_size");
         self.append_u64(b"n", n);
     }
+
+    fn append_public_inputs_hash(&mut self, public_inputs: &[BlsScalar]) {
+        self.append_scalar(b"pi_hash", &hash_public_inputs(public_inputs));
+    }
+}
+
+/// Recording of the transcript operations, so the transcripts of a prover
+/// and a verifier can be compared to find where they diverge.
+#[cfg(feature = "transcript-debug")]
+pub mod debug {
+    use dusk_bls12_381::BlsScalar;
+    use std::cell::RefCell;
+    use std::vec::Vec;
+
+    /// Operation done on a transcript.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum TranscriptEntry {
+        /// A message appended with the given label.
+        Append {
+            /// Label of the message
+            label: &'static [u8],
+            /// Bytes of the message
+            message: Vec<u8>,
+        },
+        /// A challenge derived with the given label.
+        Challenge {
+            /// Label of the challenge
+            label: &'static [u8],
+            /// Value of the challenge
+            value: BlsScalar,
+        },
+    }
+
+    std::thread_local! {
+        static RECORD: RefCell<Option<Vec<TranscriptEntry>>> =
+            RefCell::new(None);
+    }
+
+    /// Runs `f`, returning its result along with every transcript operation
+    /// done by the crate on the current thread while it ran.
+    ///
+    /// The operations of all the transcripts used by `f` are recorded, in
+    /// the order they happen. Messages appended directly through
+    /// [`merlin::Transcript`], such as the ones of `key_transcript`, are not
+    /// recorded.
+    pub fn record<R, F>(f: F) -> (R, Vec<TranscriptEntry>)
+    where
+        F: FnOnce() -> R,
+    {
+        let previous =
+            RECORD.with(|record| record.borrow_mut().replace(Vec::new()));
+        let result = f();
+        let entries = RECORD.with(|record| {
+            core::mem::replace(&mut *record.borrow_mut(), previous)
+        });
+
+        (result, entries.unwrap_or_default())
+    }
+
+    /// Returns the index of the first entry where two recordings differ, or
+    /// `None` if they are equal.
+    ///
+    /// If one recording is a prefix of the other, the index is the length
+    /// of the shortest one.
+    pub fn first_divergence(
+        a: &[TranscriptEntry],
+        b: &[TranscriptEntry],
+    ) -> Option<usize> {
+        a.iter()
+            .zip(b.iter())
+            .position(|(a, b)| a != b)
+            .or_else(|| match a.len() == b.len() {
+                true => None,
+                false => Some(core::cmp::min(a.len(), b.len())),
+            })
+    }
+
+    fn push(entry: TranscriptEntry) {
+        RECORD.with(|record| {
+            if let Some(entries) = record.borrow_mut().as_mut() {
+                entries.push(entry);
+            }
+        });
+    }
+
+    pub(crate) fn record_append(label: &'static [u8], message: &[u8]) {
+        push(TranscriptEntry::Append {
+            label,
+            message: message.to_vec(),
+        });
+    }
+
+    pub(crate) fn record_challenge(label: &'static [u8], value: BlsScalar) {
+        push(TranscriptEntry::Challenge { label, value });
+    }
+}
+
+#[cfg(all(test, feature = "transcript-debug"))]
+mod tests {
+    use super::debug::{first_divergence, record, TranscriptEntry};
+    use crate::commitment_scheme::kzg10::PublicParameters;
+    use crate::constraint_system::helper::dummy_gadget;
+    use crate::proof_system::{Prover, Verifier};
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_transcript_divergence() {
+        let public_parameters =
+            PublicParameters::setup(2 * 30, &mut OsRng).unwrap();
+        let (ck, vk) = public_parameters.trim(2 * 20).unwrap();
+
+        let mut prover = Prover::new(b"demo");
+        dummy_gadget(10, prover.mut_cs());
+        prover.preprocess(&ck).unwrap();
+        let public_inputs = prover.cs.construct_dense_pi_vec();
+        let (proof, prover_record) = record(|| prover.prove(&ck).unwrap());
+
+        let mut verifier = Verifier::new(b"demo");
+        dummy_gadget(10, verifier.mut_cs());
+        verifier.preprocess(&ck).unwrap();
+
+        let (result, _) =
+            record(|| verifier.verify(&proof, &vk, &public_inputs));
+        assert!(result.is_ok());
+
+        // Replacing the query commitment makes the transcripts diverge when
+        // it is absorbed
+        let mut tampered = proof.clone();
+        tampered.f_comm = tampered.a_comm;
+        let (result, verifier_record) =
+            record(|| verifier.verify(&tampered, &vk, &public_inputs));
+        assert!(result.is_err());
+
+        let index = first_divergence(&prover_record, &verifier_record)
+            .expect("tampered transcripts must diverge");
+        match &verifier_record[index] {
+            TranscriptEntry::Append { label, .. } => {
+                assert_eq!(*label, &b"f"[..])
+            }
+            entry => panic!("unexpected entry {:?}", entry),
+        }
+    }
 }
@@ -54,7 +54,14 @@ pub(crate) fn slow_multiscalar_mul_single_base(
 // while we do not have batch inversion for scalars
 use core::ops::MulAssign;
 
-pub fn batch_inversion(v: &mut [BlsScalar]) {
+/// Replaces every scalar of `v` by its inverse, with a single field
+/// inversion for the whole slice, which makes regenerating constant tables
+/// such as [`INVERSES_S_I`] cheap. Zeros have no inverse and are left as
+/// zero.
+///
+/// [`INVERSES_S_I`]:
+/// crate::plookup::table::hash_tables::constants::INVERSES_S_I
+pub fn batch_invert(v: &mut [BlsScalar]) {
     // Montgomery’s Trick and Fast Implementation of Masked AES
     // Genelle, Prouff and Quisquater
     // Section 3.2
@@ -90,7 +97,7 @@ pub fn batch_inversion(v: &mut [BlsScalar]) {
 mod test {
     use super::*;
     #[test]
-    fn test_batch_inversion() {
+    fn test_batch_invert() {
         let one = BlsScalar::from(1);
         let two = BlsScalar::from(2);
         let three = BlsScalar::from(3);
@@ -100,9 +107,24 @@ mod test {
         let original_scalars = vec![one, two, three, four, five];
         let mut inverted_scalars = vec![one, two, three, four, five];
 
-        batch_inversion(&mut inverted_scalars);
+        batch_invert(&mut inverted_scalars);
         for (x, x_inv) in original_scalars.iter().zip(inverted_scalars.iter()) {
             assert_eq!(x.invert().unwrap(), *x_inv);
         }
+
+        // Zeros are skipped without breaking the inverses around them
+        let original_scalars: Vec<BlsScalar> = (0..40u64)
+            .map(|k| BlsScalar::from(k * k * 7919 % 257))
+            .collect();
+        let mut inverted_scalars = original_scalars.clone();
+        batch_invert(&mut inverted_scalars);
+        for (x, x_inv) in original_scalars.iter().zip(inverted_scalars.iter()) {
+            assert_eq!(x.invert().unwrap_or(BlsScalar::zero()), *x_inv);
+        }
+
+        let mut zeros = vec![BlsScalar::zero(); 3];
+        batch_invert(&mut zeros);
+        assert_eq!(zeros, vec![BlsScalar::zero(); 3]);
+        batch_invert(&mut []);
     }
 }
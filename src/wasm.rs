@@ -0,0 +1,249 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! WebAssembly entry points to prove and verify the Zelbet hash circuit in a
+//! browser, without threads nor filesystem.
+//!
+//! The circuit is compiled natively, for a given number of inputs, by
+//! [`compile_zelbet_hash`], which returns the bytes of the prover and
+//! verifier sides of the circuit. These are passed, along with the inputs, to
+//! [`prove_circuit`] and [`verify_proof`], which are exported to JavaScript
+//! with `wasm-bindgen`.
+//!
+//! The module is built with the `wasm` feature, which should be used
+//! without the default features on `wasm32-unknown-unknown`, so that no
+//! thread pool is spawned.
+
+use crate::circuit::{Circuit, PublicInputs, VerifierData};
+use crate::commitment_scheme::kzg10::{
+    CommitKey, OpeningKey, PublicParameters,
+};
+use crate::constraint_system::{StandardComposer, Variable};
+use crate::error::Error;
+use crate::plookup::PlookupTable4Arity;
+use crate::proof_system::{Proof, Prover, ProverKey, Verifier};
+use alloc::format;
+use alloc::vec::Vec;
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::{DeserializableSlice, Serializable};
+use wasm_bindgen::prelude::*;
+
+/// Label of the transcripts of the proofs of the Zelbet hash circuit
+const TRANSCRIPT_LABEL: &[u8] = b"zelbet-hash";
+
+/// Circuit proving the knowledge of the preimage of a Zelbet sponge digest,
+/// see [`StandardComposer::zelbet_sponge_hash`]. The digest is the only
+/// public input of the circuit.
+#[derive(Debug, Clone)]
+pub struct ZelbetHashCircuit {
+    inputs: Vec<BlsScalar>,
+    padded_size: usize,
+}
+
+impl ZelbetHashCircuit {
+    /// Creates the circuit hashing `inputs`.
+    pub fn new(inputs: Vec<BlsScalar>) -> Self {
+        let mut circuit = ZelbetHashCircuit {
+            inputs,
+            padded_size: 0,
+        };
+
+        let mut composer = StandardComposer::new();
+        circuit.digest_gadget(&mut composer);
+        // The commit key is bounded by twice the domain, as in the gadget
+        // testers
+        circuit.padded_size = 2 * composer.next_power_of_two_size();
+
+        circuit
+    }
+
+    /// Hashes the inputs and binds the digest to a public input, returning
+    /// the digest.
+    fn digest_gadget(&self, composer: &mut StandardComposer) -> Variable {
+        composer.append_lookup_table(&PlookupTable4Arity::create_hash_table());
+        let inputs: Vec<Variable> = self
+            .inputs
+            .iter()
+            .map(|input| composer.add_input(*input))
+            .collect();
+
+        let digest = composer.zelbet_sponge_hash(&inputs);
+        let value = composer.variables[&digest];
+        composer.constrain_to_constant(digest, BlsScalar::zero(), Some(-value));
+
+        digest
+    }
+}
+
+impl Circuit for ZelbetHashCircuit {
+    const CIRCUIT_ID: [u8; 32] = [0x2e; 32];
+
+    fn gadget(&mut self, composer: &mut StandardComposer) -> Result<(), Error> {
+        self.digest_gadget(composer);
+        Ok(())
+    }
+
+    fn padded_circuit_size(&self) -> usize {
+        self.padded_size
+    }
+}
+
+/// Compiles the Zelbet hash circuit of `inputs` inputs, returning the bytes
+/// of its prover side, for [`prove_circuit`], and of its verifier side, for
+/// [`verify_proof`].
+///
+/// The prover side holds the number of inputs and the commit and prover
+/// keys, while the verifier side holds the opening key and the
+/// [`VerifierData`] of the circuit.
+pub fn compile_zelbet_hash(
+    pub_params: &PublicParameters,
+    inputs: usize,
+) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let mut circuit = ZelbetHashCircuit::new(vec![BlsScalar::zero(); inputs]);
+    let (prover_key, verifier_data) = circuit.compile(pub_params)?;
+    let (commit_key, opening_key) =
+        pub_params.trim(circuit.padded_circuit_size())?;
+
+    let commit_key = commit_key.to_var_bytes();
+    let mut prover_bytes = Vec::new();
+    prover_bytes.extend_from_slice(&(inputs as u32).to_bytes());
+    prover_bytes.extend_from_slice(&(commit_key.len() as u32).to_bytes());
+    prover_bytes.extend_from_slice(&commit_key);
+    prover_bytes.extend_from_slice(&prover_key.to_var_bytes());
+
+    let mut verifier_bytes = opening_key.to_bytes().to_vec();
+    verifier_bytes.extend_from_slice(&verifier_data.to_var_bytes());
+
+    Ok((prover_bytes, verifier_bytes))
+}
+
+/// Proves the knowledge of the preimage `witness`, given as the
+/// concatenation of the bytes of the input [`BlsScalar`]s, of a Zelbet
+/// digest, using the prover side `circuit_bytes` of
+/// [`compile_zelbet_hash`].
+///
+/// Returns the bytes of the [`Proof`] followed by the ones of the digest.
+pub fn prove(circuit_bytes: &[u8], witness: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut buf = circuit_bytes;
+    let inputs = u32::from_reader(&mut buf)? as usize;
+    let commit_key_len = u32::from_reader(&mut buf)? as usize;
+    if buf.len() < commit_key_len {
+        return Err(Error::NotEnoughBytes);
+    }
+    let commit_key = CommitKey::from_slice(&buf[..commit_key_len])?;
+    let prover_key = ProverKey::from_slice(&buf[commit_key_len..])?;
+
+    let witness = witness
+        .chunks(BlsScalar::SIZE)
+        .map(BlsScalar::from_slice)
+        .collect::<Result<Vec<_>, _>>()?;
+    if witness.len() != inputs {
+        return Err(Error::InputLengthMismatch {
+            left: witness.len(),
+            right: inputs,
+        });
+    }
+
+    let circuit = ZelbetHashCircuit {
+        inputs: witness,
+        padded_size: 0,
+    };
+    let mut prover = Prover::new(TRANSCRIPT_LABEL);
+    let digest = circuit.digest_gadget(prover.mut_cs());
+    let digest = prover.cs.variables[&digest];
+    prover.prover_key = Some(prover_key);
+    let proof = prover.prove(&commit_key)?;
+
+    let mut bytes = proof.to_bytes().to_vec();
+    bytes.extend_from_slice(&digest.to_bytes());
+    Ok(bytes)
+}
+
+/// Verifies the output `proof` of [`prove`], using the verifier side
+/// `verifier_bytes` of [`compile_zelbet_hash`].
+pub fn verify(verifier_bytes: &[u8], proof: &[u8]) -> Result<(), Error> {
+    let mut buf = verifier_bytes;
+    let opening_key = OpeningKey::from_reader(&mut buf)?;
+    let verifier_data = VerifierData::from_slice(buf)?;
+
+    let mut buf = proof;
+    let proof = Proof::from_reader(&mut buf)?;
+    let digest = BlsScalar::from_reader(&mut buf)?;
+
+    let mut verifier = Verifier::new(TRANSCRIPT_LABEL);
+    verifier.verifier_key = Some(verifier_data.key().clone());
+    let public_inputs =
+        PublicInputs::from_values(&[digest.into()], verifier_data.pi_pos())
+            .to_dense_pi_vec(verifier_data.key().padded_circuit_size())?;
+
+    verifier.verify(&proof, &opening_key, &public_inputs)
+}
+
+/// JavaScript binding of [`prove`], throwing the error on failure.
+#[wasm_bindgen]
+pub fn prove_circuit(
+    circuit_bytes: &[u8],
+    witness: &[u8],
+) -> Result<Vec<u8>, JsValue> {
+    prove(circuit_bytes, witness).map_err(to_js_error)
+}
+
+/// JavaScript binding of [`verify`], returning `false` if the proof doesn't
+/// verify and throwing the error if the bytes are malformed.
+#[wasm_bindgen]
+pub fn verify_proof(
+    verifier_bytes: &[u8],
+    proof: &[u8],
+) -> Result<bool, JsValue> {
+    match verify(verifier_bytes, proof) {
+        Ok(()) => Ok(true),
+        Err(Error::ProofVerificationError) => Ok(false),
+        Err(e) => Err(to_js_error(e)),
+    }
+}
+
+fn to_js_error(error: Error) -> JsValue {
+    JsValue::from_str(&format!("{:?}", error))
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_prove_verify() {
+        let inputs = [BlsScalar::from(7), -BlsScalar::from(3)];
+        let circuit = ZelbetHashCircuit::new(inputs.to_vec());
+        let pub_params =
+            PublicParameters::setup(circuit.padded_circuit_size(), &mut OsRng)
+                .unwrap();
+        let (prover_bytes, verifier_bytes) =
+            compile_zelbet_hash(&pub_params, inputs.len()).unwrap();
+
+        let witness: Vec<u8> = inputs
+            .iter()
+            .flat_map(|input| input.to_bytes().to_vec())
+            .collect();
+        let mut proof = prove(&prover_bytes, &witness).unwrap();
+        assert_eq!(proof.len(), Proof::SIZE + BlsScalar::SIZE);
+        assert!(verify(&verifier_bytes, &proof).is_ok());
+
+        // The digest is the public input of the proof
+        proof[Proof::SIZE] ^= 1;
+        assert!(matches!(
+            verify(&verifier_bytes, &proof),
+            Err(Error::ProofVerificationError)
+        ));
+
+        // The witness must hold as many inputs as the circuit
+        assert!(matches!(
+            prove(&prover_bytes, &witness[..BlsScalar::SIZE]),
+            Err(Error::InputLengthMismatch { left: 1, right: 2 })
+        ));
+    }
+}